@@ -0,0 +1,39 @@
+// CRT filter for the upscaled canvas: curved glass, scanlines, a dim border
+// and a little flickering noise.
+
+#import bevy_sprite::mesh2d_vertex_output::VertexOutput
+#import bevy_sprite::mesh2d_view_bindings::globals
+
+// xy: size of the canvas in pixels, one scanline per pixel row
+@group(2) @binding(0) var<uniform> source_size: vec4<f32>;
+@group(2) @binding(1) var canvas_texture: texture_2d<f32>;
+@group(2) @binding(2) var canvas_sampler: sampler;
+
+const PI: f32 = 3.14159265;
+const CURVATURE: f32 = 0.06;
+const SCANLINE_STRENGTH: f32 = 0.3;
+const NOISE_STRENGTH: f32 = 0.04;
+const VIGNETTE_STRENGTH: f32 = 0.35;
+
+fn hash(point: vec2<f32>) -> f32 {
+    return fract(sin(dot(point, vec2(12.9898, 78.233))) * 43758.5453);
+}
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    // Push the picture out towards the corners, like the bulge of a tube
+    let centered = in.uv * 2.0 - 1.0;
+    let bent = centered + centered * (centered.yx * centered.yx) * CURVATURE;
+    let uv = bent * 0.5 + 0.5;
+    if any(uv < vec2(0.0)) || any(uv > vec2(1.0)) {
+        return vec4(0.0, 0.0, 0.0, 1.0);
+    }
+
+    var color = textureSample(canvas_texture, canvas_sampler, uv).rgb;
+    let scanline = 0.5 + 0.5 * cos(uv.y * source_size.y * 2.0 * PI);
+    color *= 1.0 - SCANLINE_STRENGTH * scanline;
+    color += (hash(floor(uv * source_size.xy) + fract(globals.time)) - 0.5) * NOISE_STRENGTH;
+    let edge = length(bent) / sqrt(2.0);
+    color *= 1.0 - VIGNETTE_STRENGTH * edge * edge;
+    return vec4(color, 1.0);
+}
@@ -0,0 +1,66 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::GameState;
+use crate::practice::practice_enabled;
+use crate::stats::RunStats;
+
+// Adaptive difficulty constants
+const TRACKED_RUNS: usize = 5; // Runs the rolling performance metric covers
+const TARGET_RUN_SECONDS: f32 = 30.0; // Run length the adjuster steers toward
+const MIN_SPAWN_SCALE: f32 = 0.6; // Fastest spawning, relative to the preset
+const MAX_SPAWN_SCALE: f32 = 1.6; // Gentlest spawning, relative to the preset
+const CRUISE_RAMP: f32 = 0.02; // Spawn scale shaved off per second past the usual run length
+
+// --- Resources ---
+
+/// Rolling record of recent run lengths used to rubber-band the spawn rate
+/// when the adaptive difficulty setting is on.
+#[derive(Resource, Clone, Default, Serialize, Deserialize)]
+pub struct AdaptiveDifficulty {
+    recent_runs: VecDeque<f32>,
+}
+
+impl AdaptiveDifficulty {
+    /// Average length of the recent runs, or the target before any were played.
+    fn typical_run(&self) -> f32 {
+        if self.recent_runs.is_empty() {
+            return TARGET_RUN_SECONDS;
+        }
+        self.recent_runs.iter().sum::<f32>() / self.recent_runs.len() as f32
+    }
+
+    /// Multiplier for the preset's spawn interval. Quick deaths stretch it,
+    /// and outlasting the usual run length tightens it as the run goes on.
+    pub fn spawn_scale(&self, elapsed: f32) -> f32 {
+        let typical = self.typical_run();
+        let base = TARGET_RUN_SECONDS / typical.max(1.0);
+        let cruising = (elapsed - typical).max(0.0) * CRUISE_RAMP;
+        (base - cruising).clamp(MIN_SPAWN_SCALE, MAX_SPAWN_SCALE)
+    }
+
+    fn record(&mut self, seconds: f32) {
+        self.recent_runs.push_back(seconds);
+        if self.recent_runs.len() > TRACKED_RUNS {
+            self.recent_runs.pop_front();
+        }
+    }
+}
+
+pub struct AdaptivePlugin;
+
+impl Plugin for AdaptivePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AdaptiveDifficulty>().add_systems(
+            OnEnter(GameState::GameOver),
+            record_run_length.run_if(not(practice_enabled)),
+        );
+    }
+}
+
+/// System that feeds the finished run into the rolling metric
+fn record_run_length(stats: Res<RunStats>, mut adaptive: ResMut<AdaptiveDifficulty>) {
+    adaptive.record(stats.elapsed());
+}
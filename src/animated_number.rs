@@ -0,0 +1,81 @@
+use bevy::prelude::*;
+
+// Animated number constants
+const COUNT_RATE: f32 = 8.0; // How quickly the shown value closes in on the target, per second
+const PULSE_DURATION: f32 = 0.35; // Seconds a pulse takes to settle
+const PULSE_SCALE: f32 = 0.4; // Extra font size at the peak of a pulse, as a fraction
+
+// --- Components ---
+
+/// A number in a UI text that counts toward its value instead of jumping, and
+/// pulses when it gains a lot at once. Needs `Text` and `TextFont`.
+#[derive(Component)]
+pub struct AnimatedNumber {
+    target: f32,
+    shown: f32,
+    /// A single gain at least this big pulses the text.
+    pulse_threshold: f32,
+    pulse: f32,
+    base_size: Option<f32>,
+    format: fn(f32) -> String,
+}
+
+impl AnimatedNumber {
+    pub fn new(format: fn(f32) -> String, pulse_threshold: f32) -> Self {
+        Self {
+            target: 0.0,
+            shown: 0.0,
+            pulse_threshold,
+            pulse: 0.0,
+            base_size: None,
+            format,
+        }
+    }
+
+    /// Starts counting toward a new value.
+    pub fn set(&mut self, value: f32) {
+        if value - self.target >= self.pulse_threshold {
+            self.pulse = PULSE_DURATION;
+        }
+        self.target = value;
+    }
+}
+
+pub struct AnimatedNumberPlugin;
+
+impl Plugin for AnimatedNumberPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, animate_numbers);
+    }
+}
+
+/// System that eases each number toward its value and plays its pulse
+fn animate_numbers(
+    real_time: Res<Time<Real>>,
+    mut query: Query<(&mut AnimatedNumber, &mut Text, &mut TextFont)>,
+) {
+    // Real time so the kill cam's slow motion doesn't stall the counter
+    let delta = real_time.delta_secs();
+    for (mut number, mut text, mut font) in &mut query {
+        let number = &mut *number;
+        let gap = number.target - number.shown;
+        number.shown = if gap.abs() < 0.5 {
+            number.target
+        } else {
+            number.shown + gap * (1.0 - (-COUNT_RATE * delta).exp())
+        };
+
+        let line = (number.format)(number.shown);
+        if text.0 != line {
+            text.0 = line;
+        }
+
+        let base_size = *number.base_size.get_or_insert(font.font_size);
+        number.pulse = (number.pulse - delta).max(0.0);
+        let t = number.pulse / PULSE_DURATION;
+        let size = base_size * (1.0 + PULSE_SCALE * t * t);
+        if font.font_size != size {
+            font.font_size = size;
+        }
+    }
+}
@@ -0,0 +1,202 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::despawn::DespawnQueue;
+use crate::difficulty::Difficulty;
+use crate::progression::Unlocks;
+use crate::reset::RunScoped;
+use crate::settings::{self, Settings};
+use crate::watchdog::Transient;
+use crate::{GameState, Player, RunPhase, Velocity};
+
+// Appearance constants
+const SHIP_SATURATION: f32 = 0.6;
+const SHIP_LIGHTNESS: f32 = 0.5;
+const ACCENT_SATURATION: f32 = 0.8; // Trail and shield, a lighter shade of the ship
+const ACCENT_LIGHTNESS: f32 = 0.75;
+const TRAIL_INTERVAL: f32 = 0.03; // Seconds between two trail puffs while moving
+const TRAIL_LIFETIME: f32 = 0.3;
+const TRAIL_SCALE: f32 = 0.6; // Trail puff size relative to the player
+const TRAIL_ALPHA: f32 = 0.5;
+const MAX_BANK: f32 = 0.35; // Radians the ship leans at full sideways speed
+const BANK_RATE: f32 = 12.0; // How quickly the lean follows the steering, per second
+
+/// Ship colours to pick from in the settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ShipColor {
+    #[default]
+    Blue,
+    Teal,
+    Green,
+    Gold,
+    Orange,
+    Red,
+    Pink,
+    Violet,
+}
+
+impl ShipColor {
+    const ALL: [ShipColor; 8] = [
+        ShipColor::Blue,
+        ShipColor::Teal,
+        ShipColor::Green,
+        ShipColor::Gold,
+        ShipColor::Orange,
+        ShipColor::Red,
+        ShipColor::Pink,
+        ShipColor::Violet,
+    ];
+
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|&c| c == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ShipColor::Blue => "Blue",
+            ShipColor::Teal => "Teal",
+            ShipColor::Green => "Green",
+            ShipColor::Gold => "Gold",
+            ShipColor::Orange => "Orange",
+            ShipColor::Red => "Red",
+            ShipColor::Pink => "Pink",
+            ShipColor::Violet => "Violet",
+        }
+    }
+
+    /// Hue in degrees.
+    fn hue(self) -> f32 {
+        match self {
+            ShipColor::Blue => 220.0,
+            ShipColor::Teal => 175.0,
+            ShipColor::Green => 125.0,
+            ShipColor::Gold => 48.0,
+            ShipColor::Orange => 25.0,
+            ShipColor::Red => 355.0,
+            ShipColor::Pink => 320.0,
+            ShipColor::Violet => 270.0,
+        }
+    }
+}
+
+// --- Components ---
+
+#[derive(Component)]
+#[require(RunScoped, Transient)]
+struct TrailPuff(Timer);
+
+// --- Resources ---
+
+/// Colours of the player's ship and the effects that go with it, from the
+/// ship colour picked in the settings.
+#[derive(Resource)]
+pub struct PlayerAppearance {
+    pub ship: Color,
+    /// Trail and shield bubble.
+    pub accent: Color,
+}
+
+impl PlayerAppearance {
+    fn new(color: ShipColor) -> Self {
+        let hue = color.hue();
+        Self {
+            ship: Color::hsl(hue, SHIP_SATURATION, SHIP_LIGHTNESS),
+            accent: Color::hsl(hue, ACCENT_SATURATION, ACCENT_LIGHTNESS),
+        }
+    }
+}
+
+impl Default for PlayerAppearance {
+    fn default() -> Self {
+        Self::new(ShipColor::default())
+    }
+}
+
+pub struct AppearancePlugin;
+
+impl Plugin for AppearancePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PlayerAppearance>()
+            .add_systems(
+                Update,
+                update_appearance
+                    .run_if(resource_changed::<Settings>.or(resource_changed::<Unlocks>)),
+            )
+            .add_systems(
+                Update,
+                (spawn_trail, bank_ship)
+                    .run_if(in_state(RunPhase::Alive).and(settings::motion_enabled)),
+            )
+            .add_systems(Update, fade_trail.run_if(in_state(GameState::Playing)));
+    }
+}
+
+/// System that follows the ship colour picked in the settings, as long as
+/// the profile has unlocked it
+fn update_appearance(
+    settings: Res<Settings>,
+    unlocks: Res<Unlocks>,
+    mut appearance: ResMut<PlayerAppearance>,
+) {
+    *appearance = PlayerAppearance::new(unlocks.next_ship_color(settings.ship_color));
+}
+
+/// System that leaves a fading trail behind the moving player
+fn spawn_trail(
+    mut commands: Commands,
+    time: Res<Time>,
+    appearance: Res<PlayerAppearance>,
+    mut timer: Local<Option<Timer>>,
+    query: Query<(&Transform, &Velocity), With<Player>>,
+) {
+    let timer =
+        timer.get_or_insert_with(|| Timer::from_seconds(TRAIL_INTERVAL, TimerMode::Repeating));
+    if !timer.tick(time.delta()).just_finished() {
+        return;
+    }
+    for (transform, velocity) in &query {
+        if velocity.0 == Vec2::ZERO {
+            continue;
+        }
+        commands.spawn((
+            Sprite::from_color(appearance.accent.with_alpha(TRAIL_ALPHA), Vec2::ONE),
+            Transform::from_translation(transform.translation.with_z(-0.05))
+                .with_scale(transform.scale * TRAIL_SCALE),
+            TrailPuff(Timer::from_seconds(TRAIL_LIFETIME, TimerMode::Once)),
+        ));
+    }
+}
+
+/// System that leans the ship into its turns, in proportion to how fast it's
+/// moving sideways. Only the sprite tilts; the hitbox stays level
+fn bank_ship(
+    time: Res<Time>,
+    difficulty: Res<Difficulty>,
+    mut query: Query<(&mut Transform, &Velocity), With<Player>>,
+) {
+    let speed = difficulty.config.player_speed.max(f32::EPSILON);
+    let blend = 1.0 - (-BANK_RATE * time.delta_secs()).exp();
+    for (mut transform, velocity) in &mut query {
+        let target = -(velocity.0.x / speed).clamp(-1.0, 1.0) * MAX_BANK;
+        let (_, _, bank) = transform.rotation.to_euler(EulerRot::XYZ);
+        transform.rotation = Quat::from_rotation_z(bank + (target - bank) * blend);
+    }
+}
+
+/// System that fades trail puffs out and removes them
+fn fade_trail(
+    time: Res<Time>,
+    mut despawn_queue: ResMut<DespawnQueue>,
+    mut query: Query<(Entity, &mut TrailPuff, &mut Sprite)>,
+) {
+    for (entity, mut puff, mut sprite) in &mut query {
+        puff.0.tick(time.delta());
+        sprite
+            .color
+            .set_alpha(TRAIL_ALPHA * puff.0.fraction_remaining());
+        if puff.0.finished() {
+            despawn_queue.push(entity);
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use bevy::prelude::*;
+
+use crate::reset::RunSetup;
+use crate::window::WindowMetrics;
+use crate::{Player, RunPhase};
+
+// Arena constants
+const MAX_RISK_MULTIPLIER: f32 = 3.0; // Score rate at the very top of the screen
+
+// --- Resources ---
+
+/// Whether runs are arena runs: the player can move up and down, and scores
+/// faster the higher they stay.
+#[derive(Resource, Default)]
+pub struct Arena {
+    pub enabled: bool,
+}
+
+/// Current score rate from where the player stands. Always 1.0 outside arena runs.
+#[derive(Resource)]
+pub struct RiskMultiplier(pub f32);
+
+impl Default for RiskMultiplier {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+pub struct ArenaPlugin;
+
+impl Plugin for ArenaPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Arena>()
+            .init_resource::<RiskMultiplier>()
+            .add_systems(RunSetup, reset_risk_multiplier)
+            .add_systems(
+                Update,
+                update_risk_multiplier.run_if(arena_enabled.and(in_state(RunPhase::Alive))),
+            );
+    }
+}
+
+pub fn arena_enabled(arena: Res<Arena>) -> bool {
+    arena.enabled
+}
+
+/// Score rate for a player at `y`: 1.0 anywhere in the lower half, rising to
+/// the maximum at the top edge.
+pub fn risk_multiplier(y: f32, window_height: f32) -> f32 {
+    let height_above_middle = (y / (window_height / 2.0).max(1.0)).clamp(0.0, 1.0);
+    1.0 + (MAX_RISK_MULTIPLIER - 1.0) * height_above_middle
+}
+
+/// System to start every run at the base score rate
+fn reset_risk_multiplier(mut risk: ResMut<RiskMultiplier>) {
+    risk.0 = 1.0;
+}
+
+/// System that follows the player's height with the score rate
+fn update_risk_multiplier(
+    mut risk: ResMut<RiskMultiplier>,
+    player_query: Query<&Transform, With<Player>>,
+    window: Res<WindowMetrics>,
+) {
+    let Ok(transform) = player_query.single() else {
+        return;
+    };
+    risk.0 = risk_multiplier(transform.translation.y, window.height());
+}
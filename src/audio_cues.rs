@@ -0,0 +1,175 @@
+use bevy::prelude::*;
+
+use crate::flash;
+use crate::mixer::{PlaySound, SoundCue};
+use crate::settings::Settings;
+use crate::watchdog::Transient;
+use crate::window::WindowMetrics;
+
+// Sound cue constants
+const CUE_DURATION: f32 = 0.8; // Real seconds a cue takes to fade out
+const CUE_ALPHA: f32 = 0.8;
+const FLASH_LENGTH: f32 = 160.0; // Length of an edge flash along its edge
+const FLASH_THICKNESS: f32 = 10.0;
+const PULSE_THICKNESS: f32 = 14.0; // Width of the band round the screen
+const ARRIVAL_COLOR: Color = Color::srgb(1.0, 0.85, 0.3);
+const ALARM_COLOR: Color = Color::srgb(1.0, 0.3, 0.3);
+
+// --- Components ---
+
+/// A visual stand-in for a sound, fading out: a flash on the screen edge
+/// nearest to where the sound came from, or a band all round the screen.
+#[derive(Component)]
+#[require(Transient)]
+struct CueFlash {
+    color: Color,
+    timer: Timer,
+}
+
+pub struct AudioCuePlugin;
+
+impl Plugin for AudioCuePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (show_sound_cues.run_if(cues_enabled), fade_sound_cues).chain(),
+        );
+    }
+}
+
+fn cues_enabled(settings: Res<Settings>) -> bool {
+    settings.sound_cues
+}
+
+/// The edge flash for a sound at `at`, on whichever edge of the play area is
+/// closest to it.
+fn edge_flash(at: Vec2, window: &WindowMetrics) -> Node {
+    let half = window.size / 2.0;
+    let at = at.clamp(-half, half);
+    // Where the sound is along the edges, as percentages of the play area
+    let across = Val::Percent((at.x / window.width() + 0.5) * 100.0);
+    let down = Val::Percent((0.5 - at.y / window.height()) * 100.0);
+    let along_top_or_bottom = Node {
+        position_type: PositionType::Absolute,
+        left: across,
+        width: Val::Px(FLASH_LENGTH),
+        height: Val::Px(FLASH_THICKNESS),
+        margin: UiRect::left(Val::Px(-FLASH_LENGTH / 2.0)),
+        ..default()
+    };
+    let along_side = Node {
+        position_type: PositionType::Absolute,
+        top: down,
+        width: Val::Px(FLASH_THICKNESS),
+        height: Val::Px(FLASH_LENGTH),
+        margin: UiRect::top(Val::Px(-FLASH_LENGTH / 2.0)),
+        ..default()
+    };
+    let edges = [
+        (
+            half.y - at.y,
+            Node {
+                top: Val::Px(0.0),
+                ..along_top_or_bottom.clone()
+            },
+        ),
+        (
+            half.y + at.y,
+            Node {
+                bottom: Val::Px(0.0),
+                ..along_top_or_bottom
+            },
+        ),
+        (
+            half.x + at.x,
+            Node {
+                left: Val::Px(0.0),
+                ..along_side.clone()
+            },
+        ),
+        (
+            half.x - at.x,
+            Node {
+                right: Val::Px(0.0),
+                ..along_side
+            },
+        ),
+    ];
+    edges
+        .into_iter()
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, node)| node)
+        .unwrap_or_default()
+}
+
+/// System that shows every sound played as it's heard: arrivals flash the
+/// edge they're nearest to, alarms pulse the whole edge of the screen
+fn show_sound_cues(
+    mut commands: Commands,
+    mut sounds: EventReader<PlaySound>,
+    settings: Res<Settings>,
+    window: Res<WindowMetrics>,
+) {
+    for sound in sounds.read() {
+        let alpha = flash::limit_alpha(&settings, CUE_ALPHA);
+        let timer = Timer::from_seconds(CUE_DURATION, TimerMode::Once);
+        match (sound.cue, sound.at) {
+            (SoundCue::Arrival, Some(at)) => {
+                commands.spawn((
+                    edge_flash(at, &window),
+                    BackgroundColor(ARRIVAL_COLOR.with_alpha(alpha)),
+                    CueFlash {
+                        color: ARRIVAL_COLOR.with_alpha(alpha),
+                        timer,
+                    },
+                ));
+            }
+            // A sound from nowhere in particular is shown everywhere
+            (SoundCue::Alarm, _) | (SoundCue::Arrival, None) => {
+                commands.spawn((
+                    Node {
+                        position_type: PositionType::Absolute,
+                        width: Val::Percent(100.0),
+                        height: Val::Percent(100.0),
+                        border: UiRect::all(Val::Px(PULSE_THICKNESS)),
+                        ..default()
+                    },
+                    BorderColor(ALARM_COLOR.with_alpha(alpha)),
+                    CueFlash {
+                        color: ALARM_COLOR.with_alpha(alpha),
+                        timer,
+                    },
+                ));
+            }
+        }
+    }
+}
+
+/// System that fades cues out in real time and removes them
+fn fade_sound_cues(
+    mut commands: Commands,
+    real_time: Res<Time<Real>>,
+    mut query: Query<(
+        Entity,
+        &mut CueFlash,
+        Option<&mut BackgroundColor>,
+        Option<&mut BorderColor>,
+    )>,
+) {
+    for (entity, mut cue, background, border) in &mut query {
+        cue.timer.tick(real_time.delta());
+        if cue.timer.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let color = cue
+            .color
+            .with_alpha(cue.color.alpha() * cue.timer.fraction_remaining());
+        if let Some(mut background) = background {
+            background.0 = color;
+        }
+        if let Some(mut border) = border {
+            border.0 = color;
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use std::collections::HashMap;
+use std::fs;
+use std::time::SystemTime;
+
+use bevy::input::common_conditions::input_just_pressed;
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::data;
+use crate::difficulty::{Difficulty, DifficultyConfig};
+use crate::reset::{RunCleanup, RunSetup};
+use crate::sets::RunSetupSet;
+
+// Balance constants
+const TOGGLE_KEY: KeyCode = KeyCode::F10;
+const BALANCE_FILE: &str = "balance.ron";
+const POLL_INTERVAL: f32 = 1.0; // Real seconds between checks for an edited balance file
+
+/// A named set of multipliers for the difficulty's values, by name.
+#[derive(Deserialize)]
+struct BalanceProfile {
+    name: String,
+    #[serde(default)]
+    values: HashMap<String, f32>,
+}
+
+impl BalanceProfile {
+    fn apply(&self, config: &mut DifficultyConfig) {
+        for (key, &factor) in &self.values {
+            match key.as_str() {
+                "player_speed" => config.player_speed *= factor,
+                "enemy_speed" => config.enemy_speed *= factor,
+                "spawn_interval" => {
+                    config.spawn.start_interval *= factor;
+                    config.spawn.min_interval *= factor;
+                }
+                "elite_chance" => config.elite_chance *= factor,
+                _ => warn!(key, profile = %self.name, "Unknown balance value"),
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct BalanceTable {
+    profiles: Vec<BalanceProfile>,
+}
+
+impl BalanceTable {
+    fn load() -> Self {
+        data::load_ron(BALANCE_FILE, include_str!("../assets/balance.ron"))
+    }
+}
+
+// --- Resources ---
+
+/// The balance profiles and which one is playing. The run's difficulty is
+/// kept as it was set up, so swapping profiles never compounds them.
+#[derive(Resource)]
+struct Balance {
+    table: BalanceTable,
+    active: usize,
+    /// When the balance file was last changed, to notice edits.
+    modified: Option<SystemTime>,
+    poll: Timer,
+    /// The current run's difficulty before any profile, between set-up and clean-up.
+    base: Option<DifficultyConfig>,
+}
+
+impl Balance {
+    fn profile(&self) -> Option<&BalanceProfile> {
+        self.table.profiles.get(self.active)
+    }
+
+    fn label(&self) -> String {
+        match self.profile() {
+            Some(profile) => format!(
+                "Balance {}: {} (F10)",
+                char::from(b'A' + self.active as u8),
+                profile.name
+            ),
+            None => "Balance: none loaded".to_string(),
+        }
+    }
+}
+
+// --- Components ---
+
+#[derive(Component)]
+struct BalanceLabel;
+
+/// Dev builds only: swaps between the balance profiles in `balance.ron` with
+/// F10 mid-run, reloading the file whenever it's saved.
+pub struct BalancePlugin;
+
+impl Plugin for BalancePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Balance {
+            table: BalanceTable::load(),
+            active: 0,
+            modified: balance_file_modified(),
+            poll: Timer::from_seconds(POLL_INTERVAL, TimerMode::Repeating),
+            base: None,
+        })
+        .add_systems(Startup, spawn_balance_label)
+        .add_systems(
+            RunSetup,
+            remember_base_difficulty.after(RunSetupSet::DifficultyOverrides),
+        )
+        .add_systems(RunCleanup, forget_base_difficulty)
+        .add_systems(
+            Update,
+            (
+                toggle_balance.run_if(input_just_pressed(TOGGLE_KEY)),
+                reload_balance,
+                (apply_balance, update_balance_label).run_if(resource_changed::<Balance>),
+            )
+                .chain(),
+        );
+    }
+}
+
+fn balance_file_modified() -> Option<SystemTime> {
+    fs::metadata(data::assets_dir().join(BALANCE_FILE))
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// System to spawn the corner label naming the profile in play
+fn spawn_balance_label(mut commands: Commands) {
+    commands.spawn((
+        Text::default(),
+        TextFont::from_font_size(14.0),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(10.0),
+            left: Val::Px(10.0),
+            ..default()
+        },
+        GlobalZIndex(i32::MAX),
+        BalanceLabel,
+    ));
+}
+
+/// System that keeps the run's difficulty as set up, for profiles to scale
+fn remember_base_difficulty(mut balance: ResMut<Balance>, difficulty: Res<Difficulty>) {
+    balance.base = Some(difficulty.config.clone());
+}
+
+fn forget_base_difficulty(mut balance: ResMut<Balance>) {
+    balance.base = None;
+}
+
+/// System to switch to the next balance profile
+fn toggle_balance(mut balance: ResMut<Balance>) {
+    balance.active = (balance.active + 1) % balance.table.profiles.len().max(1);
+    info!(label = %balance.label(), "Switched balance profile");
+}
+
+/// System that reloads the balance file once a second if it's been saved,
+/// keeping the same profile in play
+fn reload_balance(real_time: Res<Time<Real>>, mut balance: ResMut<Balance>) {
+    if !balance
+        .bypass_change_detection()
+        .poll
+        .tick(real_time.delta())
+        .just_finished()
+    {
+        return;
+    }
+    let modified = balance_file_modified();
+    if modified == balance.modified {
+        return;
+    }
+    balance.modified = modified;
+    balance.table = BalanceTable::load();
+    if balance.active >= balance.table.profiles.len() {
+        balance.active = 0;
+    }
+    info!(label = %balance.label(), "Reloaded the balance file");
+}
+
+/// System that puts the profile in play on the run under way
+fn apply_balance(balance: Res<Balance>, mut difficulty: ResMut<Difficulty>) {
+    let Some(base) = &balance.base else {
+        return;
+    };
+    let mut config = base.clone();
+    if let Some(profile) = balance.profile() {
+        profile.apply(&mut config);
+    }
+    difficulty.config = config;
+}
+
+/// System that names the profile in play in the corner label
+fn update_balance_label(balance: Res<Balance>, mut query: Query<&mut Text, With<BalanceLabel>>) {
+    for mut text in &mut query {
+        text.0 = balance.label();
+    }
+}
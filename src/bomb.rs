@@ -0,0 +1,195 @@
+use bevy::ecs::entity::EntityHashSet;
+use bevy::prelude::*;
+
+use crate::bug_report::report_captured;
+use crate::despawn::DespawnQueue;
+use crate::dying::Dying;
+use crate::elite::{ELITE_SCORE_MULTIPLIER, Elite, Shielded};
+use crate::flash;
+use crate::reset::{RunScoped, RunSetup};
+use crate::score::{Score, ScoreEvent, ScoreReason};
+use crate::sets::GameSet;
+use crate::settings::Settings;
+use crate::watchdog::Transient;
+use crate::window::WindowMetrics;
+use crate::{Enemy, Player, RunPhase};
+
+// Bomb constants
+const STARTING_BOMBS: u32 = 1;
+const MAX_BOMBS: u32 = 3;
+const BOMB_SCORE_INTERVAL: u32 = 500; // A bomb is earned every this many points
+const BOMB_KILL_POINTS: f32 = 5.0; // Partial score for each enemy cleared
+const SHOCKWAVE_DURATION: f32 = 0.5;
+const SHOCKWAVE_THICKNESS: f32 = 12.0;
+const SHOCKWAVE_COLOR: Color = Color::srgba(1.0, 0.9, 0.5, 0.8);
+pub const ENEMY_DEATH_DURATION: f32 = 0.25;
+
+// --- Resources ---
+
+/// Screen-clearing bombs the player has left in this run.
+#[derive(Resource, Reflect)]
+#[reflect(Resource)]
+pub struct Bombs {
+    pub count: u32,
+    next_award: u32,
+}
+
+impl Default for Bombs {
+    fn default() -> Self {
+        Self {
+            count: STARTING_BOMBS,
+            next_award: BOMB_SCORE_INTERVAL,
+        }
+    }
+}
+
+impl Bombs {
+    /// Adds a bomb unless the player already holds the maximum.
+    pub fn grant(&mut self) -> bool {
+        if self.count >= MAX_BOMBS {
+            return false;
+        }
+        self.count += 1;
+        true
+    }
+}
+
+// --- Components ---
+
+/// Expanding ring that destroys every enemy it reaches. Its scale is its
+/// current radius.
+#[derive(Component)]
+#[require(RunScoped, Transient)]
+pub struct Shockwave {
+    timer: Timer,
+    max_radius: f32,
+    // Each wave only hits an enemy once, so a shield costs one wave
+    hit: EntityHashSet,
+}
+
+pub struct BombPlugin;
+
+impl Plugin for BombPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Bombs>()
+            .add_systems(RunSetup, reset_bombs)
+            .add_systems(
+                Update,
+                (award_bombs, use_bomb.run_if(not(report_captured)))
+                    .in_set(GameSet::Simulation)
+                    .run_if(in_state(RunPhase::Alive)),
+            )
+            .add_systems(Update, expand_shockwaves);
+    }
+}
+
+/// System to give each run a fresh stock of bombs
+fn reset_bombs(mut bombs: ResMut<Bombs>) {
+    *bombs = Bombs::default();
+}
+
+/// System that awards a bomb every time the score passes another threshold
+fn award_bombs(score: Res<Score>, mut bombs: ResMut<Bombs>) {
+    while score.points() >= bombs.next_award {
+        bombs.next_award += BOMB_SCORE_INTERVAL;
+        bombs.grant();
+    }
+}
+
+/// System that sets off a bomb centered on the player
+fn use_bomb(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut bombs: ResMut<Bombs>,
+    player_query: Query<&Transform, With<Player>>,
+    window: Res<WindowMetrics>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::KeyB) || bombs.count == 0 {
+        return;
+    }
+    let Ok(player_transform) = player_query.single() else {
+        return;
+    };
+    bombs.count -= 1;
+
+    // Big enough to reach the farthest corner from anywhere on screen
+    let max_radius = Vec2::new(window.width(), window.height()).length();
+    let mut translation = player_transform.translation;
+    translation.z = 0.5;
+
+    commands.spawn((
+        Mesh2d(meshes.add(Annulus::new(1.0 - SHOCKWAVE_THICKNESS / max_radius, 1.0))),
+        MeshMaterial2d(materials.add(ColorMaterial::from_color(SHOCKWAVE_COLOR))),
+        Transform::from_translation(translation).with_scale(Vec3::ZERO),
+        Shockwave {
+            timer: Timer::from_seconds(SHOCKWAVE_DURATION, TimerMode::Once),
+            max_radius,
+            hit: EntityHashSet::default(),
+        },
+    ));
+}
+
+/// System that grows shockwaves and destroys the enemies they reach
+fn expand_shockwaves(
+    mut commands: Commands,
+    time: Res<Time>,
+    settings: Res<Settings>,
+    mut score_events: EventWriter<ScoreEvent>,
+    mut despawn_queue: ResMut<DespawnQueue>,
+    mut shockwave_query: Query<(
+        Entity,
+        &mut Shockwave,
+        &mut Transform,
+        &MeshMaterial2d<ColorMaterial>,
+    )>,
+    mut enemy_query: Query<
+        (Entity, &Transform, Has<Elite>, Option<&mut Shielded>),
+        (With<Enemy>, Without<Dying>, Without<Shockwave>),
+    >,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for (entity, mut shockwave, mut transform, material) in &mut shockwave_query {
+        shockwave.timer.tick(time.delta());
+        let progress = shockwave.timer.fraction();
+        let radius = shockwave.max_radius * progress;
+        transform.scale = Vec3::new(radius, radius, 1.0);
+
+        if let Some(material) = materials.get_mut(&material.0) {
+            let alpha = flash::limit_alpha(&settings, SHOCKWAVE_COLOR.alpha());
+            material.color = SHOCKWAVE_COLOR.with_alpha(alpha * (1.0 - progress));
+        }
+
+        let center = transform.translation.truncate();
+        for (enemy, enemy_transform, elite, shielded) in &mut enemy_query {
+            if enemy_transform.translation.truncate().distance(center) > radius
+                || !shockwave.hit.insert(enemy)
+            {
+                continue;
+            }
+            if let Some(mut shielded) = shielded {
+                shielded.hits_left -= 1;
+                if shielded.hits_left > 0 {
+                    continue;
+                }
+            }
+            commands
+                .entity(enemy)
+                .insert(Dying::new(ENEMY_DEATH_DURATION));
+            score_events.write(ScoreEvent {
+                amount: if elite {
+                    BOMB_KILL_POINTS * ELITE_SCORE_MULTIPLIER
+                } else {
+                    BOMB_KILL_POINTS
+                },
+                reason: ScoreReason::BombKill,
+                position: Some(enemy_transform.translation.truncate()),
+            });
+        }
+
+        if shockwave.timer.finished() {
+            despawn_queue.push(entity);
+        }
+    }
+}
@@ -0,0 +1,283 @@
+use std::collections::VecDeque;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::prelude::*;
+use bevy::render::view::screenshot::{Screenshot, save_to_disk};
+use bevy::state::state::StateTransitionEvent;
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use serde::Serialize;
+
+use crate::difficulty::Difficulty;
+use crate::enemy::EnemyKind;
+use crate::rng::{self, RunSeed};
+use crate::save;
+use crate::score::Score;
+use crate::sets::GameSet;
+use crate::settings::Settings;
+use crate::stats::RunStats;
+use crate::{Enemy, GameState, Player, RunPhase};
+
+// Bug report constants
+const REPORTS_DIR: &str = "reports";
+const REPORT_KEY: KeyCode = KeyCode::F12;
+const MAX_RECENT_EVENTS: usize = 200; // Oldest entries are dropped past this
+const SCREENSHOT_FILE: &str = "screenshot.png";
+const SNAPSHOT_FILE: &str = "snapshot.ron.gz";
+#[cfg(feature = "report-upload")]
+const REPORT_CONFIG_FILE: &str = "report.ron"; // In the data directory
+
+// --- Resources ---
+
+/// Rolling log of what happened recently, included in bug reports.
+#[derive(Resource, Default)]
+struct RecentEvents(VecDeque<String>);
+
+impl RecentEvents {
+    fn push(&mut self, time: f32, entry: String) {
+        self.0.push_back(format!("[{time:8.2}] {entry}"));
+        if self.0.len() > MAX_RECENT_EVENTS {
+            self.0.pop_front();
+        }
+    }
+}
+
+/// Present while the game is frozen after taking a report.
+#[derive(Resource)]
+struct CapturedReport {
+    /// Whether time was already paused, by the pause screen or the kill cam,
+    /// so resuming leaves it that way.
+    was_paused: bool,
+}
+
+// --- Components ---
+
+#[derive(Component)]
+struct ReportNotice;
+
+/// Everything but the screenshot, written compressed into the report bundle.
+#[derive(Serialize)]
+struct ReportSnapshot {
+    version: &'static str,
+    created: u64,
+    state: String,
+    difficulty: String,
+    seed: String,
+    score: u32,
+    run_time: f32,
+    settings: Settings,
+    player: Option<[f32; 2]>,
+    enemies: Vec<EnemySnapshot>,
+    recent_events: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct EnemySnapshot {
+    kind: EnemyKind,
+    position: [f32; 2],
+    rotation: f32,
+}
+
+pub struct BugReportPlugin;
+
+impl Plugin for BugReportPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RecentEvents>()
+            // Nothing plays while the game is frozen
+            .configure_sets(Update, GameSet::Input.run_if(not(report_captured)))
+            .add_systems(
+                Update,
+                (
+                    record_recent_events,
+                    capture_report.run_if(not(report_captured)),
+                    // Not on the frame the report was taken, which saw the same press
+                    resume_after_report
+                        .run_if(report_captured.and(not(resource_added::<CapturedReport>))),
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Whether the game is frozen on a bug report.
+pub fn report_captured(report: Option<Res<CapturedReport>>) -> bool {
+    report.is_some()
+}
+
+/// System that keeps the recent event log filled with transitions and key presses
+fn record_recent_events(
+    time: Res<Time<Real>>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut game_transitions: EventReader<StateTransitionEvent<GameState>>,
+    mut phase_transitions: EventReader<StateTransitionEvent<RunPhase>>,
+    mut recent: ResMut<RecentEvents>,
+) {
+    let now = time.elapsed_secs();
+    for transition in game_transitions.read() {
+        recent.push(
+            now,
+            format!("state {:?} -> {:?}", transition.exited, transition.entered),
+        );
+    }
+    for transition in phase_transitions.read() {
+        recent.push(
+            now,
+            format!("phase {:?} -> {:?}", transition.exited, transition.entered),
+        );
+    }
+    for key in keyboard_input.get_just_pressed() {
+        recent.push(now, format!("key {key:?}"));
+    }
+}
+
+/// System that freezes the game and writes a report bundle when the report key is pressed
+fn capture_report(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut time: ResMut<Time<Virtual>>,
+    recent: Res<RecentEvents>,
+    state: Res<State<GameState>>,
+    difficulty: Res<Difficulty>,
+    seed: Res<RunSeed>,
+    score: Res<Score>,
+    stats: Res<RunStats>,
+    settings: Res<Settings>,
+    player_query: Query<&Transform, With<Player>>,
+    enemy_query: Query<(&Transform, &EnemyKind), With<Enemy>>,
+) {
+    if !keyboard_input.just_pressed(REPORT_KEY) {
+        return;
+    }
+    let was_paused = time.is_paused();
+    time.pause();
+
+    let created = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let snapshot = ReportSnapshot {
+        version: env!("CARGO_PKG_VERSION"),
+        created,
+        state: format!("{:?}", state.get()),
+        difficulty: difficulty.preset.name().to_string(),
+        seed: rng::format_seed(seed.seed),
+        score: score.points(),
+        run_time: stats.elapsed(),
+        settings: settings.clone(),
+        player: player_query
+            .single()
+            .ok()
+            .map(|transform| transform.translation.truncate().to_array()),
+        enemies: enemy_query
+            .iter()
+            .map(|(transform, &kind)| EnemySnapshot {
+                kind,
+                position: transform.translation.truncate().to_array(),
+                rotation: transform.rotation.to_euler(EulerRot::XYZ).2,
+            })
+            .collect(),
+        recent_events: recent.0.iter().cloned().collect(),
+    };
+
+    let dir = save::data_dir()
+        .join(REPORTS_DIR)
+        .join(format!("report-{created}"));
+    let message = match write_snapshot(&dir, &snapshot) {
+        Ok(()) => {
+            commands
+                .spawn(Screenshot::primary_window())
+                .observe(save_to_disk(dir.join(SCREENSHOT_FILE)));
+            #[cfg(feature = "report-upload")]
+            upload_report(dir.join(SNAPSHOT_FILE));
+            format!(
+                "Bug report saved to\n{}\n\nAttach this folder to an issue. Press {REPORT_KEY:?} to resume.",
+                dir.display()
+            )
+        }
+        Err(err) => {
+            error!(path = %dir.display(), "Could not save bug report: {err}");
+            format!("Could not save the bug report: {err}\n\nPress {REPORT_KEY:?} to resume.")
+        }
+    };
+
+    commands.spawn((
+        Text::new(message),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(40.0),
+            left: Val::Px(40.0),
+            ..default()
+        },
+        BackgroundColor(Color::BLACK.with_alpha(0.7)),
+        ReportNotice,
+    ));
+    commands.insert_resource(CapturedReport { was_paused });
+}
+
+fn write_snapshot(dir: &Path, snapshot: &ReportSnapshot) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    let contents = ron::ser::to_string_pretty(snapshot, ron::ser::PrettyConfig::default())
+        .map_err(io::Error::other)?;
+    let mut encoder = GzEncoder::new(
+        fs::File::create(dir.join(SNAPSHOT_FILE))?,
+        Compression::default(),
+    );
+    encoder.write_all(contents.as_bytes())?;
+    encoder.finish().map(|_| ())
+}
+
+/// System that unfreezes the game and clears the notice
+fn resume_after_report(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    report: Res<CapturedReport>,
+    mut time: ResMut<Time<Virtual>>,
+    notice_query: Query<Entity, With<ReportNotice>>,
+) {
+    if !keyboard_input.just_pressed(REPORT_KEY) {
+        return;
+    }
+    if !report.was_paused {
+        time.unpause();
+    }
+    for entity in &notice_query {
+        commands.entity(entity).despawn();
+    }
+    commands.remove_resource::<CapturedReport>();
+}
+
+/// Contents of `report.ron`; reports are only uploaded when `endpoint` is set.
+#[cfg(feature = "report-upload")]
+#[derive(Default, serde::Deserialize)]
+#[serde(default)]
+struct ReportConfig {
+    endpoint: Option<String>,
+}
+
+/// POSTs the compressed snapshot to the configured endpoint in the background.
+#[cfg(feature = "report-upload")]
+fn upload_report(snapshot: std::path::PathBuf) {
+    let config: ReportConfig = save::load_or_default(&save::data_dir().join(REPORT_CONFIG_FILE));
+    let Some(endpoint) = config.endpoint else {
+        return;
+    };
+
+    bevy::tasks::IoTaskPool::get()
+        .spawn(async move {
+            let result = fs::read(&snapshot)
+                .map_err(|err| err.to_string())
+                .and_then(|body| {
+                    ureq::post(&endpoint)
+                        .set("Content-Type", "application/gzip")
+                        .send_bytes(&body)
+                        .map_err(|err| err.to_string())
+                });
+            match result {
+                Ok(_) => info!(%endpoint, "Bug report uploaded"),
+                Err(err) => warn!(%endpoint, "Could not upload bug report: {err}"),
+            }
+        })
+        .detach();
+}
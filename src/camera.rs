@@ -0,0 +1,330 @@
+use bevy::asset::RenderAssetUsages;
+use bevy::image::ImageSampler;
+use bevy::prelude::*;
+use bevy::render::camera::{CameraUpdateSystem, RenderTarget, ScalingMode, Viewport};
+use bevy::render::render_resource::{
+    AsBindGroup, Extent3d, ShaderRef, TextureDimension, TextureFormat, TextureUsages,
+};
+use bevy::render::view::RenderLayers;
+use bevy::sprite::{Material2d, Material2dPlugin};
+use bevy::window::{PrimaryWindow, WindowRef, WindowResized, WindowScaleFactorChanged};
+
+use crate::settings::Settings;
+
+// Camera constants
+pub const PLAY_AREA: Vec2 = Vec2::new(1280.0, 720.0); // Logical size of the dodge area, whatever the window
+const PIXEL_RESOLUTION: UVec2 = UVec2::new(320, 180); // Size the world is drawn at in pixel mode
+const PIXEL_LAYER: usize = 29; // Render layer nothing but the upscale camera uses
+const CRT_SHADER: &str = "shaders/crt.wgsl";
+const LETTERBOX_COLOR: Color = Color::BLACK;
+const MIN_UI_SCALE: f32 = 0.5; // Smallest the UI shrinks to in a small window
+
+// --- Components ---
+
+/// The camera that draws the game world, and its UI unless pixel mode is on.
+#[derive(Component)]
+pub struct GameCamera;
+
+/// Clears the whole window behind the game camera, so the bars around the
+/// play area stay black.
+#[derive(Component)]
+struct LetterboxCamera;
+
+/// Draws the canvas the game camera renders into blown up to the play area,
+/// with the UI on top at native resolution. Only used in pixel mode or with
+/// the CRT filter.
+#[derive(Component)]
+struct UpscaleCamera;
+
+/// The quad the upscale camera draws the canvas on.
+#[derive(Component)]
+struct CanvasQuad;
+
+/// Scanlines, screen curvature and a little noise over the canvas.
+#[derive(Asset, TypePath, AsBindGroup, Clone)]
+struct CrtMaterial {
+    /// Size of the canvas in pixels in `xy`, so there's a scanline per row.
+    #[uniform(0)]
+    source_size: Vec4,
+    #[texture(1)]
+    #[sampler(2)]
+    canvas: Handle<Image>,
+}
+
+impl Material2d for CrtMaterial {
+    fn fragment_shader() -> ShaderRef {
+        CRT_SHADER.into()
+    }
+}
+
+// --- Resources ---
+
+/// The texture the game camera draws into when the world is post-processed:
+/// small and sampled without smoothing in pixel mode so every pixel stays
+/// sharp, otherwise the size of the play area.
+#[derive(Resource)]
+struct Canvas {
+    image: Handle<Image>,
+    quad: Handle<Mesh>,
+    plain: Handle<ColorMaterial>,
+    crt: Handle<CrtMaterial>,
+}
+
+impl FromWorld for Canvas {
+    fn from_world(world: &mut World) -> Self {
+        let mut image = Image::new_fill(
+            canvas_extent(PLAY_AREA.as_uvec2()),
+            TextureDimension::D2,
+            &[0, 0, 0, 255],
+            TextureFormat::Bgra8UnormSrgb,
+            RenderAssetUsages::default(),
+        );
+        image.texture_descriptor.usage = TextureUsages::TEXTURE_BINDING
+            | TextureUsages::COPY_DST
+            | TextureUsages::RENDER_ATTACHMENT;
+        let image = world.resource_mut::<Assets<Image>>().add(image);
+        let quad = world
+            .resource_mut::<Assets<Mesh>>()
+            .add(Rectangle::from_size(PLAY_AREA));
+        let plain = world
+            .resource_mut::<Assets<ColorMaterial>>()
+            .add(ColorMaterial::from(image.clone()));
+        let crt = world
+            .resource_mut::<Assets<CrtMaterial>>()
+            .add(CrtMaterial {
+                source_size: PLAY_AREA.extend(0.0).extend(0.0),
+                canvas: image.clone(),
+            });
+        Self {
+            image,
+            quad,
+            plain,
+            crt,
+        }
+    }
+}
+
+fn canvas_extent(size: UVec2) -> Extent3d {
+    Extent3d {
+        width: size.x,
+        height: size.y,
+        depth_or_array_layers: 1,
+    }
+}
+
+pub struct CameraPlugin;
+
+impl Plugin for CameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(Material2dPlugin::<CrtMaterial>::default())
+            .init_resource::<Canvas>()
+            .add_systems(Startup, setup_cameras)
+            .add_systems(
+                Update,
+                apply_canvas_settings.run_if(resource_changed::<Settings>),
+            )
+            .add_systems(Update, scale_ui)
+            .add_systems(PostUpdate, fit_play_area.before(CameraUpdateSystem));
+    }
+}
+
+/// A projection that always shows exactly the play area.
+fn play_area_projection() -> Projection {
+    Projection::Orthographic(OrthographicProjection {
+        scaling_mode: ScalingMode::Fixed {
+            width: PLAY_AREA.x,
+            height: PLAY_AREA.y,
+        },
+        ..OrthographicProjection::default_2d()
+    })
+}
+
+/// System to set up the game camera, the letterbox camera behind it and the
+/// upscale camera, which starts out switched off
+fn setup_cameras(mut commands: Commands, canvas: Res<Canvas>) {
+    commands.spawn((
+        Camera2d,
+        Camera {
+            order: -1,
+            clear_color: ClearColorConfig::Custom(LETTERBOX_COLOR),
+            ..default()
+        },
+        RenderLayers::none(),
+        LetterboxCamera,
+    ));
+    commands.spawn((
+        Camera2d,
+        play_area_projection(),
+        IsDefaultUiCamera,
+        GameCamera,
+    ));
+    commands.spawn((
+        Camera2d,
+        Camera {
+            order: 1,
+            is_active: false,
+            clear_color: ClearColorConfig::Custom(LETTERBOX_COLOR),
+            ..default()
+        },
+        play_area_projection(),
+        RenderLayers::layer(PIXEL_LAYER),
+        UpscaleCamera,
+    ));
+    commands.spawn((
+        Mesh2d(canvas.quad.clone()),
+        MeshMaterial2d(canvas.plain.clone()),
+        RenderLayers::layer(PIXEL_LAYER),
+        CanvasQuad,
+    ));
+}
+
+/// System that points the game camera at the canvas or back at the window,
+/// moving the UI to whichever camera draws to the window, and sizes and
+/// filters the canvas for pixel mode and the CRT filter
+fn apply_canvas_settings(
+    mut commands: Commands,
+    mut applied: Local<Option<(bool, bool)>>,
+    settings: Res<Settings>,
+    canvas: Res<Canvas>,
+    mut images: ResMut<Assets<Image>>,
+    mut crt_materials: ResMut<Assets<CrtMaterial>>,
+    mut game_query: Query<(Entity, &mut Camera), (With<GameCamera>, Without<UpscaleCamera>)>,
+    mut upscale_query: Query<(Entity, &mut Camera), With<UpscaleCamera>>,
+    quad_query: Query<Entity, With<CanvasQuad>>,
+) {
+    let (Ok((game, mut game_camera)), Ok((upscale, mut upscale_camera)), Ok(quad)) = (
+        game_query.single_mut(),
+        upscale_query.single_mut(),
+        quad_query.single(),
+    ) else {
+        return;
+    };
+    let wanted = (settings.pixel_mode, settings.crt_filter);
+    if *applied == Some(wanted) {
+        return;
+    }
+    *applied = Some(wanted);
+
+    let size = if settings.pixel_mode {
+        PIXEL_RESOLUTION
+    } else {
+        PLAY_AREA.as_uvec2()
+    };
+    if let Some(image) = images.get_mut(&canvas.image) {
+        image.resize(canvas_extent(size));
+        image.sampler = if settings.pixel_mode {
+            ImageSampler::nearest()
+        } else {
+            ImageSampler::linear()
+        };
+    }
+    if settings.crt_filter {
+        if let Some(material) = crt_materials.get_mut(&canvas.crt) {
+            material.source_size = size.as_vec2().extend(0.0).extend(0.0);
+        }
+        commands
+            .entity(quad)
+            .remove::<MeshMaterial2d<ColorMaterial>>()
+            .insert(MeshMaterial2d(canvas.crt.clone()));
+    } else {
+        commands
+            .entity(quad)
+            .remove::<MeshMaterial2d<CrtMaterial>>()
+            .insert(MeshMaterial2d(canvas.plain.clone()));
+    }
+
+    let use_canvas = settings.pixel_mode || settings.crt_filter;
+    upscale_camera.is_active = use_canvas;
+    if use_canvas {
+        game_camera.target = RenderTarget::Image(canvas.image.clone().into());
+        commands.entity(game).remove::<IsDefaultUiCamera>();
+        commands.entity(upscale).insert(IsDefaultUiCamera);
+    } else {
+        game_camera.target = RenderTarget::Window(WindowRef::Primary);
+        commands.entity(upscale).remove::<IsDefaultUiCamera>();
+        commands.entity(game).insert(IsDefaultUiCamera);
+    }
+}
+
+/// The physical position and size of the largest play area the window fits,
+/// centred with bars on the sides or top and bottom, or `None` while the
+/// window is minimised or too thin to show a single pixel of it.
+fn fitted_viewport(window: &Window) -> Option<(UVec2, UVec2)> {
+    let physical = window.physical_size().as_vec2();
+    let size = (PLAY_AREA * (physical / PLAY_AREA).min_element()).floor();
+    if size.min_element() < 1.0 {
+        return None;
+    }
+    let position = ((physical - size) / 2.0).floor();
+    Some((position.as_uvec2(), size.as_uvec2()))
+}
+
+/// System that scales the play area to fit the window, keeping the last fit
+/// while there's nothing to draw into
+fn fit_play_area(
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut camera_query: Query<&mut Camera, Or<(With<GameCamera>, With<UpscaleCamera>)>>,
+) {
+    let Ok(window) = window_query.single() else {
+        return;
+    };
+    let Some(fitted) = fitted_viewport(window) else {
+        return;
+    };
+    for mut camera in &mut camera_query {
+        // The game camera fills the canvas whole
+        let wanted = match camera.target {
+            RenderTarget::Window(_) => Some(fitted),
+            _ => None,
+        };
+        let current = camera
+            .viewport
+            .as_ref()
+            .map(|viewport| (viewport.physical_position, viewport.physical_size));
+        if current == wanted {
+            continue;
+        }
+        camera.viewport = wanted.map(|(physical_position, physical_size)| Viewport {
+            physical_position,
+            physical_size,
+            ..default()
+        });
+    }
+}
+
+/// System that sizes the UI with the play area whenever the window is resized
+/// or moved to a monitor with another scale factor, so the HUD covers the
+/// same part of the play area everywhere
+fn scale_ui(
+    mut scaled: Local<bool>,
+    mut scale_events: EventReader<WindowScaleFactorChanged>,
+    mut resize_events: EventReader<WindowResized>,
+    window_query: Query<(Entity, &Window), With<PrimaryWindow>>,
+    mut ui_scale: ResMut<UiScale>,
+) {
+    let Ok((entity, window)) = window_query.single() else {
+        return;
+    };
+    let mut changed = false;
+    for event in scale_events.read().filter(|event| event.window == entity) {
+        info!(
+            scale_factor = event.scale_factor,
+            "Window scale factor changed"
+        );
+        changed = true;
+    }
+    changed |= resize_events.read().any(|event| event.window == entity);
+    if *scaled && !changed {
+        return;
+    }
+    let Some((_, size)) = fitted_viewport(window) else {
+        return;
+    };
+    *scaled = true;
+
+    let logical_height = size.y as f32 / window.scale_factor();
+    let scale = (logical_height / PLAY_AREA.y).max(MIN_UI_SCALE);
+    if ui_scale.0 != scale {
+        ui_scale.0 = scale;
+    }
+}
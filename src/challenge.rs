@@ -0,0 +1,343 @@
+use bevy::prelude::*;
+
+use crate::GameState;
+use crate::arena::Arena;
+use crate::difficulty::{Difficulty, DifficultyPreset};
+use crate::hud_layout::{HudAnchor, HudSlot};
+use crate::lanes::LaneMode;
+use crate::mutator::{Mutator, MutatorSelection, RunMutators};
+use crate::reset::RunScoped;
+use crate::rng::RunSeed;
+use crate::text_style::TextStyleLibrary;
+
+// Challenge code constants
+const CODE_VERSION: u8 = 1; // Bumped whenever the packing below changes
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ"; // Crockford's base 32
+const GROUP_LEN: usize = 4; // Characters between dashes
+pub const MAX_CODE_LEN: usize = 22; // 11 bytes of base 32 and its dashes
+
+/// How the player moves in a challenge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChallengeMode {
+    Normal,
+    Arena,
+    Lanes,
+}
+
+impl ChallengeMode {
+    const ALL: [ChallengeMode; 3] = [
+        ChallengeMode::Normal,
+        ChallengeMode::Arena,
+        ChallengeMode::Lanes,
+    ];
+}
+
+/// Everything that makes two runs the same run, packed into a short code
+/// players can pass around.
+///
+/// The code is a header byte (version, difficulty and mode), a byte of
+/// mutator flags, the seed's bytes without their trailing zeros and a
+/// checksum byte, written out in base 32 with a dash every few characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Challenge {
+    pub difficulty: DifficultyPreset,
+    pub mode: ChallengeMode,
+    pub mutators: Vec<Mutator>,
+    pub seed: u64,
+}
+
+impl Challenge {
+    /// The challenge the menu's choices would make, if it has a seed to share.
+    pub fn from_menu(
+        difficulty: &Difficulty,
+        arena: &Arena,
+        lane_mode: &LaneMode,
+        mutators: &MutatorSelection,
+        seed: &RunSeed,
+    ) -> Option<Self> {
+        Some(Self {
+            difficulty: difficulty.preset,
+            mode: mode_of(arena, lane_mode),
+            mutators: mutators.stack(),
+            seed: seed.fixed?,
+        })
+    }
+
+    /// The challenge the run that was just played makes.
+    pub fn of_run(
+        difficulty: &Difficulty,
+        arena: &Arena,
+        lane_mode: &LaneMode,
+        run_mutators: &RunMutators,
+        seed: &RunSeed,
+    ) -> Self {
+        Self {
+            difficulty: difficulty.preset,
+            mode: mode_of(arena, lane_mode),
+            mutators: run_mutators.0.clone(),
+            seed: seed.seed,
+        }
+    }
+
+    pub fn code(&self) -> String {
+        let mode = ChallengeMode::ALL
+            .iter()
+            .position(|&m| m == self.mode)
+            .unwrap_or(0) as u8;
+        let difficulty = DifficultyPreset::ALL
+            .iter()
+            .position(|&p| p == self.difficulty)
+            .unwrap_or(0) as u8;
+        let flags = Mutator::ALL
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| self.mutators.contains(m))
+            .fold(0u8, |flags, (bit, _)| flags | 1 << bit);
+
+        let mut bytes = vec![CODE_VERSION << 5 | difficulty << 2 | mode, flags];
+        let seed = self.seed.to_le_bytes();
+        let seed_len = seed
+            .iter()
+            .rposition(|&b| b != 0)
+            .map_or(0, |last| last + 1);
+        bytes.extend_from_slice(&seed[..seed_len]);
+        bytes.push(checksum(&bytes));
+
+        let mut code = String::new();
+        for (index, c) in to_base32(&bytes).chars().enumerate() {
+            if index > 0 && index % GROUP_LEN == 0 {
+                code.push('-');
+            }
+            code.push(c);
+        }
+        code
+    }
+
+    /// Reads a code back, or `None` if it's mistyped or from another version.
+    pub fn parse(code: &str) -> Option<Self> {
+        let mut bytes = from_base32(code)?;
+        let check = bytes.pop()?;
+        if bytes.len() < 2 || bytes.len() > 10 || checksum(&bytes) != check {
+            return None;
+        }
+        let header = bytes[0];
+        if header >> 5 != CODE_VERSION {
+            return None;
+        }
+        let difficulty = *DifficultyPreset::ALL.get(usize::from(header >> 2 & 0b111))?;
+        let mode = *ChallengeMode::ALL.get(usize::from(header & 0b11))?;
+        let mutators = Mutator::ALL
+            .into_iter()
+            .enumerate()
+            .filter(|(bit, _)| bytes[1] & 1 << bit != 0)
+            .map(|(_, m)| m)
+            .collect();
+        let mut seed = [0; 8];
+        seed[..bytes.len() - 2].copy_from_slice(&bytes[2..]);
+        Some(Self {
+            difficulty,
+            mode,
+            mutators,
+            seed: u64::from_le_bytes(seed),
+        })
+    }
+
+    /// Sets the menu up to play this challenge next.
+    pub fn apply(
+        &self,
+        difficulty: &mut Difficulty,
+        arena: &mut Arena,
+        lane_mode: &mut LaneMode,
+        mutators: &mut MutatorSelection,
+        seed: &mut RunSeed,
+    ) {
+        if difficulty.preset != self.difficulty {
+            *difficulty = Difficulty::load(self.difficulty);
+        }
+        arena.enabled = self.mode == ChallengeMode::Arena;
+        lane_mode.enabled = self.mode == ChallengeMode::Lanes;
+        mutators.weekly = false;
+        mutators.set(&self.mutators);
+        seed.fixed = Some(self.seed);
+    }
+}
+
+fn mode_of(arena: &Arena, lane_mode: &LaneMode) -> ChallengeMode {
+    if arena.enabled {
+        ChallengeMode::Arena
+    } else if lane_mode.enabled {
+        ChallengeMode::Lanes
+    } else {
+        ChallengeMode::Normal
+    }
+}
+
+/// Catches most single typos and swapped neighbours.
+fn checksum(bytes: &[u8]) -> u8 {
+    bytes
+        .iter()
+        .fold(0x5A, |check: u8, &byte| check.rotate_left(3) ^ byte)
+}
+
+fn to_base32(bytes: &[u8]) -> String {
+    let mut chars = String::new();
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for &byte in bytes {
+        buffer = buffer << 8 | u32::from(byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            chars.push(ALPHABET[(buffer >> bits & 0x1F) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        chars.push(ALPHABET[(buffer << (5 - bits) & 0x1F) as usize] as char);
+    }
+    chars
+}
+
+/// Decodes base 32, ignoring dashes and case and reading the easily confused
+/// I, L and O as 1, 1 and 0.
+fn from_base32(text: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::new();
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for c in text.chars().filter(|&c| c != '-') {
+        let c = match c.to_ascii_uppercase() {
+            'I' | 'L' => '1',
+            'O' => '0',
+            c => c,
+        };
+        let value = ALPHABET.iter().position(|&a| a as char == c)? as u32;
+        buffer = buffer << 5 | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits & 0xFF) as u8);
+        }
+    }
+    Some(bytes)
+}
+
+pub fn is_code_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-'
+}
+
+// --- Components ---
+
+#[derive(Component)]
+#[require(RunScoped)]
+struct ChallengeCode;
+
+pub struct ChallengePlugin;
+
+impl Plugin for ChallengePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::GameOver), show_challenge_code);
+    }
+}
+
+/// System to show the code of the run that just ended, so it can be shared
+fn show_challenge_code(
+    mut commands: Commands,
+    difficulty: Res<Difficulty>,
+    arena: Res<Arena>,
+    lane_mode: Res<LaneMode>,
+    run_mutators: Res<RunMutators>,
+    seed: Res<RunSeed>,
+    styles: Res<TextStyleLibrary>,
+) {
+    let challenge = Challenge::of_run(&difficulty, &arena, &lane_mode, &run_mutators, &seed);
+    commands.spawn((
+        Text::new(format!("Challenge code: {}", challenge.code())),
+        styles.body.ui(),
+        HudSlot::new(HudAnchor::Bottom, 10),
+        ChallengeCode,
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn challenge(
+        difficulty: DifficultyPreset,
+        mode: ChallengeMode,
+        mutators: &[Mutator],
+        seed: u64,
+    ) -> Challenge {
+        Challenge {
+            difficulty,
+            mode,
+            mutators: mutators.to_vec(),
+            seed,
+        }
+    }
+
+    #[test]
+    fn codes_parse_back_to_the_same_challenge() {
+        let challenges = [
+            challenge(DifficultyPreset::Easy, ChallengeMode::Normal, &[], 0),
+            challenge(
+                DifficultyPreset::Hard,
+                ChallengeMode::Arena,
+                &[Mutator::LowGravity, Mutator::DoubleSpawns],
+                0xDEAD_BEEF,
+            ),
+            challenge(
+                DifficultyPreset::Nightmare,
+                ChallengeMode::Lanes,
+                &Mutator::ALL,
+                u64::MAX,
+            ),
+        ];
+        for challenge in challenges {
+            let code = challenge.code();
+            assert!(code.len() <= MAX_CODE_LEN, "{code}");
+            assert_eq!(Challenge::parse(&code), Some(challenge));
+        }
+    }
+
+    #[test]
+    fn codes_keep_their_packing() {
+        let challenge = challenge(
+            DifficultyPreset::Hard,
+            ChallengeMode::Arena,
+            &[Mutator::LowGravity, Mutator::DoubleSpawns],
+            0xDEAD_BEEF,
+        );
+        // Codes already shared have to keep working
+        assert_eq!(challenge.code(), "542Y-ZFND-VSQ0");
+    }
+
+    #[test]
+    fn parsing_forgives_case_dashes_and_lookalikes() {
+        let expected = Challenge::parse("542Y-ZFND-VSQ0");
+        assert!(expected.is_some());
+        assert_eq!(Challenge::parse("542y-zfnd-vsqo"), expected);
+        assert_eq!(Challenge::parse("542YZFNDVSQ0"), expected);
+    }
+
+    #[test]
+    fn malformed_codes_are_rejected() {
+        for code in [
+            "",
+            "----",
+            "54",
+            // A mistyped character fails the checksum
+            "542Y-ZFMD-VSQ0",
+            // A character dropped off the end
+            "542Y-ZFND-VSQ",
+            // U isn't in the alphabet
+            "542U-ZFND-VSQ0",
+            // Valid checksums, but from another version, with a mode that
+            // doesn't exist and with a difficulty that doesn't exist
+            "8G02-N3R",
+            "4W02-MNR",
+            "6G02-N4R",
+        ] {
+            assert_eq!(Challenge::parse(code), None, "{code}");
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use bevy::prelude::*;
+
+use crate::GameState;
+use crate::focus::MenuNav;
+use crate::profile::{ActiveProfile, PROGRESS_FILE, Progress};
+use crate::text_style::TextStyleLibrary;
+
+// Changelog constants
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+const CHANGELOG: &str = include_str!("../CHANGELOG.md");
+const HEADING_COLOR: Color = Color::srgb(1.0, 0.85, 0.3);
+
+/// The release notes as shown in game: headings and their lines, skipping
+/// the file's title.
+fn changelog_lines() -> impl Iterator<Item = (bool, &'static str)> {
+    CHANGELOG
+        .lines()
+        .filter(|line| !line.starts_with("# "))
+        .map(|line| match line.strip_prefix("## ") {
+            Some(version) => (true, version),
+            None => (false, line),
+        })
+}
+
+/// Whether this version came out since the profile last read the changelog.
+pub fn has_news(progress: &Progress) -> bool {
+    progress.seen_version != VERSION
+}
+
+// --- Components ---
+
+#[derive(Component)]
+struct ChangelogScreen;
+
+pub struct ChangelogPlugin;
+
+impl Plugin for ChangelogPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(GameState::Changelog),
+            (spawn_changelog_screen, mark_changelog_seen),
+        )
+        .add_systems(
+            Update,
+            changelog_screen_input.run_if(in_state(GameState::Changelog)),
+        )
+        .add_systems(OnExit(GameState::Changelog), despawn_changelog_screen);
+    }
+}
+
+/// System to spawn the "What's new" screen with the embedded changelog
+fn spawn_changelog_screen(mut commands: Commands, styles: Res<TextStyleLibrary>) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(40.0),
+                left: Val::Px(40.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(4.0),
+                ..default()
+            },
+            ChangelogScreen,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(format!("What's new\n\nYou are playing version {VERSION}")),
+                styles.body.ui(),
+            ));
+            for (heading, line) in changelog_lines() {
+                if heading {
+                    let label = if line == VERSION {
+                        format!("\n{line} (this version)")
+                    } else {
+                        format!("\n{line}")
+                    };
+                    parent.spawn((Text::new(label), styles.body.ui(), TextColor(HEADING_COLOR)));
+                } else if !line.is_empty() {
+                    parent.spawn((Text::new(format!("  {line}")), styles.body.ui()));
+                }
+            }
+            parent.spawn((Text::new("\nEsc: Back"), styles.body.ui()));
+        });
+}
+
+/// System that remembers the profile has read this version's notes, which
+/// takes the "new" badge off the menu
+fn mark_changelog_seen(profile: Res<ActiveProfile>, mut progress: ResMut<Progress>) {
+    if !has_news(&progress) {
+        return;
+    }
+    progress.seen_version = VERSION.to_string();
+    profile.save(PROGRESS_FILE, &*progress);
+}
+
+/// System to leave the "What's new" screen
+fn changelog_screen_input(nav: Res<MenuNav>, mut next_state: ResMut<NextState<GameState>>) {
+    if nav.back {
+        next_state.set(GameState::Menu);
+    }
+}
+
+/// System to remove the "What's new" screen
+fn despawn_changelog_screen(mut commands: Commands, query: Query<Entity, With<ChangelogScreen>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
@@ -0,0 +1,246 @@
+use bevy::prelude::*;
+
+use crate::settings::Settings;
+use crate::{GameState, Player, collide};
+
+// Collision constants
+const FORGIVING_HITBOX_SCALE: f32 = 0.7; // Player hitbox size relative to its sprite
+pub const COLLISION_GRACE: f32 = 0.1; // Seconds of overlap forgiven before a hit counts
+const SWEEP_SAMPLES: usize = 4; // Points along a sweep where spinning boxes are tested exactly
+
+/// A box as it moved during the frame, for swept collision tests.
+#[derive(Clone, Copy)]
+pub struct Sweep {
+    /// Where the box ended up this frame.
+    pub position: Vec3,
+    /// How far it moved this frame.
+    pub displacement: Vec2,
+    pub size: Vec2,
+    pub rotation: Quat,
+}
+
+impl Sweep {
+    /// Where the box was at `t` through the frame, from 0 (start) to 1 (now).
+    pub fn position_at(&self, t: f32) -> Vec3 {
+        self.position - (self.displacement * (1.0 - t)).extend(0.0)
+    }
+
+    /// Half the size of the axis-aligned box around the rotated one.
+    fn half_bounds(&self) -> Vec2 {
+        let u = (self.rotation * Vec3::X).truncate().abs();
+        let v = (self.rotation * Vec3::Y).truncate().abs();
+        (u * self.size.x + v * self.size.y) / 2.0
+    }
+}
+
+// --- Components ---
+
+/// Hitbox used for collisions, independent of the sprite's transform so
+/// visual effects (scaling, tilting) never change what counts as a hit.
+#[derive(Component, Clone, Copy, Reflect)]
+#[reflect(Component)]
+#[require(CollisionLayer)]
+pub struct Collider {
+    pub size: Vec2,
+    /// Per-entity factor applied on top of `size`.
+    pub scale: f32,
+}
+
+impl Collider {
+    pub fn new(size: Vec2) -> Self {
+        Self { size, scale: 1.0 }
+    }
+
+    /// The size actually tested for collisions.
+    pub fn effective_size(&self) -> Vec2 {
+        self.size * self.scale
+    }
+}
+
+/// What a collider takes part in. Hazards (enemies, bullets) end the run on
+/// contact; debris is scenery that just breaks when the player flies into it.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CollisionLayer {
+    #[default]
+    Hazard,
+    Debris,
+}
+
+/// How long an enemy has been overlapping the player, for the forgiving hitbox.
+#[derive(Component)]
+pub struct Overlap(pub f32);
+
+pub struct CollisionPlugin;
+
+impl Plugin for CollisionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            apply_hitbox_setting.run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+/// When during the frame an axis-aligned box `a` first touched a box `b` that
+/// may be spinning, from 0 (start) to 1 (now), or `None` if they never did.
+/// Testing the whole path rather than where the boxes ended up means fast
+/// enemies and bullets can't skip past the player between two frames.
+pub fn time_of_impact(a: &Sweep, b: &Sweep) -> Option<f32> {
+    // Empty boxes, like a danger zone that hasn't started rising, never touch
+    if a.size.min_element() <= 0.0 || b.size.min_element() <= 0.0 {
+        return None;
+    }
+    // Slab test of `b`'s bounds moving relative to `a`
+    let start = (b.position_at(0.0) - a.position_at(0.0)).truncate();
+    let motion = b.displacement - a.displacement;
+    let reach = a.size / 2.0 + b.half_bounds();
+    let (mut enter, mut exit) = (0.0_f32, 1.0_f32);
+    for axis in 0..2 {
+        let (offset, speed, reach) = (start[axis], motion[axis], reach[axis]);
+        if speed.abs() < f32::EPSILON {
+            if offset.abs() >= reach {
+                return None;
+            }
+            continue;
+        }
+        let (t0, t1) = ((-reach - offset) / speed, (reach - offset) / speed);
+        enter = enter.max(t0.min(t1));
+        exit = exit.min(t0.max(t1));
+        if enter > exit {
+            return None;
+        }
+    }
+    if b.rotation == Quat::IDENTITY {
+        return Some(enter);
+    }
+
+    // The bounds of a rotated box are loose, so test the box itself along the
+    // stretch of the path where the bounds overlap
+    (0..SWEEP_SAMPLES).find_map(|sample| {
+        let t = enter + (exit - enter) * sample as f32 / (SWEEP_SAMPLES - 1) as f32;
+        collide(
+            a.position_at(t),
+            a.size,
+            b.position_at(t),
+            b.size,
+            b.rotation,
+        )
+        .then_some(t)
+    })
+}
+
+/// System that shrinks the player's hitbox when the forgiving hitbox setting is on
+fn apply_hitbox_setting(settings: Res<Settings>, mut query: Query<&mut Collider, With<Player>>) {
+    let scale = if settings.forgiving_hitbox {
+        FORGIVING_HITBOX_SCALE
+    } else {
+        1.0
+    };
+    for mut collider in &mut query {
+        if collider.scale != scale {
+            collider.scale = scale;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_4;
+
+    use super::*;
+
+    const PLAYER: Vec2 = Vec2::splat(20.0);
+    const ENEMY: Vec2 = Vec2::splat(10.0);
+
+    fn resting(size: Vec2) -> Sweep {
+        Sweep {
+            position: Vec3::ZERO,
+            displacement: Vec2::ZERO,
+            size,
+            rotation: Quat::IDENTITY,
+        }
+    }
+
+    /// An enemy that moved from `from` to `to` this frame.
+    fn falling(from: Vec2, to: Vec2, rotation: Quat) -> Sweep {
+        Sweep {
+            position: to.extend(0.0),
+            displacement: to - from,
+            size: ENEMY,
+            rotation,
+        }
+    }
+
+    #[test]
+    fn fast_enemies_passing_through_the_player_hit() {
+        let enemy = falling(
+            Vec2::new(0.0, 100.0),
+            Vec2::new(0.0, -100.0),
+            Quat::IDENTITY,
+        );
+        // Neither end of the frame overlaps, only the path in between
+        assert!(!collide(
+            enemy.position,
+            ENEMY,
+            Vec3::ZERO,
+            PLAYER,
+            Quat::IDENTITY
+        ));
+        let t = time_of_impact(&resting(PLAYER), &enemy).unwrap();
+        // The edges meet once the enemy has covered 85 of its 200 pixels
+        assert!((t - 0.425).abs() < 1e-4, "{t}");
+    }
+
+    #[test]
+    fn spinning_enemies_passing_through_the_player_hit() {
+        let enemy = falling(
+            Vec2::new(5.0, 100.0),
+            Vec2::new(5.0, -100.0),
+            Quat::from_rotation_z(FRAC_PI_4),
+        );
+        assert!(time_of_impact(&resting(PLAYER), &enemy).is_some());
+    }
+
+    #[test]
+    fn sweeps_just_to_the_side_miss() {
+        // The boxes would need to be closer than 15 to touch
+        let enemy = falling(
+            Vec2::new(15.5, 100.0),
+            Vec2::new(15.5, -100.0),
+            Quat::IDENTITY,
+        );
+        assert_eq!(time_of_impact(&resting(PLAYER), &enemy), None);
+    }
+
+    #[test]
+    fn sweeps_stopping_just_short_miss() {
+        let enemy = falling(Vec2::new(0.0, 100.0), Vec2::new(0.0, 15.5), Quat::IDENTITY);
+        assert_eq!(time_of_impact(&resting(PLAYER), &enemy), None);
+    }
+
+    #[test]
+    fn spinning_sweeps_through_the_corner_gap_miss() {
+        // Passing diagonally past the player's corner: the enemy's bounds
+        // overlap the player, but the rotated box itself never does
+        let enemy = falling(
+            Vec2::new(-100.0, 130.0),
+            Vec2::new(100.0, -70.0),
+            Quat::from_rotation_z(FRAC_PI_4),
+        );
+        assert_eq!(time_of_impact(&resting(PLAYER), &enemy), None);
+    }
+
+    #[test]
+    fn empty_boxes_never_touch() {
+        let enemy = falling(
+            Vec2::new(0.0, 100.0),
+            Vec2::new(0.0, -100.0),
+            Quat::IDENTITY,
+        );
+        assert_eq!(time_of_impact(&resting(Vec2::ZERO), &enemy), None);
+        assert_eq!(
+            time_of_impact(&resting(Vec2::new(20.0, -1.0)), &enemy),
+            None
+        );
+    }
+}
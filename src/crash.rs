@@ -0,0 +1,167 @@
+use std::backtrace::Backtrace;
+use std::env;
+use std::fs;
+use std::panic::{self, PanicHookInfo};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use bevy::prelude::*;
+use bevy::time::common_conditions::on_timer;
+
+use crate::GameState;
+use crate::difficulty::Difficulty;
+use crate::rng::{self, RunSeed};
+use crate::save;
+use crate::score::Score;
+use crate::stats::RunStats;
+
+// Crash handling constants
+const CRASHES_DIR: &str = "crashes";
+const SESSION_MARKER: &str = "session.lock"; // Present while the game runs, left behind by a crash
+const SAFE_MODE_FLAG: &str = "--safe-mode";
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+static SAFE_MODE: AtomicBool = AtomicBool::new(false);
+// What the game was doing, kept outside the world so the panic hook can read it
+static CRASH_CONTEXT: Mutex<String> = Mutex::new(String::new());
+
+// --- Resources ---
+
+/// How this session was launched with respect to crashes.
+#[derive(Resource)]
+pub struct CrashInfo {
+    /// The previous session didn't shut down cleanly.
+    pub last_session_crashed: bool,
+}
+
+pub struct CrashPlugin;
+
+impl Plugin for CrashPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, log_session_start).add_systems(
+            Update,
+            update_crash_context.run_if(on_timer(Duration::from_secs(1))),
+        );
+    }
+}
+
+/// Whether settings and data-file overrides are skipped this session.
+pub fn safe_mode() -> bool {
+    SAFE_MODE.load(Ordering::Relaxed)
+}
+
+fn marker_path() -> PathBuf {
+    save::data_dir().join(SESSION_MARKER)
+}
+
+/// Installs the panic hook and marks the session as running. Safe mode is on
+/// when asked for with `--safe-mode` or when the last session crashed.
+pub fn start_session() -> CrashInfo {
+    let last_session_crashed = marker_path().exists();
+    let requested = env::args().any(|arg| arg == SAFE_MODE_FLAG);
+    SAFE_MODE.store(requested || last_session_crashed, Ordering::Relaxed);
+    if let Err(err) =
+        fs::create_dir_all(save::data_dir()).and_then(|_| fs::write(marker_path(), VERSION))
+    {
+        // Logging isn't set up this early
+        eprintln!("Could not write the session marker: {err}");
+    }
+
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let log = write_crash_log(info);
+        default_hook(info);
+        show_crash_dialog(log);
+    }));
+
+    CrashInfo {
+        last_session_crashed,
+    }
+}
+
+/// Clears the session marker after a clean shutdown.
+pub fn end_session() {
+    let _ = fs::remove_file(marker_path());
+}
+
+/// Writes the panic, a backtrace and the last known game state to the crash
+/// folder, returning where it went.
+fn write_crash_log(info: &PanicHookInfo) -> Option<PathBuf> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    // try_lock, the panic may have happened while the context was being updated
+    let context = CRASH_CONTEXT
+        .try_lock()
+        .map(|context| context.clone())
+        .unwrap_or_default();
+    let contents = format!(
+        "Rusty Dodger {VERSION} crashed at unix time {timestamp}\n\n{info}\n\nGame state:\n{context}\n\nBacktrace:\n{}\n",
+        Backtrace::force_capture()
+    );
+
+    let dir = save::data_dir().join(CRASHES_DIR);
+    let path = dir.join(format!("crash-{timestamp}.log"));
+    match fs::create_dir_all(&dir).and_then(|_| fs::write(&path, contents)) {
+        Ok(()) => {
+            error!(path = %path.display(), "Crash log written");
+            Some(path)
+        }
+        Err(err) => {
+            error!("Could not write crash log: {err}");
+            None
+        }
+    }
+}
+
+/// Tells the player what happened instead of the window just vanishing.
+fn show_crash_dialog(log: Option<PathBuf>) {
+    let mut description = "Rusty Dodger ran into a problem and has to close.".to_string();
+    if let Some(log) = log {
+        description.push_str(&format!("\n\nA crash log was saved to:\n{}", log.display()));
+    }
+    description.push_str(&format!(
+        "\n\nThe next launch starts in safe mode. You can also start it with {SAFE_MODE_FLAG}."
+    ));
+
+    rfd::MessageDialog::new()
+        .set_level(rfd::MessageLevel::Error)
+        .set_title("Rusty Dodger crashed")
+        .set_description(description)
+        .set_buttons(rfd::MessageButtons::Ok)
+        .show();
+}
+
+/// System that notes how the session started
+fn log_session_start(crash_info: Res<CrashInfo>) {
+    if crash_info.last_session_crashed {
+        warn!("The last session didn't shut down cleanly");
+    }
+    if safe_mode() {
+        warn!("Starting in safe mode: settings and data file overrides are skipped");
+    }
+}
+
+/// System that keeps a summary of the run around for crash logs
+fn update_crash_context(
+    state: Res<State<GameState>>,
+    score: Res<Score>,
+    stats: Res<RunStats>,
+    difficulty: Res<Difficulty>,
+    seed: Res<RunSeed>,
+) {
+    let summary = format!(
+        "state: {:?}\ndifficulty: {}\nseed: {}\nscore: {}\nrun time: {:.1}s\nsafe mode: {}",
+        state.get(),
+        difficulty.preset.name(),
+        rng::format_seed(seed.seed),
+        score.points(),
+        stats.elapsed(),
+        safe_mode()
+    );
+    if let Ok(mut context) = CRASH_CONTEXT.lock() {
+        *context = summary;
+    }
+}
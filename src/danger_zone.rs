@@ -0,0 +1,93 @@
+use bevy::prelude::*;
+use rusty_dodger::math;
+
+use crate::arena::Arena;
+use crate::collision::Collider;
+use crate::reset::{RunScoped, RunSetup};
+use crate::sets::GameSet;
+use crate::settings::Settings;
+use crate::stats::RunStats;
+use crate::window::WindowMetrics;
+use crate::{GameState, RunPhase};
+
+// Danger zone constants
+const ZONE_DELAY: f32 = 10.0; // Seconds into a run before the zone starts rising
+const ZONE_RISE_TIME: f32 = 120.0; // Seconds it takes to reach its full height
+const ZONE_MAX_HEIGHT: f32 = 0.5; // Fraction of the play area it covers at most
+const ZONE_COLOR: Color = Color::srgba(0.9, 0.1, 0.15, 0.35);
+const ZONE_PULSE_SPEED: f32 = 3.0; // Radians per second
+const ZONE_PULSE: f32 = 0.1; // How far the zone's alpha swings either way
+const ZONE_Z: f32 = -0.2; // Over the scenery, under enemies and the player
+
+// --- Components ---
+
+/// A lethal band rising from the bottom of the play area during arena runs,
+/// pushing the player ever higher. Hits the player like an enemy would, so
+/// the bubble and the forgiving hitbox work against it as usual.
+#[derive(Component)]
+#[require(RunScoped)]
+pub struct DangerZone;
+
+pub struct DangerZonePlugin;
+
+impl Plugin for DangerZonePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(RunSetup, spawn_danger_zone).add_systems(
+            Update,
+            (
+                raise_danger_zone
+                    .in_set(GameSet::Simulation)
+                    .run_if(in_state(RunPhase::Alive)),
+                pulse_danger_zone,
+            )
+                .run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+/// How tall the zone is `elapsed` seconds into a run.
+fn zone_height(elapsed: f32, area_height: f32) -> f32 {
+    math::ramp(
+        0.0,
+        area_height * ZONE_MAX_HEIGHT,
+        elapsed - ZONE_DELAY,
+        ZONE_RISE_TIME,
+    )
+}
+
+/// System to lay the zone along the bottom of arena runs that have it turned on
+fn spawn_danger_zone(mut commands: Commands, arena: Res<Arena>, settings: Res<Settings>) {
+    if !arena.enabled || !settings.danger_zone {
+        return;
+    }
+    commands.spawn((
+        Sprite::from_color(ZONE_COLOR, Vec2::ONE),
+        Transform::from_xyz(0.0, 0.0, ZONE_Z).with_scale(Vec3::ZERO),
+        Collider::new(Vec2::ZERO),
+        DangerZone,
+    ));
+}
+
+/// System that raises the zone with the run's time, keeping it flush with
+/// the bottom edge
+fn raise_danger_zone(
+    stats: Res<RunStats>,
+    window: Res<WindowMetrics>,
+    mut query: Query<(&mut Transform, &mut Collider), With<DangerZone>>,
+) {
+    let height = zone_height(stats.elapsed(), window.height());
+    let size = Vec2::new(window.width(), height);
+    for (mut transform, mut collider) in &mut query {
+        transform.translation.y = (height - window.height()) / 2.0;
+        transform.scale = size.extend(1.0);
+        collider.size = size;
+    }
+}
+
+/// System that makes the zone throb, so it reads as a threat and not scenery
+fn pulse_danger_zone(time: Res<Time>, mut query: Query<&mut Sprite, With<DangerZone>>) {
+    let alpha = ZONE_COLOR.alpha() + ZONE_PULSE * (time.elapsed_secs() * ZONE_PULSE_SPEED).sin();
+    for mut sprite in &mut query {
+        sprite.color.set_alpha(alpha);
+    }
+}
@@ -0,0 +1,40 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use bevy::log::warn;
+use serde::de::DeserializeOwned;
+
+use crate::crash;
+
+/// The game's `assets` folder, found the same way Bevy's asset server finds it.
+pub fn assets_dir() -> PathBuf {
+    let base = env::var_os("BEVY_ASSET_ROOT")
+        .or_else(|| env::var_os("CARGO_MANIFEST_DIR"))
+        .map(PathBuf::from)
+        .or_else(|| {
+            env::current_exe()
+                .ok()
+                .and_then(|exe| exe.parent().map(PathBuf::from))
+        })
+        .unwrap_or_default();
+    base.join("assets")
+}
+
+/// Loads a RON data file from the assets folder, falling back to the copy
+/// built into the binary if the file is missing or doesn't parse. Safe mode
+/// always uses the built-in copy.
+pub fn load_ron<T: DeserializeOwned>(relative_path: &str, builtin: &str) -> T {
+    let path = assets_dir().join(relative_path);
+    if !crash::safe_mode() {
+        match fs::read_to_string(&path) {
+            Ok(contents) => match ron::from_str(&contents) {
+                Ok(value) => return value,
+                Err(err) => warn!(path = %path.display(), "Ignoring invalid data file: {err}"),
+            },
+            Err(err) => warn!(path = %path.display(), "Could not read data file: {err}"),
+        }
+    }
+    ron::from_str(builtin)
+        .unwrap_or_else(|err| panic!("built-in copy of {relative_path} is invalid: {err}"))
+}
@@ -0,0 +1,95 @@
+use bevy::prelude::*;
+
+use crate::despawn::DespawnQueue;
+use crate::sets::GameSet;
+use crate::settings::Settings;
+use crate::stress::StressTest;
+use crate::swarm::PatternEnemy;
+use crate::{Enemy, GameState, RunPhase};
+
+// --- Components ---
+
+/// When an enemy turned up, relative to the others: lower is older.
+#[derive(Component)]
+struct SpawnOrder(u64);
+
+// --- Resources ---
+
+/// The order the next enemy gets.
+#[derive(Resource, Default)]
+struct NextSpawnOrder(u64);
+
+pub struct DensityPlugin;
+
+impl Plugin for DensityPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<NextSpawnOrder>().add_systems(
+            Update,
+            (
+                number_enemies,
+                cull_enemies
+                    .in_set(GameSet::Cleanup)
+                    .run_if(in_state(RunPhase::Alive)),
+            )
+                .chain()
+                .run_if(in_state(GameState::Playing).and(density_capped)),
+        );
+    }
+}
+
+/// Whether the settings limit how many enemies can be out at once. Stress
+/// tests ignore the limit, they're there to find out what the game can take.
+fn density_capped(settings: Res<Settings>, stress: Res<StressTest>) -> bool {
+    settings.max_enemies > 0 && !stress.enabled
+}
+
+/// Whether there's room for another enemy. The spawner waits while there
+/// isn't, so it never adds to a wall the player can't read.
+pub fn below_cap(
+    settings: Res<Settings>,
+    stress: Res<StressTest>,
+    query: Query<(), With<Enemy>>,
+) -> bool {
+    settings.max_enemies == 0
+        || stress.enabled
+        || query.iter().count() < settings.max_enemies as usize
+}
+
+/// System that numbers new enemies in the order they turn up
+fn number_enemies(
+    mut commands: Commands,
+    mut next: ResMut<NextSpawnOrder>,
+    query: Query<Entity, (With<Enemy>, Without<SpawnOrder>)>,
+) {
+    for entity in &query {
+        commands.entity(entity).insert(SpawnOrder(next.0));
+        next.0 += 1;
+    }
+}
+
+/// System that removes the oldest enemies over the limit, like extras from
+/// splits and mirrored spawns. Pattern enemies are never culled, so a
+/// pattern's way through always looks the way it was made.
+fn cull_enemies(
+    settings: Res<Settings>,
+    mut despawn_queue: ResMut<DespawnQueue>,
+    enemy_query: Query<(), With<Enemy>>,
+    cullable_query: Query<(Entity, &SpawnOrder), (With<Enemy>, Without<PatternEnemy>)>,
+) {
+    let excess = enemy_query
+        .iter()
+        .count()
+        .saturating_sub(settings.max_enemies as usize);
+    if excess == 0 {
+        return;
+    }
+    let mut cullable: Vec<(Entity, u64)> = cullable_query
+        .iter()
+        .map(|(entity, order)| (entity, order.0))
+        .collect();
+    cullable.sort_unstable_by_key(|&(_, order)| order);
+    for (entity, _) in cullable.into_iter().take(excess) {
+        despawn_queue.push(entity);
+    }
+    debug!(excess, "Culled enemies over the limit");
+}
@@ -0,0 +1,36 @@
+use bevy::ecs::entity::EntityHashSet;
+use bevy::prelude::*;
+
+// --- Resources ---
+
+/// Entities to despawn at the end of the frame.
+///
+/// Several systems can decide to kill the same entity in one frame (a bomb and
+/// the off-screen check, say). Queuing here instead of despawning directly
+/// means each entity is despawned exactly once and entities that are already
+/// gone are skipped quietly.
+#[derive(Resource, Default)]
+pub struct DespawnQueue(EntityHashSet);
+
+impl DespawnQueue {
+    pub fn push(&mut self, entity: Entity) {
+        self.0.insert(entity);
+    }
+}
+
+pub struct DespawnPlugin;
+
+impl Plugin for DespawnPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DespawnQueue>()
+            .add_systems(Last, flush_despawn_queue);
+    }
+}
+
+/// System that despawns everything queued this frame
+fn flush_despawn_queue(mut commands: Commands, mut queue: ResMut<DespawnQueue>) {
+    for entity in queue.0.drain() {
+        // Children go with their parent, so some entries may be gone already
+        commands.entity(entity).try_despawn();
+    }
+}
@@ -0,0 +1,234 @@
+use bevy::prelude::*;
+use rand::Rng;
+use rusty_dodger::math;
+use serde::{Deserialize, Serialize};
+
+use crate::data;
+use crate::enemy::EnemyKind;
+
+/// Named difficulty levels, each backed by a data file in `assets/difficulty`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DifficultyPreset {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+    Nightmare,
+}
+
+impl DifficultyPreset {
+    pub const ALL: [DifficultyPreset; 4] = [
+        DifficultyPreset::Easy,
+        DifficultyPreset::Normal,
+        DifficultyPreset::Hard,
+        DifficultyPreset::Nightmare,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            DifficultyPreset::Easy => "Easy",
+            DifficultyPreset::Normal => "Normal",
+            DifficultyPreset::Hard => "Hard",
+            DifficultyPreset::Nightmare => "Nightmare",
+        }
+    }
+
+    fn file(self) -> &'static str {
+        match self {
+            DifficultyPreset::Easy => "difficulty/easy.ron",
+            DifficultyPreset::Normal => "difficulty/normal.ron",
+            DifficultyPreset::Hard => "difficulty/hard.ron",
+            DifficultyPreset::Nightmare => "difficulty/nightmare.ron",
+        }
+    }
+
+    fn builtin(self) -> &'static str {
+        match self {
+            DifficultyPreset::Easy => include_str!("../assets/difficulty/easy.ron"),
+            DifficultyPreset::Normal => include_str!("../assets/difficulty/normal.ron"),
+            DifficultyPreset::Hard => include_str!("../assets/difficulty/hard.ron"),
+            DifficultyPreset::Nightmare => include_str!("../assets/difficulty/nightmare.ron"),
+        }
+    }
+
+    /// The preset after this one, wrapping around.
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|&p| p == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// The preset before this one, wrapping around.
+    pub fn previous(self) -> Self {
+        let index = Self::ALL.iter().position(|&p| p == self).unwrap_or(0);
+        Self::ALL[(index + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+}
+
+/// How the time between enemy spawns shrinks over a run.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SpawnCurve {
+    /// Seconds between spawns at the start of a run.
+    pub start_interval: f32,
+    /// Seconds between spawns once the ramp is over.
+    pub min_interval: f32,
+    /// Seconds it takes to go from `start_interval` to `min_interval`.
+    pub ramp_seconds: f32,
+}
+
+impl SpawnCurve {
+    pub fn interval(&self, elapsed: f32) -> f32 {
+        math::ramp(
+            self.start_interval,
+            self.min_interval,
+            elapsed,
+            self.ramp_seconds,
+        )
+    }
+}
+
+/// How often one enemy kind is picked relative to the others.
+#[derive(Debug, Clone, Deserialize)]
+pub struct KindWeight {
+    pub kind: EnemyKind,
+    pub weight: f32,
+}
+
+/// Which enemies shoot at the player, and from how far into a run.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BulletConfig {
+    /// Enemy kinds that can be armed.
+    pub shooters: Vec<EnemyKind>,
+    /// Chance a spawned enemy of a shooter kind is armed.
+    pub chance: f32,
+    /// Seconds into the run before any enemy is armed.
+    pub start_seconds: f32,
+    /// Seconds between volleys from one shooter.
+    pub fire_interval: f32,
+    pub speed: f32,
+}
+
+/// A strip above the player that early spawns are kept out of, so a run can't
+/// open with an enemy dropped straight onto the ship.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SpawnSafety {
+    /// Half the width of the strip, in pixels.
+    pub half_width: f32,
+    /// Seconds into the run the strip is kept clear for.
+    pub seconds: f32,
+}
+
+impl Default for SpawnSafety {
+    fn default() -> Self {
+        Self {
+            half_width: 60.0,
+            seconds: 30.0,
+        }
+    }
+}
+
+impl SpawnSafety {
+    /// Moves a spawn at `x` so an enemy `half_size` wide, which drifts `drift`
+    /// sideways before it reaches the player's row, lands clear of the strip
+    /// above `player_x`. It goes to whichever side it was nearer that still
+    /// spawns between `x_min` and `x_max`.
+    pub fn keep_clear(
+        &self,
+        x: f32,
+        half_size: f32,
+        drift: f32,
+        player_x: f32,
+        x_min: f32,
+        x_max: f32,
+    ) -> f32 {
+        let clearance = self.half_width + half_size;
+        let landing = x + drift;
+        if (landing - player_x).abs() >= clearance {
+            return x;
+        }
+        let left = player_x - clearance - drift;
+        let right = player_x + clearance - drift;
+        let sides = if landing < player_x {
+            [left, right]
+        } else {
+            [right, left]
+        };
+        // A window too narrow for either side keeps the spawn where it was
+        sides
+            .into_iter()
+            .find(|side| (x_min..=x_max).contains(side))
+            .unwrap_or(x)
+    }
+}
+
+/// Every tunable parameter that changes between difficulty presets.
+#[derive(Debug, Clone, Deserialize)]
+pub struct DifficultyConfig {
+    pub player_speed: f32,
+    pub enemy_speed: f32,
+    pub spawn: SpawnCurve,
+    pub kinds: Vec<KindWeight>,
+    /// Chance each spawned enemy is an elite.
+    #[serde(default)]
+    pub elite_chance: f32,
+    /// Enemy projectiles, off when missing.
+    #[serde(default)]
+    pub bullets: Option<BulletConfig>,
+    /// Player size relative to the usual one.
+    #[serde(default = "default_player_scale")]
+    pub player_scale: f32,
+    /// Swap left and right.
+    #[serde(default)]
+    pub mirrored_controls: bool,
+    /// Send every enemy down with its mirror image on the other side.
+    #[serde(default)]
+    pub mirrored_spawns: bool,
+    /// Strip kept clear above the player when safe spawns are on.
+    #[serde(default)]
+    pub spawn_safety: SpawnSafety,
+}
+
+fn default_player_scale() -> f32 {
+    1.0
+}
+
+impl DifficultyConfig {
+    /// Picks an enemy kind according to the configured weights.
+    pub fn pick_kind(&self, rng: &mut impl Rng) -> EnemyKind {
+        let total: f32 = self.kinds.iter().map(|k| k.weight.max(0.0)).sum();
+        if total <= 0.0 {
+            return EnemyKind::Basic;
+        }
+        let mut roll = rng.random_range(0.0..total);
+        for entry in &self.kinds {
+            roll -= entry.weight.max(0.0);
+            if roll < 0.0 {
+                return entry.kind;
+            }
+        }
+        self.kinds.last().map_or(EnemyKind::Basic, |k| k.kind)
+    }
+}
+
+// --- Resources ---
+
+/// The difficulty the next run is played at.
+#[derive(Resource)]
+pub struct Difficulty {
+    pub preset: DifficultyPreset,
+    pub config: DifficultyConfig,
+}
+
+impl Difficulty {
+    pub fn load(preset: DifficultyPreset) -> Self {
+        Self {
+            preset,
+            config: data::load_ron(preset.file(), preset.builtin()),
+        }
+    }
+}
+
+impl Default for Difficulty {
+    fn default() -> Self {
+        Self::load(DifficultyPreset::default())
+    }
+}
@@ -0,0 +1,59 @@
+use bevy::prelude::*;
+
+use crate::despawn::DespawnQueue;
+
+const FLASH_FRACTION: f32 = 0.2; // Part of the animation spent flashing white
+
+// --- Components ---
+
+/// Plays a short death animation (white flash, then shrink and fade) and
+/// despawns the entity once it's over. Works on any entity with a sprite.
+#[derive(Component)]
+pub struct Dying {
+    timer: Timer,
+    start: Option<(Vec3, Color)>,
+}
+
+impl Dying {
+    pub fn new(duration: f32) -> Self {
+        Self {
+            timer: Timer::from_seconds(duration, TimerMode::Once),
+            start: None,
+        }
+    }
+}
+
+pub struct DyingPlugin;
+
+impl Plugin for DyingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, animate_dying);
+    }
+}
+
+/// System that animates dying entities and despawns them when done
+fn animate_dying(
+    time: Res<Time>,
+    mut despawn_queue: ResMut<DespawnQueue>,
+    mut query: Query<(Entity, &mut Dying, &mut Transform, &mut Sprite)>,
+) {
+    for (entity, mut dying, mut transform, mut sprite) in &mut query {
+        let (start_scale, start_color) =
+            *dying.start.get_or_insert((transform.scale, sprite.color));
+
+        dying.timer.tick(time.delta());
+        if dying.timer.finished() {
+            despawn_queue.push(entity);
+            continue;
+        }
+
+        let progress = dying.timer.fraction();
+        if progress < FLASH_FRACTION {
+            sprite.color = Color::WHITE;
+        } else {
+            let t = (progress - FLASH_FRACTION) / (1.0 - FLASH_FRACTION);
+            transform.scale = start_scale * (1.0 - t);
+            sprite.color = start_color.with_alpha(start_color.alpha() * (1.0 - t));
+        }
+    }
+}
@@ -0,0 +1,254 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::dying::Dying;
+use crate::score::{ScoreEvent, ScoreReason};
+use crate::shapes::Shape;
+use crate::window::WindowMetrics;
+use crate::{Enemy, GameState, RunPhase, Velocity};
+
+// Elite constants
+const SHIELD_HITS: u8 = 2; // Bomb hits a shielded elite takes to destroy
+const PHASE_INTERVAL: f32 = 0.8; // Seconds between a phasing elite blinking in and out
+const PHASED_ALPHA: f32 = 0.25; // Opacity while a phasing elite can't be hit
+const HEAVY_BOOST: f32 = 1.8; // Fall speed multiplier once a heavy elite passes mid-screen
+const SECOND_MODIFIER_CHANCE: f64 = 0.2; // Chance an elite stacks a second modifier
+const ELITE_DODGE_POINTS: f32 = 20.0; // Score for letting an elite fall off the screen
+const DODGE_TEXT_LIFT: f32 = 40.0; // Pixels above the bottom edge the dodge points show at
+pub const ELITE_SCORE_MULTIPLIER: f32 = 4.0; // Applied to points for destroying an elite
+const OUTLINE_SCALE: f32 = 1.3; // Outline size relative to the elite
+const SHIELDED_COLOR: Color = Color::srgb(0.3, 0.9, 1.0);
+const HEAVY_COLOR: Color = Color::srgb(1.0, 0.8, 0.2);
+const PHASING_COLOR: Color = Color::srgb(0.7, 0.4, 1.0);
+const BARE_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+
+/// The ways an enemy can be made elite. Several can be stacked on one enemy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EliteModifier {
+    Shielded,
+    Phasing,
+    Heavy,
+}
+
+impl EliteModifier {
+    pub const ALL: [EliteModifier; 3] = [
+        EliteModifier::Shielded,
+        EliteModifier::Phasing,
+        EliteModifier::Heavy,
+    ];
+
+    /// Outline shape of an elite whose strongest modifier this is.
+    pub fn outline_shape(self) -> Shape {
+        match self {
+            EliteModifier::Shielded => Shape::Circle,
+            EliteModifier::Phasing => Shape::Diamond,
+            EliteModifier::Heavy => Shape::Square,
+        }
+    }
+}
+
+// --- Components ---
+
+/// An enemy wearing one or more elite modifiers, worth more score.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct Elite;
+
+/// Survives bomb hits until `hits_left` runs out.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Shielded {
+    pub hits_left: u8,
+}
+
+/// Blinks in and out of collidability.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Phasing {
+    timer: Timer,
+}
+
+/// Present while a phasing elite can't collide with anything.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct Intangible;
+
+/// Falls faster once past the middle of the screen.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Heavy {
+    boosted: bool,
+}
+
+/// Colored frame behind an elite showing its strongest modifier.
+#[derive(Component)]
+struct EliteOutline;
+
+pub struct ElitePlugin;
+
+impl Plugin for ElitePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                add_elite_outlines,
+                update_elite_outlines,
+                remove_dying_outlines,
+            )
+                .run_if(in_state(GameState::Playing)),
+        )
+        .add_systems(
+            Update,
+            (phase_elites, boost_heavy_elites, score_dodged_elites)
+                .run_if(in_state(RunPhase::Alive)),
+        );
+    }
+}
+
+/// Rolls whether a new enemy is elite, and with which modifiers.
+pub fn roll(rng: &mut impl Rng, chance: f32) -> Vec<EliteModifier> {
+    if chance <= 0.0 || rng.random::<f32>() >= chance {
+        return Vec::new();
+    }
+    let first = EliteModifier::ALL[rng.random_range(0..EliteModifier::ALL.len())];
+    let mut modifiers = vec![first];
+    if rng.random_bool(SECOND_MODIFIER_CHANCE) {
+        let second = EliteModifier::ALL[rng.random_range(0..EliteModifier::ALL.len())];
+        if second != first {
+            modifiers.push(second);
+        }
+    }
+    modifiers
+}
+
+/// Stacks the modifiers' components on a freshly spawned enemy.
+pub fn make_elite(entity: &mut EntityCommands, modifiers: &[EliteModifier]) {
+    if modifiers.is_empty() {
+        return;
+    }
+    entity.insert(Elite);
+    for modifier in modifiers {
+        match modifier {
+            EliteModifier::Shielded => {
+                entity.insert(Shielded {
+                    hits_left: SHIELD_HITS,
+                });
+            }
+            EliteModifier::Phasing => {
+                entity.insert(Phasing {
+                    timer: Timer::from_seconds(PHASE_INTERVAL, TimerMode::Repeating),
+                });
+            }
+            EliteModifier::Heavy => {
+                entity.insert(Heavy { boosted: false });
+            }
+        }
+    }
+}
+
+/// System that frames new elites, including ones restored from a snapshot
+fn add_elite_outlines(mut commands: Commands, query: Query<Entity, Added<Elite>>) {
+    for entity in &query {
+        commands.entity(entity).with_child((
+            Sprite::from_color(BARE_COLOR, Vec2::ONE),
+            Shape::Square,
+            Transform {
+                translation: Vec3::new(0.0, 0.0, -0.1),
+                scale: Vec3::new(OUTLINE_SCALE, OUTLINE_SCALE, 1.0),
+                ..default()
+            },
+            EliteOutline,
+        ));
+    }
+}
+
+/// System that colors and shapes outlines after the elite's strongest
+/// remaining modifier
+fn update_elite_outlines(
+    elite_query: Query<(&Children, Has<Shielded>, Has<Heavy>, Has<Phasing>), With<Elite>>,
+    mut outline_query: Query<(&mut Sprite, &mut Shape), With<EliteOutline>>,
+) {
+    for (children, shielded, heavy, phasing) in &elite_query {
+        let (color, shape) = if shielded {
+            (SHIELDED_COLOR, EliteModifier::Shielded.outline_shape())
+        } else if heavy {
+            (HEAVY_COLOR, EliteModifier::Heavy.outline_shape())
+        } else if phasing {
+            (PHASING_COLOR, EliteModifier::Phasing.outline_shape())
+        } else {
+            (BARE_COLOR, Shape::Square)
+        };
+        for &child in children {
+            if let Ok((mut sprite, mut outline_shape)) = outline_query.get_mut(child) {
+                sprite.color = color;
+                outline_shape.set_if_neq(shape);
+            }
+        }
+    }
+}
+
+/// System that drops the outline as soon as an elite starts dying
+fn remove_dying_outlines(
+    mut commands: Commands,
+    elite_query: Query<&Children, (With<Elite>, Added<Dying>)>,
+    outline_query: Query<(), With<EliteOutline>>,
+) {
+    for children in &elite_query {
+        for &child in children {
+            if outline_query.contains(child) {
+                commands.entity(child).despawn();
+            }
+        }
+    }
+}
+
+/// System that blinks phasing elites in and out of collidability
+fn phase_elites(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Phasing, &mut Sprite, Has<Intangible>), Without<Dying>>,
+) {
+    for (entity, mut phasing, mut sprite, intangible) in &mut query {
+        if phasing.timer.tick(time.delta()).just_finished() {
+            if intangible {
+                commands.entity(entity).remove::<Intangible>();
+            } else {
+                commands.entity(entity).insert(Intangible);
+            }
+        }
+        let intangible_now = intangible != phasing.timer.just_finished();
+        let alpha = if intangible_now { PHASED_ALPHA } else { 1.0 };
+        sprite.color = sprite.color.with_alpha(alpha);
+    }
+}
+
+/// System that speeds heavy elites up once they pass the middle of the screen
+fn boost_heavy_elites(mut query: Query<(&Transform, &mut Heavy, &mut Velocity)>) {
+    for (transform, mut heavy, mut velocity) in &mut query {
+        if !heavy.boosted && transform.translation.y < 0.0 {
+            heavy.boosted = true;
+            velocity.0.y *= HEAVY_BOOST;
+        }
+    }
+}
+
+/// System that rewards letting an elite fall past the bottom of the screen
+fn score_dodged_elites(
+    mut score_events: EventWriter<ScoreEvent>,
+    query: Query<&Transform, (With<Elite>, With<Enemy>, Without<Dying>)>,
+    window: Res<WindowMetrics>,
+) {
+    let bottom = -window.height() / 2.0;
+    // Same test as the off-screen despawn, so each elite scores on its last frame
+    for transform in &query {
+        if transform.translation.y + transform.scale.y / 2.0 < bottom {
+            // Shown just above the edge the elite fell past
+            let position = Vec2::new(transform.translation.x, bottom + DODGE_TEXT_LIFT);
+            score_events.write(ScoreEvent {
+                amount: ELITE_DODGE_POINTS,
+                reason: ScoreReason::EliteDodge,
+                position: Some(position),
+            });
+        }
+    }
+}
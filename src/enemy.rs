@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::data;
+use crate::sets::GameSet;
+use crate::shapes::Shape;
+use crate::shield::KnockedBack;
+use crate::window::WindowMetrics;
+use crate::{Enemy, GameState, Velocity};
+
+const ENEMIES_FILE: &str = "enemies.ron";
+const BUILTIN_ENEMIES: &str = include_str!("../assets/enemies.ron");
+
+// --- Components ---
+
+/// The different kinds of enemy that can fall from the top of the screen.
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[reflect(Component)]
+pub enum EnemyKind {
+    Basic,
+    Fast,
+    Large,
+    /// Falls diagonally, bouncing off the sides of the screen.
+    Bouncer,
+}
+
+impl EnemyKind {
+    pub fn size(self) -> Vec2 {
+        match self {
+            EnemyKind::Basic => Vec2::new(40.0, 40.0),
+            EnemyKind::Fast => Vec2::new(25.0, 25.0),
+            EnemyKind::Large => Vec2::new(80.0, 80.0),
+            EnemyKind::Bouncer => Vec2::new(30.0, 30.0),
+        }
+    }
+
+    /// Multiplier applied to the difficulty's base enemy speed.
+    pub fn speed_multiplier(self) -> f32 {
+        match self {
+            EnemyKind::Basic => 1.0,
+            EnemyKind::Fast => 1.6,
+            EnemyKind::Large => 0.6,
+            EnemyKind::Bouncer => 0.8,
+        }
+    }
+
+    pub fn color(self) -> Color {
+        match self {
+            EnemyKind::Basic => Color::srgb(0.9, 0.2, 0.2),
+            EnemyKind::Fast => Color::srgb(0.95, 0.5, 0.1),
+            EnemyKind::Large => Color::srgb(0.6, 0.1, 0.3),
+            EnemyKind::Bouncer => Color::srgb(0.3, 0.85, 0.4),
+        }
+    }
+
+    pub fn shape(self) -> Shape {
+        match self {
+            EnemyKind::Basic => Shape::Square,
+            EnemyKind::Fast => Shape::Triangle,
+            EnemyKind::Large => Shape::Hexagon,
+            EnemyKind::Bouncer => Shape::Diamond,
+        }
+    }
+}
+
+/// Rotation speed of an enemy in radians per second.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Spin(pub f32);
+
+/// A `(min, max)` range a value is rolled from.
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct Range(pub f32, pub f32);
+
+impl Range {
+    pub fn sample(self, rng: &mut impl Rng) -> f32 {
+        if self.1 > self.0 {
+            rng.random_range(self.0..self.1)
+        } else {
+            self.0
+        }
+    }
+}
+
+/// How much one kind's movement varies between individual enemies.
+#[derive(Debug, Clone, Deserialize)]
+pub struct KindMotion {
+    pub speed: Range,
+    pub drift: Range,
+    pub spin: Range,
+}
+
+impl Default for KindMotion {
+    fn default() -> Self {
+        Self {
+            speed: Range(1.0, 1.0),
+            drift: Range(0.0, 0.0),
+            spin: Range(0.0, 0.0),
+        }
+    }
+}
+
+/// Rolled movement of one spawned enemy.
+pub struct RolledMotion {
+    pub velocity: Vec2,
+    pub spin: f32,
+}
+
+// --- Resources ---
+
+/// Per-kind motion ranges, loaded from `assets/enemies.ron`.
+#[derive(Resource)]
+pub struct EnemyMotion(HashMap<EnemyKind, KindMotion>);
+
+impl Default for EnemyMotion {
+    fn default() -> Self {
+        Self(data::load_ron(ENEMIES_FILE, BUILTIN_ENEMIES))
+    }
+}
+
+impl EnemyMotion {
+    /// Rolls the velocity and spin of a new enemy falling at `fall_speed`.
+    pub fn roll(&self, kind: EnemyKind, fall_speed: f32, rng: &mut impl Rng) -> RolledMotion {
+        let motion = self.0.get(&kind).cloned().unwrap_or_default();
+        // Rolled in a fixed order so seeded runs stay reproducible
+        let speed = fall_speed * kind.speed_multiplier() * motion.speed.sample(rng);
+        let mut drift = motion.drift.sample(rng);
+        let spin = motion.spin.sample(rng);
+        // Bouncers roll how fast they cross and then which way
+        if kind == EnemyKind::Bouncer && rng.random_bool(0.5) {
+            drift = -drift;
+        }
+        RolledMotion {
+            velocity: Vec2::new(drift, -speed),
+            spin,
+        }
+    }
+}
+
+pub struct EnemyPlugin;
+
+impl Plugin for EnemyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EnemyMotion>().add_systems(
+            Update,
+            (spin_enemies, bounce_off_walls.in_set(GameSet::Simulation))
+                .run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+/// System that keeps enemies turning
+fn spin_enemies(time: Res<Time>, mut query: Query<(&mut Transform, &Spin), With<Enemy>>) {
+    for (mut transform, spin) in &mut query {
+        transform.rotate_z(spin.0 * time.delta_secs());
+    }
+}
+
+/// System that turns bouncers around at the sides of the play area, holding
+/// ones wider than it in the middle
+fn bounce_off_walls(
+    mut query: Query<
+        (&mut Transform, &mut Velocity, &EnemyKind),
+        (With<Enemy>, Without<KnockedBack>),
+    >,
+    window: Res<WindowMetrics>,
+) {
+    for (mut transform, mut velocity, kind) in &mut query {
+        if *kind != EnemyKind::Bouncer {
+            continue;
+        }
+        let x_max = (window.width() / 2.0 - transform.scale.x / 2.0).max(0.0);
+        let x = transform.translation.x;
+        if x > x_max {
+            transform.translation.x = x_max;
+            velocity.0.x = -velocity.0.x.abs();
+        } else if x < -x_max {
+            transform.translation.x = -x_max;
+            velocity.0.x = velocity.0.x.abs();
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::heatmap::DeathHeatmap;
+use crate::profile::{
+    self, DEATHS_FILE, HIGH_SCORES_FILE, MAX_NAME_LEN, PROGRESS_FILE, Progress, SETTINGS_FILE,
+    SPLITS_FILE, profile_dir,
+};
+use crate::save::{self, Versioned};
+use crate::score::HighScores;
+use crate::settings::Settings;
+use crate::splits::PersonalBests;
+
+// Export constants
+pub const EXPORT_EXTENSION: &str = "ron";
+const EXPORT_VERSION: u32 = 1; // Bump with every change to `ProfileExport`
+
+/// A whole profile in one file, for moving it between machines by hand.
+#[derive(Serialize, Deserialize)]
+struct ProfileExport {
+    version: u32,
+    name: String,
+    settings: Settings,
+    progress: Progress,
+    high_scores: HighScores,
+    #[serde(default)]
+    deaths: DeathHeatmap,
+    #[serde(default)]
+    personal_bests: PersonalBests,
+}
+
+/// Just enough of an export to tell which format it is in.
+#[derive(Deserialize)]
+struct ExportHeader {
+    version: u32,
+}
+
+/// Writes a profile's saves to a single file.
+pub fn export_profile(name: &str, path: &Path) -> Result<(), String> {
+    let dir = profile_dir(name);
+    let export = ProfileExport {
+        version: EXPORT_VERSION,
+        name: name.to_string(),
+        settings: save::load_versioned(&dir.join(SETTINGS_FILE)),
+        progress: save::load_versioned(&dir.join(PROGRESS_FILE)),
+        high_scores: save::load_versioned(&dir.join(HIGH_SCORES_FILE)),
+        deaths: save::load_or_default(&dir.join(DEATHS_FILE)),
+        personal_bests: save::load_versioned(&dir.join(SPLITS_FILE)),
+    };
+    save::store(path, &export).map_err(|err| format!("Could not export '{name}': {err}"))
+}
+
+/// Reads an exported profile and adds it as a new profile, renaming it if the
+/// name is taken. Returns the name it was imported under.
+pub fn import_profile(path: &Path) -> Result<String, String> {
+    let contents =
+        fs::read_to_string(path).map_err(|err| format!("Could not read the file: {err}"))?;
+    let header: ExportHeader =
+        ron::from_str(&contents).map_err(|_| "This is not a profile export".to_string())?;
+    if header.version > EXPORT_VERSION {
+        return Err("This profile was exported by a newer version of the game".to_string());
+    }
+    let mut export: ProfileExport =
+        ron::from_str(&contents).map_err(|err| format!("The profile export is damaged: {err}"))?;
+    upgrade_all(&mut export).map_err(|err| format!("This profile was {err}"))?;
+
+    let name = export.name.trim();
+    if name.is_empty() || !name.chars().all(profile::is_name_char) {
+        return Err("The exported profile has an invalid name".to_string());
+    }
+    let name = free_name(name);
+    let dir = profile_dir(&name);
+    let written = save::store(&dir.join(SETTINGS_FILE), &export.settings)
+        .and_then(|_| save::store(&dir.join(PROGRESS_FILE), &export.progress))
+        .and_then(|_| save::store(&dir.join(HIGH_SCORES_FILE), &export.high_scores))
+        .and_then(|_| save::store(&dir.join(DEATHS_FILE), &export.deaths))
+        .and_then(|_| save::store(&dir.join(SPLITS_FILE), &export.personal_bests));
+    if let Err(err) = written {
+        // Don't leave half a profile behind
+        let _ = fs::remove_dir_all(&dir);
+        return Err(format!("Could not import '{name}': {err}"));
+    }
+    Ok(name)
+}
+
+/// Brings every save inside an export up to the current format.
+fn upgrade_all(export: &mut ProfileExport) -> Result<(), save::TooNew> {
+    save::upgrade(&mut export.settings)?;
+    save::upgrade(&mut export.progress)?;
+    save::upgrade(&mut export.high_scores)?;
+    save::upgrade(&mut export.personal_bests)?;
+    Ok(())
+}
+
+/// The name itself if no profile uses it yet, otherwise the first free
+/// numbered variant.
+fn free_name(name: &str) -> String {
+    let name: String = name.chars().take(MAX_NAME_LEN).collect();
+    if !profile_dir(&name).exists() {
+        return name;
+    }
+    (2..)
+        .map(|n| {
+            let suffix = format!(" {n}");
+            let stem: String = name
+                .chars()
+                .take(MAX_NAME_LEN.saturating_sub(suffix.len()))
+                .collect();
+            format!("{}{suffix}", stem.trim_end())
+        })
+        .find(|candidate| !profile_dir(candidate).exists())
+        .unwrap_or(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::splits::SplitBests;
+
+    fn export_with_bests() -> ProfileExport {
+        let mut personal_bests = PersonalBests::default();
+        personal_bests.by_difficulty.insert(
+            "Normal".to_string(),
+            SplitBests {
+                personal_best: [(100, 12.5), (250, 31.0)].into(),
+                best_segments: [(100, 12.5), (250, 17.25)].into(),
+                attempts: 7,
+            },
+        );
+        ProfileExport {
+            version: EXPORT_VERSION,
+            name: "Ada".to_string(),
+            settings: Settings::default(),
+            progress: Progress::default(),
+            high_scores: HighScores::default(),
+            deaths: DeathHeatmap::default(),
+            personal_bests,
+        }
+    }
+
+    #[test]
+    fn personal_bests_survive_an_export() {
+        let contents = ron::to_string(&export_with_bests()).unwrap();
+        let mut export: ProfileExport = ron::from_str(&contents).unwrap();
+        assert!(upgrade_all(&mut export).is_ok());
+        let bests = &export.personal_bests.by_difficulty["Normal"];
+        assert_eq!(bests.attempts, 7);
+        assert_eq!(bests.personal_best.get(&250), Some(&31.0));
+        assert_eq!(bests.best_segments.get(&250), Some(&17.25));
+    }
+
+    #[test]
+    fn exports_from_before_splits_have_no_bests() {
+        let mut export = export_with_bests();
+        export.personal_bests = PersonalBests::default();
+        let contents = ron::to_string(&export)
+            .unwrap()
+            .replace(",personal_bests:(version:1,by_difficulty:{})", "");
+        assert!(!contents.contains("personal_bests"));
+        let export: ProfileExport = ron::from_str(&contents).unwrap();
+        assert!(export.personal_bests.by_difficulty.is_empty());
+    }
+}
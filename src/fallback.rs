@@ -0,0 +1,90 @@
+use std::sync::Arc;
+
+use bevy::prelude::*;
+
+use crate::GameState;
+
+// Fallback constants
+const FALLBACK_FONT: &[u8] = include_bytes!("../assets/fonts/FiraSans-Regular.ttf");
+const FALLBACK_SOUND: &[u8] = include_bytes!("../assets/fallback/placeholder.wav");
+const BANNER_BACKGROUND: Color = Color::srgba(0.55, 0.1, 0.1, 0.85);
+
+// --- Components ---
+
+#[derive(Component)]
+struct FallbackBanner;
+
+// --- Resources ---
+
+/// Assets that failed to load and are being stood in for by the embedded
+/// placeholders.
+#[derive(Resource, Default)]
+pub struct MissingAssets(pub Vec<&'static str>);
+
+pub struct FallbackPlugin;
+
+impl Plugin for FallbackPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MissingAssets>()
+            .add_systems(OnExit(GameState::Loading), spawn_fallback_banner);
+    }
+}
+
+/// Puts the embedded font behind a font handle whose file failed to load, so
+/// text using it still renders.
+pub fn replace_font(fonts: &mut Assets<Font>, id: AssetId<Font>) {
+    let font = match Font::try_from_bytes(FALLBACK_FONT.to_vec()) {
+        Ok(font) => font,
+        Err(err) => {
+            error!("The embedded fallback font is unreadable: {err}");
+            return;
+        }
+    };
+    if let Err(err) = fonts.insert(id, font) {
+        warn!("Could not use the fallback font: {err}");
+    }
+}
+
+/// Puts the embedded placeholder sound behind a sound handle whose file failed
+/// to load.
+pub fn replace_sound(sounds: &mut Assets<AudioSource>, id: AssetId<AudioSource>) {
+    let sound = AudioSource {
+        bytes: Arc::from(FALLBACK_SOUND),
+    };
+    if let Err(err) = sounds.insert(id, sound) {
+        warn!("Could not use the fallback sound: {err}");
+    }
+}
+
+/// System that keeps a warning on screen for the rest of the session when
+/// placeholders are in use
+fn spawn_fallback_banner(
+    mut commands: Commands,
+    missing: Res<MissingAssets>,
+    banner_query: Query<(), With<FallbackBanner>>,
+) {
+    if missing.0.is_empty() || !banner_query.is_empty() {
+        return;
+    }
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(0.0),
+                width: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                padding: UiRect::all(Val::Px(4.0)),
+                ..default()
+            },
+            BackgroundColor(BANNER_BACKGROUND),
+            GlobalZIndex(i32::MAX - 1),
+            FallbackBanner,
+        ))
+        .with_child((
+            Text::new(format!(
+                "Missing game files, using placeholders: {}",
+                missing.0.join(", ")
+            )),
+            TextFont::from_font_size(14.0),
+        ));
+}
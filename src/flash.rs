@@ -0,0 +1,122 @@
+use bevy::prelude::*;
+
+use crate::settings::Settings;
+
+// Flash constants
+const SAFE_MAX_ALPHA: f32 = 0.25; // Brightest any flash gets in epilepsy-safe mode
+const SAFE_MIN_DURATION: f32 = 0.6; // Safe screen flashes ease in and out over at least this long
+const SAFE_MAX_BLINK_RATE: f32 = 1.5; // Fastest blink allowed in epilepsy-safe mode, per second
+const SAFE_MIN_BLINK_ALPHA: f32 = 0.6; // Safe blinks never dip below this opacity
+
+// --- Events ---
+
+/// Sent to flash the whole screen. Full-screen flashes go through this so the
+/// epilepsy-safe setting can tone every one of them down.
+#[derive(Event)]
+pub struct ScreenFlash {
+    pub color: Color,
+    pub alpha: f32,
+    /// Real (unscaled) seconds the flash takes to fade out.
+    pub duration: f32,
+}
+
+// --- Components ---
+
+#[derive(Component)]
+struct FlashOverlay {
+    color: Color,
+    peak: f32,
+    // Eases in and out instead of starting at full strength
+    gentle: bool,
+    timer: Timer,
+}
+
+pub struct FlashPlugin;
+
+impl Plugin for FlashPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ScreenFlash>()
+            .add_systems(Update, (spawn_screen_flashes, fade_screen_flashes).chain());
+    }
+}
+
+/// Caps the opacity of a flashing effect in epilepsy-safe mode.
+pub fn limit_alpha(settings: &Settings, alpha: f32) -> f32 {
+    if settings.epilepsy_safe {
+        alpha.min(SAFE_MAX_ALPHA)
+    } else {
+        alpha
+    }
+}
+
+/// Opacity of a blinking effect `elapsed` seconds in. Epilepsy-safe mode slows
+/// the blink down and keeps it from dipping far.
+pub fn blink(settings: &Settings, elapsed: f32, rate: f32, min_alpha: f32) -> f32 {
+    let (rate, min_alpha) = if settings.epilepsy_safe {
+        (
+            rate.min(SAFE_MAX_BLINK_RATE),
+            min_alpha.max(SAFE_MIN_BLINK_ALPHA),
+        )
+    } else {
+        (rate, min_alpha)
+    };
+    let wave = (elapsed * rate * std::f32::consts::TAU).sin() * 0.5 + 0.5;
+    min_alpha + (1.0 - min_alpha) * wave
+}
+
+/// System that puts up an overlay for every requested flash
+fn spawn_screen_flashes(
+    mut commands: Commands,
+    mut events: EventReader<ScreenFlash>,
+    settings: Res<Settings>,
+) {
+    for flash in events.read() {
+        let gentle = settings.epilepsy_safe;
+        let duration = if gentle {
+            flash.duration.max(SAFE_MIN_DURATION)
+        } else {
+            flash.duration
+        };
+        let peak = limit_alpha(&settings, flash.alpha);
+        let start_alpha = if gentle { 0.0 } else { peak };
+
+        commands.spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            BackgroundColor(flash.color.with_alpha(start_alpha)),
+            FlashOverlay {
+                color: flash.color,
+                peak,
+                gentle,
+                timer: Timer::from_seconds(duration, TimerMode::Once),
+            },
+        ));
+    }
+}
+
+/// System that fades flashes out and removes them
+fn fade_screen_flashes(
+    mut commands: Commands,
+    real_time: Res<Time<Real>>,
+    mut query: Query<(Entity, &mut FlashOverlay, &mut BackgroundColor)>,
+) {
+    for (entity, mut flash, mut background) in &mut query {
+        // Real time, so slow motion doesn't stretch the flash
+        flash.timer.tick(real_time.delta());
+        if flash.timer.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let t = flash.timer.fraction();
+        let strength = if flash.gentle {
+            (t * std::f32::consts::PI).sin()
+        } else {
+            1.0 - t
+        };
+        background.0 = flash.color.with_alpha(flash.peak * strength);
+    }
+}
@@ -0,0 +1,102 @@
+use bevy::prelude::*;
+
+use crate::GameState;
+use crate::despawn::DespawnQueue;
+use crate::reset::RunScoped;
+use crate::score::{ScoreEvent, ScoreReason};
+use crate::sets::GameSet;
+use crate::settings::Settings;
+use crate::text_style::TextStyleLibrary;
+use crate::watchdog::Transient;
+
+// Floating text constants
+const FLOAT_DURATION: f32 = 0.8; // Seconds a score popup stays up
+const FLOAT_RISE: f32 = 60.0; // Pixels a popup rises over its lifetime
+const FLOAT_FONT_SIZE: f32 = 22.0;
+const DODGE_COLOR: Color = Color::srgb(0.6, 0.9, 1.0);
+const KILL_COLOR: Color = Color::srgb(1.0, 0.85, 0.3);
+const OBJECTIVE_COLOR: Color = Color::srgb(0.5, 1.0, 0.5);
+const JACKPOT_COLOR: Color = Color::srgb(1.0, 0.82, 0.2);
+
+// --- Components ---
+
+/// Points earned at a spot, rising and fading out above it.
+#[derive(Component)]
+#[require(RunScoped, Transient)]
+struct FloatingText {
+    timer: Timer,
+    start: Vec2,
+}
+
+pub struct FloatingTextPlugin;
+
+impl Plugin for FloatingTextPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (spawn_floating_text, animate_floating_text)
+                .chain()
+                .in_set(GameSet::UiSync)
+                .run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+/// System to pop the points of every placed score event up where they were earned
+fn spawn_floating_text(
+    mut commands: Commands,
+    mut score_events: EventReader<ScoreEvent>,
+    styles: Res<TextStyleLibrary>,
+) {
+    for event in score_events.read() {
+        let Some(position) = event.position else {
+            continue;
+        };
+        let color = match event.reason {
+            ScoreReason::EliteDodge => DODGE_COLOR,
+            ScoreReason::BombKill => KILL_COLOR,
+            ScoreReason::Objective => OBJECTIVE_COLOR,
+            ScoreReason::Jackpot => JACKPOT_COLOR,
+            // Survival points trickle in every frame and aren't placed
+            ScoreReason::Survival => continue,
+        };
+        commands.spawn((
+            Text2d::new(format!("+{}", event.amount.round() as u32)),
+            TextFont {
+                font: styles.hud.font.clone(),
+                font_size: FLOAT_FONT_SIZE,
+                ..default()
+            },
+            TextColor(color),
+            Transform::from_translation(position.extend(5.0)),
+            FloatingText {
+                timer: Timer::from_seconds(FLOAT_DURATION, TimerMode::Once),
+                start: position,
+            },
+        ));
+    }
+}
+
+/// System that lifts and fades the popups, removing them once they're gone
+fn animate_floating_text(
+    time: Res<Time>,
+    settings: Res<Settings>,
+    mut despawn_queue: ResMut<DespawnQueue>,
+    mut query: Query<(Entity, &mut FloatingText, &mut Transform, &mut TextColor)>,
+) {
+    for (entity, mut text, mut transform, mut color) in &mut query {
+        text.timer.tick(time.delta());
+        let progress = text.timer.fraction();
+        // With reduced motion the popup fades in place
+        let rise = if settings.reduce_motion {
+            0.0
+        } else {
+            FLOAT_RISE * progress
+        };
+        transform.translation.y = text.start.y + rise;
+        color.0.set_alpha(1.0 - progress);
+        if text.timer.finished() {
+            despawn_queue.push(entity);
+        }
+    }
+}
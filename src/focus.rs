@@ -0,0 +1,163 @@
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+
+use crate::input_buffer::{BufferedAction, InputBuffer};
+
+// Focus constants
+const FOCUS_BACKGROUND: Color = Color::srgba(1.0, 1.0, 1.0, 0.15);
+const FOCUS_BORDER: Color = Color::srgb(1.0, 0.85, 0.3);
+
+// --- Components ---
+
+/// A menu entry that can take focus. Entries are visited in `order`.
+#[derive(Component)]
+pub struct Focusable {
+    pub order: usize,
+}
+
+/// Marks the focused entry.
+#[derive(Component)]
+pub struct Focused;
+
+// --- Resources ---
+
+/// Menu navigation pressed this frame, from the keyboard or any gamepad.
+#[derive(Resource, Default)]
+pub struct MenuNav {
+    pub up: bool,
+    pub down: bool,
+    pub left: bool,
+    pub right: bool,
+    pub confirm: bool,
+    pub back: bool,
+}
+
+/// Present while a text field has the keyboard, so typing doesn't also
+/// navigate the menu.
+#[derive(Resource)]
+pub struct TextEntryActive;
+
+// --- Events ---
+
+/// Sent when the focused entry is confirmed.
+#[derive(Event)]
+pub struct FocusActivated(pub Entity);
+
+pub struct FocusPlugin;
+
+impl Plugin for FocusPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MenuNav>()
+            .add_event::<FocusActivated>()
+            .add_systems(
+                PreUpdate,
+                (read_menu_nav, move_focus, activate_focused)
+                    .chain()
+                    .after(InputSystem),
+            )
+            .add_systems(Update, highlight_focus);
+    }
+}
+
+/// A text menu entry that can take focus.
+pub fn entry(label: impl Into<String>, order: usize) -> impl Bundle {
+    (
+        Text::new(label),
+        Node {
+            padding: UiRect::axes(Val::Px(8.0), Val::Px(2.0)),
+            border: UiRect::all(Val::Px(1.0)),
+            ..default()
+        },
+        BackgroundColor(Color::NONE),
+        BorderColor(Color::NONE),
+        Focusable { order },
+    )
+}
+
+/// System that gathers this frame's navigation presses
+fn read_menu_nav(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    text_entry: Option<Res<TextEntryActive>>,
+    focusable_query: Query<(), With<Focusable>>,
+    mut input_buffer: ResMut<InputBuffer>,
+    mut nav: ResMut<MenuNav>,
+) {
+    *nav = MenuNav::default();
+    // Leave Enter to screens without entries, like the profile list
+    if text_entry.is_some() || focusable_query.is_empty() {
+        return;
+    }
+
+    let pad = |button| gamepads.iter().any(|gamepad| gamepad.just_pressed(button));
+    nav.up = keyboard_input.just_pressed(KeyCode::ArrowUp) || pad(GamepadButton::DPadUp);
+    nav.down = keyboard_input.just_pressed(KeyCode::ArrowDown) || pad(GamepadButton::DPadDown);
+    nav.left = keyboard_input.just_pressed(KeyCode::ArrowLeft) || pad(GamepadButton::DPadLeft);
+    nav.right = keyboard_input.just_pressed(KeyCode::ArrowRight) || pad(GamepadButton::DPadRight);
+    nav.confirm = input_buffer.consume(BufferedAction::Confirm)
+        || keyboard_input.just_pressed(KeyCode::Space)
+        || pad(GamepadButton::South);
+    nav.back = keyboard_input.just_pressed(KeyCode::Escape) || pad(GamepadButton::East);
+}
+
+/// System that keeps one entry focused and moves focus up and down the list
+fn move_focus(
+    mut commands: Commands,
+    nav: Res<MenuNav>,
+    focusable_query: Query<(Entity, &Focusable, Has<Focused>)>,
+) {
+    let mut entries: Vec<(usize, Entity, bool)> = focusable_query
+        .iter()
+        .map(|(entity, focusable, focused)| (focusable.order, entity, focused))
+        .collect();
+    if entries.is_empty() {
+        return;
+    }
+    entries.sort();
+
+    let current = entries.iter().position(|&(_, _, focused)| focused);
+    let count = entries.len();
+    let next = match current {
+        None => 0,
+        Some(index) if nav.up => (index + count - 1) % count,
+        Some(index) if nav.down => (index + 1) % count,
+        Some(_) => return,
+    };
+    if let Some(index) = current {
+        commands.entity(entries[index].1).remove::<Focused>();
+    }
+    commands.entity(entries[next].1).insert(Focused);
+}
+
+/// System that reports the focused entry being confirmed
+fn activate_focused(
+    nav: Res<MenuNav>,
+    focused_query: Query<Entity, (With<Focusable>, With<Focused>)>,
+    mut activated: EventWriter<FocusActivated>,
+) {
+    if !nav.confirm {
+        return;
+    }
+    for entity in &focused_query {
+        activated.write(FocusActivated(entity));
+    }
+}
+
+/// System that outlines the focused entry
+fn highlight_focus(
+    mut query: Query<(&mut BackgroundColor, &mut BorderColor, Has<Focused>), With<Focusable>>,
+) {
+    for (mut background, mut border, focused) in &mut query {
+        let (fill, outline) = if focused {
+            (FOCUS_BACKGROUND, FOCUS_BORDER)
+        } else {
+            (Color::NONE, Color::NONE)
+        };
+        if background.0 != fill {
+            background.0 = fill;
+        }
+        if border.0 != outline {
+            border.0 = outline;
+        }
+    }
+}
@@ -0,0 +1,233 @@
+use bevy::prelude::*;
+use rand::Rng;
+use rusty_dodger::math;
+
+use crate::collision::Collider;
+use crate::dying::Dying;
+use crate::graze::NearMiss;
+use crate::mixer::{Bus, PlaySound, SoundCue};
+use crate::reset::RunSetup;
+use crate::rng::GameRng;
+use crate::score::{ScoreEvent, ScoreReason};
+use crate::sets::GameSet;
+use crate::shapes::Shape;
+use crate::shield::KnockedBack;
+use crate::stats::RunStats;
+use crate::window::WindowMetrics;
+use crate::{Enemy, Player, RunPhase, Velocity};
+
+// Golden enemy constants
+const FIRST_ROLL_SECONDS: f32 = 20.0; // Seconds into a run before one can show up
+const ROLL_INTERVAL: f32 = 10.0; // Seconds between two chances of a golden enemy
+const SPAWN_CHANCE: f64 = 0.15;
+const GOLDEN_SIZE: f32 = 26.0;
+const GOLDEN_LIFETIME: f32 = 6.0; // Seconds it stays before slipping away
+const GOLDEN_FALL_SPEED: f32 = 40.0;
+const FLEE_SPEED: f32 = 320.0; // Sideways speed away from the player
+const FLEE_ACCELERATION: f32 = 900.0;
+const VANISH_DURATION: f32 = 0.4;
+const GRAZE_JACKPOT: f32 = 500.0;
+const SHOT_JACKPOT: f32 = 1000.0;
+const GOLDEN_COLOR: Color = Color::srgb(1.0, 0.82, 0.2);
+pub const GOLDEN_SHAPE: Shape = Shape::Star;
+pub const SPAWN_SOUND: &str = "sounds/golden_spawn.wav";
+
+// --- Components ---
+
+/// A rare enemy worth a jackpot if it's bombed or grazed before its time is
+/// up. It runs away from the player sideways.
+#[derive(Component)]
+struct Golden {
+    lifetime: Timer,
+}
+
+// --- Resources ---
+
+/// Counts down to the next roll for a golden enemy.
+#[derive(Resource)]
+struct GoldenTimer(Timer);
+
+impl Default for GoldenTimer {
+    fn default() -> Self {
+        Self(Timer::from_seconds(ROLL_INTERVAL, TimerMode::Repeating))
+    }
+}
+
+#[derive(Resource)]
+struct GoldenAssets {
+    spawn_sound: Handle<AudioSource>,
+}
+
+impl FromWorld for GoldenAssets {
+    fn from_world(world: &mut World) -> Self {
+        Self {
+            spawn_sound: world.resource::<AssetServer>().load(SPAWN_SOUND),
+        }
+    }
+}
+
+pub struct GoldenPlugin;
+
+impl Plugin for GoldenPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GoldenTimer>()
+            .init_resource::<GoldenAssets>()
+            .add_systems(RunSetup, reset_golden_timer)
+            .add_systems(
+                Update,
+                (
+                    (spawn_golden, flee_player).in_set(GameSet::Simulation),
+                    (pay_grazes, pay_shots).in_set(GameSet::Cleanup),
+                    expire_golden.in_set(GameSet::Cleanup),
+                )
+                    .run_if(in_state(RunPhase::Alive)),
+            );
+    }
+}
+
+/// System to start each run's rolls over
+fn reset_golden_timer(mut timer: ResMut<GoldenTimer>) {
+    *timer = GoldenTimer::default();
+}
+
+/// System that now and then sends in a golden enemy, one at a time, with a
+/// chime so the player knows to go after it
+fn spawn_golden(
+    mut commands: Commands,
+    time: Res<Time>,
+    stats: Res<RunStats>,
+    golden_assets: Res<GoldenAssets>,
+    mut sounds: EventWriter<PlaySound>,
+    mut timer: ResMut<GoldenTimer>,
+    mut rng: ResMut<GameRng>,
+    golden_query: Query<(), With<Golden>>,
+    window: Res<WindowMetrics>,
+) {
+    if stats.elapsed() < FIRST_ROLL_SECONDS || !golden_query.is_empty() {
+        return;
+    }
+    if !timer.0.tick(time.delta()).just_finished() || !rng.0.random_bool(SPAWN_CHANCE) {
+        return;
+    }
+    let (x_min, x_max) = math::spawn_x_range(window.width(), GOLDEN_SIZE);
+    let x = rng.0.random_range(x_min..=x_max);
+    let y = window.height() / 2.0 - GOLDEN_SIZE;
+    let size = Vec2::splat(GOLDEN_SIZE);
+
+    commands.spawn((
+        Sprite::from_color(GOLDEN_COLOR, Vec2::ONE),
+        GOLDEN_SHAPE,
+        Transform::from_xyz(x, y, 0.0).with_scale(size.extend(1.0)),
+        Enemy,
+        Collider::new(GOLDEN_SHAPE.hitbox(size)),
+        Velocity(Vec2::new(0.0, -GOLDEN_FALL_SPEED)),
+        Golden {
+            lifetime: Timer::from_seconds(GOLDEN_LIFETIME, TimerMode::Once),
+        },
+    ));
+    sounds.write(PlaySound {
+        sound: golden_assets.spawn_sound.clone(),
+        bus: Bus::Sfx,
+        at: Some(Vec2::new(x, y)),
+        cue: SoundCue::Arrival,
+    });
+}
+
+/// System that steers golden enemies sideways away from the player, stopping
+/// at the edges of the screen so they can be cornered
+fn flee_player(
+    time: Res<Time>,
+    player_query: Query<&Transform, With<Player>>,
+    mut golden_query: Query<
+        (&Transform, &mut Velocity),
+        (With<Golden>, Without<Dying>, Without<KnockedBack>),
+    >,
+    window: Res<WindowMetrics>,
+) {
+    let Ok(player_transform) = player_query.single() else {
+        return;
+    };
+    let half_width = ((window.width() - GOLDEN_SIZE) / 2.0).max(0.0);
+    for (transform, mut velocity) in &mut golden_query {
+        let x = transform.translation.x;
+        let away = (x - player_transform.translation.x).signum();
+        let target = if (away > 0.0 && x >= half_width) || (away < 0.0 && x <= -half_width) {
+            0.0
+        } else {
+            away * FLEE_SPEED
+        };
+        let step = FLEE_ACCELERATION * time.delta_secs();
+        velocity.0.x += (target - velocity.0.x).clamp(-step, step);
+    }
+}
+
+/// Pays out a golden enemy and lets it vanish.
+fn pay_jackpot(
+    commands: &mut Commands,
+    score_events: &mut EventWriter<ScoreEvent>,
+    entity: Entity,
+    amount: f32,
+    at: Vec2,
+) {
+    commands.entity(entity).remove::<Golden>();
+    score_events.write(ScoreEvent {
+        amount,
+        reason: ScoreReason::Jackpot,
+        position: Some(at),
+    });
+    info!(amount, "Jackpot");
+}
+
+/// System that pays the jackpot for grazing a golden enemy
+fn pay_grazes(
+    mut commands: Commands,
+    mut near_misses: EventReader<NearMiss>,
+    mut score_events: EventWriter<ScoreEvent>,
+    golden_query: Query<&Transform, (With<Golden>, Without<Dying>)>,
+) {
+    for near_miss in near_misses.read() {
+        let Ok(transform) = golden_query.get(near_miss.enemy) else {
+            continue;
+        };
+        let at = transform.translation.truncate();
+        pay_jackpot(
+            &mut commands,
+            &mut score_events,
+            near_miss.enemy,
+            GRAZE_JACKPOT,
+            at,
+        );
+        commands
+            .entity(near_miss.enemy)
+            .insert(Dying::new(VANISH_DURATION));
+    }
+}
+
+/// System that pays the bigger jackpot for bombing a golden enemy. Bouncing
+/// one off the shield doesn't count
+fn pay_shots(
+    mut commands: Commands,
+    mut score_events: EventWriter<ScoreEvent>,
+    golden_query: Query<(Entity, &Transform), (With<Golden>, Added<Dying>, Without<KnockedBack>)>,
+) {
+    for (entity, transform) in &golden_query {
+        let at = transform.translation.truncate();
+        pay_jackpot(&mut commands, &mut score_events, entity, SHOT_JACKPOT, at);
+    }
+}
+
+/// System that makes golden enemies slip away once their time is up
+fn expire_golden(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut golden_query: Query<(Entity, &mut Golden), Without<Dying>>,
+) {
+    for (entity, mut golden) in &mut golden_query {
+        if golden.lifetime.tick(time.delta()).just_finished() {
+            commands
+                .entity(entity)
+                .remove::<Golden>()
+                .insert(Dying::new(VANISH_DURATION));
+        }
+    }
+}
@@ -0,0 +1,271 @@
+use bevy::prelude::*;
+use rand::Rng;
+use rusty_dodger::math;
+
+use crate::arena::Arena;
+use crate::collision::Collider;
+use crate::despawn::DespawnQueue;
+use crate::difficulty::Difficulty;
+use crate::dying::Dying;
+use crate::enemy::EnemyKind;
+use crate::graze::NearMiss;
+use crate::lanes;
+use crate::reset::{RunScoped, RunSetup};
+use crate::rng::GameRng;
+use crate::sets::GameSet;
+use crate::shapes::Shape;
+use crate::shield::KnockedBack;
+use crate::window::WindowMetrics;
+use crate::{Enemy, Player, RunPhase, Velocity};
+
+// Gravity well constants
+const FIRST_WELL: f32 = 60.0; // Seconds into a run before the first well
+const WELL_GAP_MIN: f32 = 50.0; // Seconds between two wells, rolled in this range
+const WELL_GAP_MAX: f32 = 80.0;
+const WELL_DURATION: f32 = 12.0; // Seconds a well lasts if it isn't broken first
+const WELL_SIZE: f32 = 60.0;
+const WELL_HEIGHT: f32 = 0.15; // In arena runs, fraction of the screen height above the middle
+const GRAZE_GAP: f32 = 10.0; // Outside arena runs, gap between the lowest orbiter and the player
+const MAX_PULL: f32 = 0.7; // Strongest pull relative to the player's speed, so it can always be outrun
+const PULL_FALLOFF: f32 = 250.0; // Distance in pixels at which the pull has halved
+const WELL_CHARGES: u32 = 3; // Grazes on orbiting enemies it takes to break the well
+const ORBITERS: usize = 6;
+const ORBIT_RADIUS: f32 = 110.0;
+const ORBIT_SPEED: f32 = 1.6; // Radians per second
+const ORBITER_SIZE: f32 = 22.0;
+const ORBITER_COLOR: Color = Color::srgb(0.6, 0.35, 0.95);
+pub const ORBITER_SHAPE: Shape = Shape::Ring;
+const WELL_COLOR: Color = Color::srgba(0.35, 0.1, 0.6, 0.8);
+const COLLAPSE_DURATION: f32 = 0.5;
+const FLING_FALL_SPEED: f32 = 150.0; // Added to flung orbiters so they leave by the bottom
+
+// --- Components ---
+
+/// A point pulling the player in, until it times out or enough of its
+/// orbiting enemies are grazed to break it.
+#[derive(Component)]
+#[require(RunScoped)]
+struct GravityWell {
+    lifetime: Timer,
+    charges: u32,
+}
+
+/// An enemy circling a gravity well.
+#[derive(Component)]
+struct Orbiter {
+    well: Entity,
+    angle: f32,
+}
+
+// --- Resources ---
+
+/// Counts down to the next well.
+#[derive(Resource)]
+struct WellTimer(Timer);
+
+impl Default for WellTimer {
+    fn default() -> Self {
+        Self(Timer::from_seconds(FIRST_WELL, TimerMode::Once))
+    }
+}
+
+#[derive(Resource)]
+struct WellAssets {
+    circle: Handle<Mesh>,
+    material: Handle<ColorMaterial>,
+}
+
+impl FromWorld for WellAssets {
+    fn from_world(world: &mut World) -> Self {
+        let circle = world.resource_mut::<Assets<Mesh>>().add(Circle::new(0.5));
+        let material = world
+            .resource_mut::<Assets<ColorMaterial>>()
+            .add(ColorMaterial::from_color(WELL_COLOR));
+        Self { circle, material }
+    }
+}
+
+pub struct GravityWellPlugin;
+
+impl Plugin for GravityWellPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WellTimer>()
+            .init_resource::<WellAssets>()
+            .add_systems(RunSetup, reset_well_timer)
+            .add_systems(
+                Update,
+                (
+                    spawn_well.in_set(GameSet::Simulation),
+                    // Once the controls have set the player's velocity, before
+                    // anything moves
+                    pull_player
+                        .after(GameSet::Input)
+                        .before(GameSet::Simulation),
+                    orbit_wells.in_set(GameSet::Simulation),
+                    weaken_wells.in_set(GameSet::Collision),
+                    collapse_wells.in_set(GameSet::Cleanup),
+                )
+                    .run_if(in_state(RunPhase::Alive).and(not(lanes::lanes_enabled))),
+            );
+    }
+}
+
+/// System to hold back the first well of a run
+fn reset_well_timer(mut timer: ResMut<WellTimer>) {
+    *timer = WellTimer::default();
+}
+
+/// System that opens a well once the countdown is over, ringed by enemies
+fn spawn_well(
+    mut commands: Commands,
+    time: Res<Time>,
+    well_assets: Res<WellAssets>,
+    mut timer: ResMut<WellTimer>,
+    mut rng: ResMut<GameRng>,
+    arena: Res<Arena>,
+    well_query: Query<(), With<GravityWell>>,
+    player_query: Query<(&Transform, &Collider), With<Player>>,
+    window: Res<WindowMetrics>,
+) {
+    if !well_query.is_empty() || !timer.0.tick(time.delta()).finished() {
+        return;
+    }
+    let Ok((player_transform, player_collider)) = player_query.single() else {
+        return;
+    };
+    let gap = rng.0.random_range(WELL_GAP_MIN..WELL_GAP_MAX);
+    timer.0 = Timer::from_seconds(gap, TimerMode::Once);
+
+    // The whole ring of orbiters has to fit
+    let (x_min, x_max) = math::spawn_x_range(window.width(), 2.0 * (ORBIT_RADIUS + ORBITER_SIZE));
+    // The player can't leave their row outside arena runs, so the ring is
+    // lowered until its bottom passes just above them, close enough to graze
+    let y = if arena.enabled {
+        window.height() * WELL_HEIGHT
+    } else {
+        player_transform.translation.y
+            + player_collider.effective_size().y / 2.0
+            + GRAZE_GAP
+            + ORBITER_SIZE / 2.0
+            + ORBIT_RADIUS
+    };
+    let center = Vec2::new(rng.0.random_range(x_min..=x_max), y);
+    let well = commands
+        .spawn((
+            Mesh2d(well_assets.circle.clone()),
+            MeshMaterial2d(well_assets.material.clone()),
+            Transform::from_translation(center.extend(-0.2)).with_scale(Vec3::splat(WELL_SIZE)),
+            GravityWell {
+                lifetime: Timer::from_seconds(WELL_DURATION, TimerMode::Once),
+                charges: WELL_CHARGES,
+            },
+        ))
+        .id();
+    info!(x = center.x, y = center.y, "Gravity well");
+
+    let size = Vec2::splat(ORBITER_SIZE);
+    for index in 0..ORBITERS {
+        let angle = std::f32::consts::TAU * index as f32 / ORBITERS as f32;
+        let position = center + Vec2::from_angle(angle) * ORBIT_RADIUS;
+        commands.spawn((
+            Sprite::from_color(ORBITER_COLOR, Vec2::ONE),
+            ORBITER_SHAPE,
+            Transform::from_translation(position.extend(0.0)).with_scale(size.extend(1.0)),
+            Enemy,
+            EnemyKind::Basic,
+            Collider::new(ORBITER_SHAPE.hitbox(size)),
+            Orbiter { well, angle },
+        ));
+    }
+}
+
+/// System that drags the player towards the well, harder the closer they
+/// are but never faster than they can fly away. Outside arena runs the
+/// player can only move sideways, so only the sideways pull applies
+fn pull_player(
+    difficulty: Res<Difficulty>,
+    arena: Res<Arena>,
+    well_query: Query<&Transform, With<GravityWell>>,
+    mut player_query: Query<(&Transform, &mut Velocity), With<Player>>,
+) {
+    let Ok((player_transform, mut velocity)) = player_query.single_mut() else {
+        return;
+    };
+    for well_transform in &well_query {
+        let offset = (well_transform.translation - player_transform.translation).truncate();
+        let strength =
+            MAX_PULL * difficulty.config.player_speed / (1.0 + offset.length() / PULL_FALLOFF);
+        let mut pull = offset.normalize_or_zero() * strength;
+        if !arena.enabled {
+            pull.y = 0.0;
+        }
+        velocity.0 += pull;
+    }
+}
+
+/// System that swings orbiting enemies around their well
+fn orbit_wells(
+    time: Res<Time>,
+    well_query: Query<&Transform, (With<GravityWell>, Without<Orbiter>)>,
+    mut orbiter_query: Query<
+        (&mut Orbiter, &mut Transform),
+        (Without<Dying>, Without<KnockedBack>),
+    >,
+) {
+    for (mut orbiter, mut transform) in &mut orbiter_query {
+        let Ok(well_transform) = well_query.get(orbiter.well) else {
+            continue;
+        };
+        orbiter.angle += ORBIT_SPEED * time.delta_secs();
+        let position =
+            well_transform.translation.truncate() + Vec2::from_angle(orbiter.angle) * ORBIT_RADIUS;
+        transform.translation = position.extend(transform.translation.z);
+    }
+}
+
+/// System that uses up a charge of the well for every orbiter grazed
+fn weaken_wells(
+    mut near_misses: EventReader<NearMiss>,
+    orbiter_query: Query<&Orbiter>,
+    mut well_query: Query<&mut GravityWell>,
+) {
+    for near_miss in near_misses.read() {
+        let Ok(orbiter) = orbiter_query.get(near_miss.enemy) else {
+            continue;
+        };
+        if let Ok(mut well) = well_query.get_mut(orbiter.well) {
+            well.charges = well.charges.saturating_sub(1);
+        }
+    }
+}
+
+/// System that closes wells that ran out of time or charges, flinging their
+/// orbiters off along their orbit
+fn collapse_wells(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut despawn_queue: ResMut<DespawnQueue>,
+    mut well_query: Query<(Entity, &mut GravityWell)>,
+    orbiter_query: Query<(Entity, &Orbiter)>,
+) {
+    for (entity, mut well) in &mut well_query {
+        let expired = well.lifetime.tick(time.delta()).finished();
+        if !expired && well.charges > 0 {
+            continue;
+        }
+        info!(broken = well.charges == 0, "Gravity well collapsed");
+        despawn_queue.push(entity);
+        for (orbiter_entity, orbiter) in &orbiter_query {
+            if orbiter.well != entity {
+                continue;
+            }
+            let tangent = Vec2::from_angle(orbiter.angle).perp() * ORBIT_SPEED * ORBIT_RADIUS;
+            let fling = tangent - Vec2::Y * FLING_FALL_SPEED;
+            let mut orbiter_commands = commands.entity(orbiter_entity);
+            orbiter_commands.remove::<Orbiter>().insert(Velocity(fling));
+            if well.charges == 0 {
+                orbiter_commands.insert(Dying::new(COLLAPSE_DURATION));
+            }
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use bevy::prelude::*;
+
+use crate::bomb::Bombs;
+use crate::collision::Collider;
+use crate::dying::Dying;
+use crate::elite::Intangible;
+use crate::hud_layout::{HudAnchor, HudSlot};
+use crate::profiler::timed;
+use crate::reset::{RunScoped, RunSetup};
+use crate::segmented_bar::SegmentedBar;
+use crate::sets::GameSet;
+use crate::shield::{KnockedBack, TemporaryShield};
+use crate::{Enemy, GameState, Player, RunPhase, collide};
+
+// Graze constants
+const GRAZE_MARGIN: f32 = 20.0; // How close an enemy has to pass to count as a near miss
+const GRAZE_FILL: f32 = 0.15; // Meter gained per near miss
+const GRAZE_DECAY: f32 = 0.1; // Meter lost per second once the player stops grazing
+const GRAZE_DECAY_DELAY: f32 = 1.5; // Seconds after a graze before the meter starts decaying
+const GRAZE_SHIELD_DURATION: f32 = 3.0;
+const METER_SIZE: Vec2 = Vec2::new(150.0, 12.0);
+const METER_SEGMENTS: usize = 5;
+const METER_COLOR: Color = Color::srgb(0.4, 0.9, 1.0);
+const METER_NEARLY_FULL_COLOR: Color = Color::srgb(1.0, 0.85, 0.3);
+
+// --- Resources ---
+
+/// Near-miss meter; filling it grants a bomb, or a short shield when bombs are maxed.
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource)]
+pub struct GrazeMeter {
+    pub value: f32,
+    since_last_graze: f32,
+}
+
+// --- Components ---
+
+/// Marks enemies that already counted as a near miss.
+#[derive(Component)]
+struct Grazed;
+
+#[derive(Component)]
+#[require(RunScoped)]
+struct GrazeBar;
+
+// --- Events ---
+
+/// Sent when an enemy passes just outside the player's hitbox.
+#[derive(Event)]
+pub struct NearMiss {
+    pub enemy: Entity,
+}
+
+pub struct GrazePlugin;
+
+impl Plugin for GrazePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GrazeMeter>()
+            .add_event::<NearMiss>()
+            .add_systems(RunSetup, (reset_graze_meter, spawn_graze_bar))
+            .add_systems(
+                Update,
+                (
+                    timed("detect_grazes", detect_grazes),
+                    decay_graze_meter,
+                    fill_graze_meter,
+                )
+                    .chain()
+                    .in_set(GameSet::Collision)
+                    .run_if(in_state(RunPhase::Alive)),
+            )
+            .add_systems(
+                Update,
+                update_graze_bar
+                    .in_set(GameSet::UiSync)
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+/// System to empty the meter at the start of a run
+fn reset_graze_meter(mut meter: ResMut<GrazeMeter>) {
+    *meter = GrazeMeter::default();
+}
+
+/// System that counts enemies passing just outside the player's hitbox
+fn detect_grazes(
+    mut commands: Commands,
+    mut meter: ResMut<GrazeMeter>,
+    mut near_misses: EventWriter<NearMiss>,
+    player_query: Query<(&Transform, &Collider), With<Player>>,
+    enemy_query: Query<
+        (Entity, &Transform, &Collider),
+        (
+            With<Enemy>,
+            Without<Grazed>,
+            Without<Dying>,
+            Without<Intangible>,
+            Without<KnockedBack>,
+        ),
+    >,
+) {
+    let Ok((player_transform, player_collider)) = player_query.single() else {
+        return;
+    };
+    let player_size = player_collider.effective_size();
+    let graze_size = player_size + Vec2::splat(GRAZE_MARGIN * 2.0);
+
+    for (entity, enemy_transform, enemy_collider) in &enemy_query {
+        let enemy_size = enemy_collider.effective_size();
+        let near = collide(
+            player_transform.translation,
+            graze_size,
+            enemy_transform.translation,
+            enemy_size,
+            enemy_transform.rotation,
+        );
+        let hit = collide(
+            player_transform.translation,
+            player_size,
+            enemy_transform.translation,
+            enemy_size,
+            enemy_transform.rotation,
+        );
+        if near && !hit {
+            commands.entity(entity).insert(Grazed);
+            meter.value = (meter.value + GRAZE_FILL).min(1.0);
+            meter.since_last_graze = 0.0;
+            near_misses.write(NearMiss { enemy: entity });
+        }
+    }
+}
+
+/// System that slowly drains the meter when the player plays it safe
+fn decay_graze_meter(time: Res<Time>, mut meter: ResMut<GrazeMeter>) {
+    meter.since_last_graze += time.delta_secs();
+    if meter.since_last_graze > GRAZE_DECAY_DELAY {
+        meter.value = (meter.value - GRAZE_DECAY * time.delta_secs()).max(0.0);
+    }
+}
+
+/// System that cashes in a full meter
+fn fill_graze_meter(
+    mut commands: Commands,
+    mut meter: ResMut<GrazeMeter>,
+    mut bombs: ResMut<Bombs>,
+    player_query: Query<Entity, With<Player>>,
+) {
+    if meter.value < 1.0 {
+        return;
+    }
+    meter.value = 0.0;
+
+    if !bombs.grant()
+        && let Ok(player) = player_query.single()
+    {
+        commands
+            .entity(player)
+            .insert(TemporaryShield::new(GRAZE_SHIELD_DURATION));
+    }
+}
+
+/// System to spawn the meter bar under the score
+fn spawn_graze_bar(mut commands: Commands) {
+    commands.spawn((
+        Node {
+            width: Val::Px(METER_SIZE.x),
+            height: Val::Px(METER_SIZE.y),
+            ..default()
+        },
+        SegmentedBar::new(METER_SEGMENTS, METER_NEARLY_FULL_COLOR)
+            .with_threshold(0.8, METER_COLOR)
+            .with_label("Graze"),
+        HudSlot::new(HudAnchor::TopRight, 20),
+        GrazeBar,
+    ));
+}
+
+/// System that fills the bar to the meter
+fn update_graze_bar(meter: Res<GrazeMeter>, mut query: Query<&mut SegmentedBar, With<GrazeBar>>) {
+    for mut bar in &mut query {
+        bar.set(meter.value);
+    }
+}
@@ -0,0 +1,164 @@
+use bevy::asset::RenderAssetUsages;
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use serde::{Deserialize, Serialize};
+
+use crate::RunPhase;
+use crate::difficulty::Difficulty;
+use crate::reset::{RunScoped, RunSetup};
+use crate::settings::Settings;
+use crate::stats::{Intensity, RunStats};
+
+// Heat vignette constants
+const TEXTURE_SIZE: u32 = 128;
+const INNER_RADIUS: f32 = 0.55; // Fraction of the half-diagonal left untinted
+const MAX_ALPHA: f32 = 0.45; // Edge opacity at full heat
+const INTENSITY_WEIGHT: f32 = 0.7; // The rest of the heat comes from the difficulty ramp
+const HEAT_RATE: f32 = 1.5; // How fast the vignette follows the heat, per second
+
+/// Colours the heat vignette can glow in, picked in the settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum HeatTint {
+    Off,
+    #[default]
+    Red,
+    Orange,
+    Violet,
+}
+
+impl HeatTint {
+    const ALL: [HeatTint; 4] = [
+        HeatTint::Off,
+        HeatTint::Red,
+        HeatTint::Orange,
+        HeatTint::Violet,
+    ];
+
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|&t| t == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            HeatTint::Off => "Off",
+            HeatTint::Red => "Red",
+            HeatTint::Orange => "Orange",
+            HeatTint::Violet => "Violet",
+        }
+    }
+
+    fn color(self) -> Option<Color> {
+        match self {
+            HeatTint::Off => None,
+            HeatTint::Red => Some(Color::srgb(0.9, 0.1, 0.05)),
+            HeatTint::Orange => Some(Color::srgb(1.0, 0.5, 0.1)),
+            HeatTint::Violet => Some(Color::srgb(0.6, 0.2, 0.9)),
+        }
+    }
+}
+
+// --- Components ---
+
+/// Full-screen overlay whose edges glow hotter as the run heats up.
+#[derive(Component)]
+#[require(RunScoped)]
+struct HeatVignette {
+    heat: f32,
+}
+
+// --- Resources ---
+
+/// White edges fading to a clear middle, tinted by the overlay's colour.
+#[derive(Resource)]
+struct VignetteTexture(Handle<Image>);
+
+impl FromWorld for VignetteTexture {
+    fn from_world(world: &mut World) -> Self {
+        Self(world.resource_mut::<Assets<Image>>().add(draw_vignette()))
+    }
+}
+
+fn draw_vignette() -> Image {
+    let center = Vec2::splat(TEXTURE_SIZE as f32 / 2.0);
+    let mut data = Vec::with_capacity((TEXTURE_SIZE * TEXTURE_SIZE * 4) as usize);
+    for y in 0..TEXTURE_SIZE {
+        for x in 0..TEXTURE_SIZE {
+            let point = (Vec2::new(x as f32, y as f32) + 0.5 - center) / center;
+            let distance = point.length() / std::f32::consts::SQRT_2;
+            let t = ((distance - INNER_RADIUS) / (1.0 - INNER_RADIUS)).clamp(0.0, 1.0);
+            let alpha = t * t * (3.0 - 2.0 * t);
+            data.extend_from_slice(&[255, 255, 255, (alpha * 255.0) as u8]);
+        }
+    }
+    Image::new(
+        Extent3d {
+            width: TEXTURE_SIZE,
+            height: TEXTURE_SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    )
+}
+
+pub struct HeatPlugin;
+
+impl Plugin for HeatPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<VignetteTexture>()
+            .add_systems(RunSetup, spawn_vignette)
+            .add_systems(Update, update_vignette.run_if(in_state(RunPhase::Alive)));
+    }
+}
+
+/// System to lay the vignette over the playfield, under the rest of the HUD
+fn spawn_vignette(mut commands: Commands, texture: Res<VignetteTexture>) {
+    commands.spawn((
+        ImageNode {
+            image: texture.0.clone(),
+            color: Color::NONE,
+            ..default()
+        },
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            ..default()
+        },
+        GlobalZIndex(-1),
+        HeatVignette { heat: 0.0 },
+    ));
+}
+
+/// System that eases the vignette towards the current heat, a mix of the
+/// intensity metric and how far the difficulty has ramped. It stays clear
+/// with reduced motion or epilepsy-safe flashes on
+fn update_vignette(
+    time: Res<Time>,
+    intensity: Res<Intensity>,
+    stats: Res<RunStats>,
+    difficulty: Res<Difficulty>,
+    settings: Res<Settings>,
+    mut query: Query<(&mut ImageNode, &mut HeatVignette)>,
+) {
+    let Ok((mut image, mut vignette)) = query.single_mut() else {
+        return;
+    };
+    let tint = settings
+        .heat_vignette
+        .color()
+        .filter(|_| !settings.reduce_motion && !settings.epilepsy_safe);
+    let Some(tint) = tint else {
+        image.color = Color::NONE;
+        vignette.heat = 0.0;
+        return;
+    };
+
+    let ramp = (stats.elapsed() / difficulty.config.spawn.ramp_seconds.max(f32::EPSILON)).min(1.0);
+    let target = intensity.0 * INTENSITY_WEIGHT + ramp * (1.0 - INTENSITY_WEIGHT);
+    vignette.heat += (target - vignette.heat) * (HEAT_RATE * time.delta_secs()).min(1.0);
+    image.color = tint.with_alpha(vignette.heat * MAX_ALPHA);
+}
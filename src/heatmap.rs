@@ -0,0 +1,120 @@
+use bevy::asset::RenderAssetUsages;
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use serde::{Deserialize, Serialize};
+
+use crate::practice::practice_enabled;
+use crate::profile::{ActiveProfile, DEATHS_FILE};
+use crate::window::WindowMetrics;
+use crate::{Player, RunPhase};
+
+// Heatmap constants
+const GRID_WIDTH: usize = 32; // Cells across the play area
+const GRID_HEIGHT: usize = 18; // Cells down the play area
+
+// --- Resources ---
+
+/// Where the active profile's runs have ended, counted on a coarse grid over
+/// the play area.
+#[derive(Resource, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DeathHeatmap {
+    width: usize,
+    height: usize,
+    /// Row-major death counts, row 0 at the bottom of the screen.
+    cells: Vec<u32>,
+}
+
+impl Default for DeathHeatmap {
+    fn default() -> Self {
+        Self {
+            width: GRID_WIDTH,
+            height: GRID_HEIGHT,
+            cells: vec![0; GRID_WIDTH * GRID_HEIGHT],
+        }
+    }
+}
+
+impl DeathHeatmap {
+    pub fn total(&self) -> u32 {
+        self.cells.iter().sum()
+    }
+
+    /// Counts a death at a point given in 0..1 coordinates of the play area.
+    pub fn record(&mut self, point: Vec2) {
+        // A save from a different grid size is started over rather than stretched
+        if self.cells.len() != self.width * self.height {
+            *self = Self::default();
+        }
+        let point = point.clamp(Vec2::ZERO, Vec2::splat(0.999));
+        let x = (point.x * self.width as f32) as usize;
+        let y = (point.y * self.height as f32) as usize;
+        self.cells[y * self.width + x] += 1;
+    }
+
+    /// Renders the counts as a transparent-to-red texture, one pixel per cell.
+    pub fn to_image(&self) -> Image {
+        let max = self.cells.iter().copied().max().unwrap_or(0).max(1) as f32;
+        let mut data = Vec::with_capacity(self.cells.len() * 4);
+        // Textures start at the top row, the grid at the bottom one
+        for row in self.cells.chunks(self.width.max(1)).rev() {
+            for &count in row {
+                let density = count as f32 / max;
+                let color = heat_color(density).to_srgba();
+                data.extend_from_slice(&[
+                    (color.red * 255.0) as u8,
+                    (color.green * 255.0) as u8,
+                    (color.blue * 255.0) as u8,
+                    (color.alpha * 255.0) as u8,
+                ]);
+            }
+        }
+
+        Image::new(
+            Extent3d {
+                width: self.width as u32,
+                height: self.height as u32,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::RENDER_WORLD,
+        )
+    }
+}
+
+/// Blue for the odd death, through yellow, to red for the busiest cells.
+fn heat_color(density: f32) -> Color {
+    if density <= 0.0 {
+        return Color::NONE;
+    }
+    let hue = 240.0 * (1.0 - density);
+    Color::hsla(hue, 1.0, 0.5, 0.35 + 0.65 * density)
+}
+
+pub struct HeatmapPlugin;
+
+impl Plugin for HeatmapPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DeathHeatmap>().add_systems(
+            OnEnter(RunPhase::Dying),
+            record_death.run_if(not(practice_enabled)),
+        );
+    }
+}
+
+/// System that adds the player's position at the fatal collision to the heatmap
+fn record_death(
+    profile: Res<ActiveProfile>,
+    mut heatmap: ResMut<DeathHeatmap>,
+    player_query: Query<&Transform, With<Player>>,
+    window: Res<WindowMetrics>,
+) {
+    let Ok(transform) = player_query.single() else {
+        return;
+    };
+    let point = transform.translation.truncate() / window.size + Vec2::splat(0.5);
+    heatmap.record(point);
+    profile.save(DEATHS_FILE, &*heatmap);
+}
@@ -0,0 +1,98 @@
+use bevy::prelude::*;
+
+use crate::GameState;
+use crate::animated_number::AnimatedNumber;
+use crate::arena::{Arena, RiskMultiplier};
+use crate::bomb::Bombs;
+use crate::hud_layout::{HudAnchor, HudSlot};
+use crate::practice::Practice;
+use crate::reset::{RunScoped, RunSetup};
+use crate::score::Score;
+use crate::sets::GameSet;
+use crate::settings::Settings;
+use crate::text_style::TextStyleLibrary;
+
+// HUD constants
+const SCORE_PULSE: f32 = 15.0; // Points in one go that make the score pulse
+const RISK_PULSE: f32 = 0.5; // Multiplier jump that makes the risk display pulse
+
+// --- Components ---
+
+#[derive(Component)]
+#[require(RunScoped)]
+struct HudText;
+
+#[derive(Component)]
+#[require(RunScoped)]
+struct ScoreDisplay;
+
+#[derive(Component)]
+#[require(RunScoped)]
+struct RiskDisplay;
+
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(RunSetup, spawn_hud).add_systems(
+            Update,
+            update_hud
+                .in_set(GameSet::UiSync)
+                .run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+/// System to spawn the in-run HUD in the top-right corner
+fn spawn_hud(mut commands: Commands, styles: Res<TextStyleLibrary>, arena: Res<Arena>) {
+    commands.spawn((
+        Text::default(),
+        styles.hud.ui(),
+        AnimatedNumber::new(|value| format!("Score: {}", value as u32), SCORE_PULSE),
+        HudSlot::new(HudAnchor::TopRight, 0),
+        ScoreDisplay,
+    ));
+    if arena.enabled {
+        commands.spawn((
+            Text::default(),
+            styles.hud.ui(),
+            AnimatedNumber::new(|value| format!("Risk: x{value:.1}"), RISK_PULSE),
+            HudSlot::new(HudAnchor::TopRight, 10),
+            RiskDisplay,
+        ));
+    }
+    commands.spawn((
+        Text::default(),
+        styles.hud.ui(),
+        HudSlot::new(HudAnchor::TopRight, 30),
+        HudText,
+    ));
+}
+
+/// System that keeps the HUD in sync with the run
+fn update_hud(
+    score: Res<Score>,
+    bombs: Res<Bombs>,
+    practice: Res<Practice>,
+    settings: Res<Settings>,
+    risk: Res<RiskMultiplier>,
+    mut score_query: Query<&mut AnimatedNumber, (With<ScoreDisplay>, Without<RiskDisplay>)>,
+    mut risk_query: Query<&mut AnimatedNumber, With<RiskDisplay>>,
+    mut query: Query<&mut Text, With<HudText>>,
+) {
+    for mut number in &mut score_query {
+        number.set(score.points() as f32);
+    }
+    for mut number in &mut risk_query {
+        number.set(risk.0);
+    }
+    for mut text in &mut query {
+        text.0 = format!("Bombs: {} (B)", bombs.count);
+        if practice.enabled {
+            text.0.push_str("\nPRACTICE");
+        }
+        if settings.adaptive_difficulty {
+            text.0.push_str("\nADAPTIVE (unranked)");
+        }
+    }
+}
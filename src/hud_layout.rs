@@ -0,0 +1,189 @@
+use bevy::prelude::*;
+use bevy::window::{PrimaryWindow, WindowResized};
+
+// HUD layout constants
+const MIN_MARGIN: f32 = 10.0; // Pixels kept clear along every window edge
+const SAFE_AREA_FRACTION: f32 = 0.02; // Extra margin on big screens, as a fraction of the shorter side
+const STACK_GAP: f32 = 4.0; // Pixels between the items of a stack
+
+/// A corner or edge of the screen that HUD items stack at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HudAnchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl HudAnchor {
+    const ALL: [HudAnchor; 8] = [
+        HudAnchor::TopLeft,
+        HudAnchor::Top,
+        HudAnchor::TopRight,
+        HudAnchor::Left,
+        HudAnchor::Right,
+        HudAnchor::BottomLeft,
+        HudAnchor::Bottom,
+        HudAnchor::BottomRight,
+    ];
+
+    /// The stack container's node, inset by `margin` from the window edges.
+    fn node(self, margin: f32) -> Node {
+        let edge = Val::Px(margin);
+        let mut node = Node {
+            position_type: PositionType::Absolute,
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(STACK_GAP),
+            ..default()
+        };
+        match self {
+            HudAnchor::TopLeft | HudAnchor::Left | HudAnchor::BottomLeft => {
+                node.left = edge;
+                node.align_items = AlignItems::FlexStart;
+            }
+            HudAnchor::TopRight | HudAnchor::Right | HudAnchor::BottomRight => {
+                node.right = edge;
+                node.align_items = AlignItems::FlexEnd;
+            }
+            // Span the width so the stack stays centred however the window is sized
+            HudAnchor::Top | HudAnchor::Bottom => {
+                node.left = edge;
+                node.right = edge;
+                node.align_items = AlignItems::Center;
+            }
+        }
+        match self {
+            HudAnchor::TopLeft | HudAnchor::Top | HudAnchor::TopRight => node.top = edge,
+            HudAnchor::BottomLeft | HudAnchor::Bottom | HudAnchor::BottomRight => {
+                node.bottom = edge;
+            }
+            HudAnchor::Left | HudAnchor::Right => {
+                node.top = edge;
+                node.bottom = edge;
+                node.justify_content = JustifyContent::Center;
+            }
+        }
+        node
+    }
+}
+
+// --- Components ---
+
+/// Places a HUD item in the stack at `anchor`. Items are listed by `order`,
+/// lowest first, starting from the screen edge for bottom anchors.
+#[derive(Component)]
+pub struct HudSlot {
+    pub anchor: HudAnchor,
+    pub order: u32,
+}
+
+impl HudSlot {
+    pub fn new(anchor: HudAnchor, order: u32) -> Self {
+        Self { anchor, order }
+    }
+}
+
+/// The container that holds the items of one anchor.
+#[derive(Component)]
+struct HudStack(HudAnchor);
+
+// --- Resources ---
+
+/// Space kept clear along the window edges, so nothing sits under rounded
+/// corners or gets lost to TV overscan.
+#[derive(Resource)]
+pub struct SafeArea {
+    pub margin: f32,
+}
+
+impl Default for SafeArea {
+    fn default() -> Self {
+        Self { margin: MIN_MARGIN }
+    }
+}
+
+pub struct HudLayoutPlugin;
+
+impl Plugin for HudLayoutPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SafeArea>()
+            .add_systems(Startup, spawn_hud_stacks)
+            .add_systems(
+                Update,
+                (
+                    update_safe_area,
+                    apply_safe_area.run_if(resource_changed::<SafeArea>),
+                    place_hud_items,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// System to spawn one empty stack per anchor
+fn spawn_hud_stacks(mut commands: Commands, safe_area: Res<SafeArea>) {
+    for anchor in HudAnchor::ALL {
+        commands.spawn((anchor.node(safe_area.margin), HudStack(anchor)));
+    }
+}
+
+/// System that grows the safe area with the window
+fn update_safe_area(
+    mut resized: EventReader<WindowResized>,
+    mut safe_area: ResMut<SafeArea>,
+    primary_query: Query<(), With<PrimaryWindow>>,
+) {
+    let Some(window) = resized
+        .read()
+        .filter(|event| primary_query.contains(event.window))
+        .last()
+    else {
+        return;
+    };
+    let margin = (window.width.min(window.height) * SAFE_AREA_FRACTION).max(MIN_MARGIN);
+    if safe_area.margin != margin {
+        safe_area.margin = margin;
+    }
+}
+
+/// System that moves the stacks in to the safe area
+fn apply_safe_area(safe_area: Res<SafeArea>, mut query: Query<(&mut Node, &HudStack)>) {
+    for (mut node, stack) in &mut query {
+        *node = stack.0.node(safe_area.margin);
+    }
+}
+
+/// System that moves newly spawned HUD items into their stack, in order
+fn place_hud_items(
+    mut commands: Commands,
+    new_items: Query<(), Added<HudSlot>>,
+    item_query: Query<(Entity, &HudSlot)>,
+    stack_query: Query<(Entity, &HudStack)>,
+) {
+    if new_items.is_empty() {
+        return;
+    }
+    for (stack, HudStack(anchor)) in &stack_query {
+        let mut items: Vec<(u32, Entity)> = item_query
+            .iter()
+            .filter(|(_, slot)| slot.anchor == *anchor)
+            .map(|(entity, slot)| (slot.order, entity))
+            .collect();
+        // Bottom stacks grow upward, so their first item sits at the edge
+        if matches!(
+            anchor,
+            HudAnchor::BottomLeft | HudAnchor::Bottom | HudAnchor::BottomRight
+        ) {
+            items.sort_by(|a, b| b.cmp(a));
+        } else {
+            items.sort();
+        }
+        // Re-adding every item puts them back in order
+        let items: Vec<Entity> = items.into_iter().map(|(_, entity)| entity).collect();
+        commands.entity(stack).add_children(&items);
+    }
+}
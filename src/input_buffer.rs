@@ -0,0 +1,164 @@
+use std::collections::HashMap;
+
+use bevy::input::InputSystem;
+use bevy::input::gamepad::{GamepadAxisChangedEvent, GamepadButtonChangedEvent};
+use bevy::input::keyboard::KeyboardInput;
+use bevy::input::mouse::MouseButtonInput;
+use bevy::prelude::*;
+
+// Input buffer constants
+const BUFFER_WINDOW: f64 = 0.2; // Seconds a press stays valid while waiting to be used
+const DEBOUNCE: f64 = 0.3; // Minimum seconds between two uses of the same action
+pub const GAMEPAD_THRESHOLD: f32 = 0.5; // Stick or trigger travel that counts as using the gamepad
+
+/// Actions that trigger state transitions and should survive being pressed a
+/// little too early.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BufferedAction {
+    Restart,
+    Confirm,
+}
+
+impl BufferedAction {
+    const ALL: [BufferedAction; 2] = [BufferedAction::Restart, BufferedAction::Confirm];
+
+    fn keys(self) -> &'static [KeyCode] {
+        match self {
+            BufferedAction::Restart => &[KeyCode::KeyR],
+            BufferedAction::Confirm => &[KeyCode::Enter, KeyCode::NumpadEnter],
+        }
+    }
+
+    fn buttons(self) -> &'static [GamepadButton] {
+        match self {
+            BufferedAction::Restart => &[GamepadButton::North],
+            // Gamepads confirm through menu navigation
+            BufferedAction::Confirm => &[],
+        }
+    }
+}
+
+// --- Resources ---
+
+/// Remembers recent presses of transition keys for a few frames.
+#[derive(Resource, Default)]
+pub struct InputBuffer {
+    now: f64,
+    pressed_at: HashMap<BufferedAction, f64>,
+    last_used: HashMap<BufferedAction, f64>,
+}
+
+/// Real seconds since the player last touched any input, for AFK detection.
+#[derive(Resource, Default)]
+pub struct InputActivity {
+    pub idle: f32,
+}
+
+/// The kind of device the player touched last, so prompts show its buttons.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LastInputDevice {
+    #[default]
+    Keyboard,
+    Gamepad,
+}
+
+impl InputBuffer {
+    /// Returns true (once) if the action was pressed within the buffer window
+    /// and hasn't been used too recently.
+    pub fn consume(&mut self, action: BufferedAction) -> bool {
+        let Some(&pressed_at) = self.pressed_at.get(&action) else {
+            return false;
+        };
+        let debounced = self
+            .last_used
+            .get(&action)
+            .is_some_and(|&used| self.now - used < DEBOUNCE);
+        if self.now - pressed_at > BUFFER_WINDOW || debounced {
+            return false;
+        }
+        self.pressed_at.remove(&action);
+        self.last_used.insert(action, self.now);
+        true
+    }
+
+    /// Drops a pending press that was already handled some other way.
+    pub fn clear(&mut self, action: BufferedAction) {
+        self.pressed_at.remove(&action);
+    }
+}
+
+pub struct InputBufferPlugin;
+
+impl Plugin for InputBufferPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InputBuffer>()
+            .init_resource::<LastInputDevice>()
+            .init_resource::<InputActivity>()
+            .add_systems(PreUpdate, (buffer_inputs, track_input).after(InputSystem));
+    }
+}
+
+/// System that records fresh presses of buffered actions
+pub fn buffer_inputs(
+    real_time: Res<Time<Real>>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    mut buffer: ResMut<InputBuffer>,
+) {
+    let now = real_time.elapsed_secs_f64();
+    buffer.now = now;
+
+    for action in BufferedAction::ALL {
+        let pad = gamepads
+            .iter()
+            .any(|gamepad| gamepad.any_just_pressed(action.buttons().iter().copied()));
+        if keyboard_input.any_just_pressed(action.keys().iter().copied()) || pad {
+            buffer.pressed_at.insert(action, now);
+        }
+    }
+    buffer
+        .pressed_at
+        .retain(|_, &mut pressed_at| now - pressed_at <= BUFFER_WINDOW);
+}
+
+/// System that notes how long the player has been idle, and whether the
+/// keyboard and mouse or a gamepad was used last
+fn track_input(
+    real_time: Res<Time<Real>>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut mouse_events: EventReader<MouseButtonInput>,
+    mut button_events: EventReader<GamepadButtonChangedEvent>,
+    mut axis_events: EventReader<GamepadAxisChangedEvent>,
+    mut device: ResMut<LastInputDevice>,
+    mut activity: ResMut<InputActivity>,
+) {
+    let keyboard = keyboard_events.read().count() + mouse_events.read().count() > 0;
+    // Resting sticks drift, so only clear movement counts
+    let gamepad = button_events
+        .read()
+        .any(|event| event.value > GAMEPAD_THRESHOLD)
+        || axis_events
+            .read()
+            .any(|event| event.value.abs() > GAMEPAD_THRESHOLD);
+
+    // A key or stick held steady sends no events but is still someone playing
+    let holding = keyboard_input.get_pressed().next().is_some()
+        || gamepads.iter().any(|gamepad| {
+            gamepad.get_pressed().next().is_some()
+                || gamepad.left_stick().length() > GAMEPAD_THRESHOLD
+        });
+    if keyboard || gamepad || holding {
+        activity.idle = 0.0;
+    } else {
+        activity.idle += real_time.delta_secs();
+    }
+
+    let used = match (keyboard, gamepad) {
+        (true, false) => LastInputDevice::Keyboard,
+        (false, true) => LastInputDevice::Gamepad,
+        _ => return,
+    };
+    device.set_if_neq(used);
+}
@@ -0,0 +1,115 @@
+use bevy::input::common_conditions::input_just_pressed;
+use bevy::prelude::*;
+use bevy::render::camera::RenderTarget;
+use bevy::render::view::RenderLayers;
+use bevy::window::{WindowCloseRequested, WindowRef, WindowResolution};
+use bevy_egui::{EguiContext, EguiPlugin, egui};
+use bevy_inspector_egui::DefaultInspectorConfigPlugin;
+use bevy_inspector_egui::bevy_inspector;
+
+// Inspector constants
+const TOGGLE_KEY: KeyCode = KeyCode::F7;
+const INSPECTOR_TITLE: &str = "Rusty Dodger inspector";
+const INSPECTOR_SIZE: Vec2 = Vec2::new(480.0, 720.0);
+const INSPECTOR_LAYER: usize = 30; // Render layer nothing but the inspector camera uses
+
+// --- Components ---
+
+/// The inspector window and its camera.
+#[derive(Component)]
+struct Inspector;
+
+/// The camera whose egui context draws the inspector.
+#[derive(Component)]
+struct InspectorCamera;
+
+/// Dev builds only: a second window listing every entity, component and
+/// resource, editable while the game runs. Types show up once registered
+/// for reflection, as the snapshot types are.
+pub struct InspectorPlugin;
+
+impl Plugin for InspectorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            EguiPlugin {
+                enable_multipass_for_primary_context: false,
+            },
+            DefaultInspectorConfigPlugin,
+        ))
+        .add_systems(
+            Update,
+            (
+                toggle_inspector.run_if(input_just_pressed(TOGGLE_KEY)),
+                close_inspector,
+                inspector_ui,
+            )
+                .chain(),
+        );
+    }
+}
+
+/// System to open the inspector window, or close it when it's open
+fn toggle_inspector(mut commands: Commands, query: Query<Entity, With<Inspector>>) {
+    if !query.is_empty() {
+        for entity in &query {
+            commands.entity(entity).despawn();
+        }
+        return;
+    }
+    let window = commands
+        .spawn((
+            Window {
+                title: INSPECTOR_TITLE.to_string(),
+                resolution: WindowResolution::new(INSPECTOR_SIZE.x, INSPECTOR_SIZE.y),
+                ..default()
+            },
+            Inspector,
+        ))
+        .id();
+    commands.spawn((
+        Camera2d,
+        Camera {
+            target: RenderTarget::Window(WindowRef::Entity(window)),
+            order: 2,
+            ..default()
+        },
+        // Keep the game world out of the inspector window
+        RenderLayers::layer(INSPECTOR_LAYER),
+        Inspector,
+        InspectorCamera,
+    ));
+}
+
+/// System that closes the inspector on its own, leaving the game running
+fn close_inspector(
+    mut commands: Commands,
+    mut close_events: EventReader<WindowCloseRequested>,
+    window_query: Query<(), (With<Window>, With<Inspector>)>,
+    query: Query<Entity, With<Inspector>>,
+) {
+    let closing = close_events
+        .read()
+        .any(|event| window_query.contains(event.window));
+    if !closing {
+        return;
+    }
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// Exclusive system that draws the world into the inspector window
+fn inspector_ui(world: &mut World) {
+    let Ok(context) = world
+        .query_filtered::<&mut EguiContext, With<InspectorCamera>>()
+        .single(world)
+    else {
+        return;
+    };
+    let mut context = context.clone();
+    egui::CentralPanel::default().show(context.get_mut(), |ui| {
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            bevy_inspector::ui_for_world(world, ui);
+        });
+    });
+}
@@ -0,0 +1,126 @@
+use bevy::prelude::*;
+
+use crate::camera::GameCamera;
+use crate::flash::ScreenFlash;
+use crate::settings::motion_enabled;
+use crate::{GameState, RunPhase};
+
+// Kill cam constants
+pub const KILL_CAM_DURATION: f32 = 1.2; // Real (unscaled) seconds before Game Over
+pub const KILL_CAM_TIME_SCALE: f32 = 0.2; // Game speed while the kill cam plays
+const KILL_CAM_ZOOM: f32 = 0.6; // Orthographic scale to zoom to (smaller is closer)
+const KILL_CAM_EASE_IN: f32 = 0.5; // Fraction of the kill cam spent moving the camera
+const FLASH_ALPHA: f32 = 0.8; // Starting opacity of the impact flash
+const FLASH_FADE: f32 = 0.35; // Fraction of the kill cam the flash takes to fade out
+
+// --- Resources ---
+
+/// Where the fatal collision happened, inserted by the collision system before
+/// entering `RunPhase::Dying`.
+#[derive(Resource)]
+pub struct KillCam {
+    impact: Vec2,
+    timer: Timer,
+    /// The camera's zoom when the kill cam started, which it zooms in from
+    /// and goes back to.
+    start_scale: f32,
+}
+
+impl KillCam {
+    pub fn new(impact: Vec2) -> Self {
+        Self {
+            impact,
+            timer: Timer::from_seconds(KILL_CAM_DURATION, TimerMode::Once),
+            start_scale: 1.0,
+        }
+    }
+}
+
+pub struct KillCamPlugin;
+
+impl Plugin for KillCamPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(RunPhase::Dying), start_kill_cam)
+            .add_systems(
+                Update,
+                (
+                    animate_kill_cam,
+                    move_kill_cam_camera.run_if(motion_enabled),
+                )
+                    .chain()
+                    .run_if(in_state(RunPhase::Dying)),
+            )
+            .add_systems(OnExit(RunPhase::Dying), end_kill_cam);
+    }
+}
+
+/// System that slows time down, flashes the screen and notes the camera's zoom
+fn start_kill_cam(
+    mut time: ResMut<Time<Virtual>>,
+    mut kill_cam: ResMut<KillCam>,
+    mut flashes: EventWriter<ScreenFlash>,
+    projection_query: Query<&Projection, With<GameCamera>>,
+) {
+    time.set_relative_speed(KILL_CAM_TIME_SCALE);
+    if let Ok(Projection::Orthographic(ortho)) = projection_query.single() {
+        kill_cam.start_scale = ortho.scale;
+    }
+
+    flashes.write(ScreenFlash {
+        color: Color::WHITE,
+        alpha: FLASH_ALPHA,
+        duration: KILL_CAM_DURATION * FLASH_FADE,
+    });
+}
+
+/// System that moves on to Game Over once the kill cam has played out
+fn animate_kill_cam(
+    real_time: Res<Time<Real>>,
+    mut kill_cam: ResMut<KillCam>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    // Tick on real time so the slow motion doesn't also stretch the kill cam
+    kill_cam.timer.tick(real_time.delta());
+
+    if kill_cam.timer.finished() {
+        next_state.set(GameState::GameOver);
+    }
+}
+
+/// System that zooms the camera toward the impact
+fn move_kill_cam_camera(
+    kill_cam: Res<KillCam>,
+    mut camera_query: Query<(&mut Transform, &mut Projection), With<GameCamera>>,
+) {
+    // Ease out cubic toward the impact, then hold
+    let t = (kill_cam.timer.fraction() / KILL_CAM_EASE_IN).min(1.0);
+    let eased = 1.0 - (1.0 - t).powi(3);
+
+    if let Ok((mut transform, mut projection)) = camera_query.single_mut() {
+        let target = kill_cam.impact.extend(transform.translation.z);
+        transform.translation = Vec3::ZERO.with_z(target.z).lerp(target, eased);
+        if let Projection::Orthographic(ortho) = &mut *projection {
+            ortho.scale = kill_cam.start_scale + (KILL_CAM_ZOOM - kill_cam.start_scale) * eased;
+        }
+    }
+}
+
+/// System that restores time and the camera
+fn end_kill_cam(
+    mut commands: Commands,
+    mut time: ResMut<Time<Virtual>>,
+    kill_cam: Option<Res<KillCam>>,
+    mut camera_query: Query<(&mut Transform, &mut Projection), With<GameCamera>>,
+) {
+    time.set_relative_speed(1.0);
+    let start_scale = kill_cam.map_or(1.0, |kill_cam| kill_cam.start_scale);
+
+    if let Ok((mut transform, mut projection)) = camera_query.single_mut() {
+        transform.translation = Vec3::ZERO.with_z(transform.translation.z);
+        if let Projection::Orthographic(ortho) = &mut *projection {
+            ortho.scale = start_scale;
+        }
+    }
+
+    commands.remove_resource::<KillCam>();
+}
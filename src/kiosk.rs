@@ -0,0 +1,263 @@
+use std::env;
+
+use bevy::prelude::*;
+
+use crate::difficulty::Difficulty;
+use crate::input_buffer::InputActivity;
+use crate::lanes;
+use crate::mutator;
+use crate::party::{self, Party};
+use crate::practice::Practice;
+use crate::profile::{ActiveProfile, HIGH_SCORES_FILE, ProfileChosen};
+use crate::reset::{ResetRunEvent, RunScoped, RunSetup};
+use crate::score::HighScores;
+use crate::sets::GameSet;
+use crate::text_style::TextStyleLibrary;
+use crate::window::WindowMetrics;
+use crate::{Enemy, GameState, Player, RunPhase, Velocity};
+
+// Kiosk constants
+const KIOSK_FLAG: &str = "--kiosk";
+const KIOSK_PROFILE: &str = "Kiosk"; // Every run at a kiosk is played on this profile
+const ATTRACT_DELAY: f32 = 8.0; // Seconds without input before the game plays itself
+const RESTART_COUNTDOWN: f32 = 10.0; // Seconds on the Game Over screen before the next run
+const HIGH_SCORE_RESET: f32 = 60.0 * 60.0; // Seconds between clearing the high score table
+const LOOKAHEAD: f32 = 260.0; // How far above the player the autopilot watches for enemies
+const DODGE_WIDTH: f32 = 90.0; // Enemies further to either side than this are ignored
+const CENTRE_PULL: f32 = 0.3; // How strongly the autopilot drifts back to the middle
+
+// --- Resources ---
+
+/// Kiosk mode for meetups and exhibitions, turned on with `--kiosk`: the game
+/// plays itself whenever nobody's touching it, restarts on its own after Game
+/// Over and clears its high scores every hour. Settings, profiles and quitting
+/// are off, so whoever runs the machine sets it up and stops it from outside
+/// the game.
+#[derive(Resource, Default)]
+pub struct Kiosk {
+    pub enabled: bool,
+    /// Whether the current run is the game playing itself.
+    demo: bool,
+    /// Whether practice was on before demos turned it on, to put it back.
+    practice_before: bool,
+    countdown: Timer,
+    reset_timer: Timer,
+}
+
+// --- Components ---
+
+#[derive(Component)]
+#[require(RunScoped)]
+struct DemoBanner;
+
+#[derive(Component)]
+#[require(RunScoped)]
+struct RestartCountdown;
+
+pub struct KioskPlugin;
+
+impl Plugin for KioskPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Kiosk {
+            enabled: env::args().any(|arg| arg == KIOSK_FLAG),
+            countdown: Timer::from_seconds(RESTART_COUNTDOWN, TimerMode::Once),
+            reset_timer: Timer::from_seconds(HIGH_SCORE_RESET, TimerMode::Repeating),
+            ..default()
+        })
+        .add_systems(
+            OnEnter(GameState::ProfileSelect),
+            choose_kiosk_profile.run_if(kiosk_enabled),
+        )
+        .add_systems(
+            RunSetup,
+            start_kiosk_run
+                .after(mutator::start_run_mutators)
+                .run_if(kiosk_enabled),
+        )
+        .add_systems(
+            Update,
+            (
+                attract_when_idle.run_if(idle_screen),
+                leave_party_when_idle
+                    .run_if(in_state(GameState::PartySetup).or(in_state(GameState::PartyResults))),
+                (
+                    steer_demo
+                        .in_set(GameSet::Input)
+                        .run_if(not(lanes::lanes_enabled)),
+                    take_over_demo,
+                )
+                    .run_if(demo_running.and(in_state(RunPhase::Alive))),
+                count_down_restart.run_if(in_state(GameState::GameOver)),
+                reset_high_scores.run_if(resource_exists::<ActiveProfile>),
+            )
+                .run_if(kiosk_enabled),
+        )
+        .add_systems(
+            OnEnter(GameState::GameOver),
+            show_restart_countdown.run_if(kiosk_enabled),
+        );
+    }
+}
+
+pub fn kiosk_enabled(kiosk: Res<Kiosk>) -> bool {
+    kiosk.enabled
+}
+
+/// Whether the game is playing itself.
+pub fn demo_running(kiosk: Res<Kiosk>) -> bool {
+    kiosk.enabled && kiosk.demo
+}
+
+/// Screens a visitor might walk away from, which the demo takes over.
+fn idle_screen(state: Res<State<GameState>>) -> bool {
+    matches!(
+        state.get(),
+        GameState::Menu | GameState::Stats | GameState::Progression | GameState::Changelog
+    )
+}
+
+/// System that skips profile selection, playing on the kiosk's own profile
+fn choose_kiosk_profile(mut chosen: EventWriter<ProfileChosen>) {
+    chosen.write(ProfileChosen(KIOSK_PROFILE.to_string()));
+}
+
+/// System that gives up on a party nobody's signing up for or looking at the
+/// results of, going back to the menu where the demo takes over
+fn leave_party_when_idle(
+    activity: Res<InputActivity>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if activity.idle >= ATTRACT_DELAY {
+        next_state.set(GameState::Menu);
+    }
+}
+
+/// System to start a demo run from the menus once nobody's touched anything
+/// for a while
+fn attract_when_idle(activity: Res<InputActivity>, mut next_state: ResMut<NextState<GameState>>) {
+    if activity.idle >= ATTRACT_DELAY {
+        next_state.set(GameState::Playing);
+    }
+}
+
+/// System that makes a run a demo when nobody started it, keeping it off
+/// the profile, and a real run otherwise
+pub fn start_kiosk_run(
+    mut commands: Commands,
+    mut kiosk: ResMut<Kiosk>,
+    activity: Res<InputActivity>,
+    mut practice: ResMut<Practice>,
+    styles: Res<TextStyleLibrary>,
+) {
+    let demo = activity.idle >= ATTRACT_DELAY;
+    if demo && !kiosk.demo {
+        kiosk.practice_before = practice.enabled;
+    }
+    if demo {
+        practice.enabled = true;
+    } else if kiosk.demo {
+        practice.enabled = kiosk.practice_before;
+    }
+    kiosk.demo = demo;
+    if !demo {
+        return;
+    }
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Percent(30.0),
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        DemoBanner,
+        children![(Text::new("DEMO - press any key to play"), styles.title.ui())],
+    ));
+}
+
+/// System that steers the player away from the enemies falling towards it,
+/// drifting back to the middle when there's nothing to dodge
+fn steer_demo(
+    difficulty: Res<Difficulty>,
+    window: Res<WindowMetrics>,
+    mut player_query: Query<(&Transform, &mut Velocity), With<Player>>,
+    enemy_query: Query<&Transform, (With<Enemy>, Without<Player>)>,
+) {
+    let Ok((player, mut velocity)) = player_query.single_mut() else {
+        return;
+    };
+    let position = player.translation.truncate();
+    let mut push = -position.x / (window.width() / 2.0) * CENTRE_PULL;
+    for enemy in &enemy_query {
+        let offset = enemy.translation.truncate() - position;
+        if offset.y < 0.0 || offset.y > LOOKAHEAD || offset.x.abs() > DODGE_WIDTH {
+            continue;
+        }
+        // The closer an enemy is to landing, the harder it pushes
+        push -= offset.x.signum() * (1.0 - offset.y / LOOKAHEAD);
+    }
+    velocity.0 = Vec2::new(push.clamp(-1.0, 1.0), 0.0) * difficulty.config.player_speed;
+}
+
+/// System that hands a demo over to whoever presses something, in a fresh run
+fn take_over_demo(activity: Res<InputActivity>, mut resets: EventWriter<ResetRunEvent>) {
+    if activity.idle == 0.0 {
+        resets.write(ResetRunEvent);
+    }
+}
+
+/// System to show how long until the next run starts
+fn show_restart_countdown(
+    mut commands: Commands,
+    mut kiosk: ResMut<Kiosk>,
+    styles: Res<TextStyleLibrary>,
+) {
+    kiosk.countdown.reset();
+    commands.spawn((
+        Text::default(),
+        styles.body.ui(),
+        TextLayout::new_with_justify(JustifyText::Center),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(40.0),
+            width: Val::Percent(100.0),
+            ..default()
+        },
+        RestartCountdown,
+    ));
+}
+
+/// System that counts down on the Game Over screen in real time and starts
+/// the next run, a demo if the last player has gone
+fn count_down_restart(
+    real_time: Res<Time<Real>>,
+    mut kiosk: ResMut<Kiosk>,
+    party: Option<Res<Party>>,
+    mut query: Query<&mut Text, With<RestartCountdown>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    kiosk.countdown.tick(real_time.delta());
+    let seconds = kiosk.countdown.remaining_secs().ceil() as u32;
+    for mut text in &mut query {
+        text.0 = format!("Next game in {seconds}");
+    }
+    if kiosk.countdown.just_finished() {
+        next_state.set(party::after_game_over(party.as_deref()));
+    }
+}
+
+/// System that clears the high score table on a schedule, so every session
+/// gets a fresh one to climb
+fn reset_high_scores(
+    real_time: Res<Time<Real>>,
+    mut kiosk: ResMut<Kiosk>,
+    profile: Res<ActiveProfile>,
+    mut high_scores: ResMut<HighScores>,
+) {
+    if !kiosk.reset_timer.tick(real_time.delta()).just_finished() {
+        return;
+    }
+    high_scores.entries.clear();
+    profile.save(HIGH_SCORES_FILE, &*high_scores);
+    info!("Kiosk mode: cleared the high score table");
+}
@@ -0,0 +1,132 @@
+use bevy::prelude::*;
+
+use crate::difficulty::Difficulty;
+use crate::sets::GameSet;
+use crate::window::WindowMetrics;
+use crate::{GameState, Player, RunPhase, Velocity};
+
+// Lane constants
+pub const LANE_COUNT: usize = 5;
+const HOP_RATE: f32 = 18.0; // How quickly the player closes in on their lane, per second
+const LANE_FILL: f32 = 0.8; // Widest an enemy can be, as a fraction of its lane
+const GUIDE_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.08);
+
+// --- Components ---
+
+/// The lane an entity is in, counted from the left. In lane runs only entities
+/// in the same lane can collide.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lane(pub usize);
+
+// --- Resources ---
+
+/// Whether runs are lane runs: the screen is split into lanes, the player hops
+/// between them with taps and enemies fall straight down their lane.
+#[derive(Resource, Default)]
+pub struct LaneMode {
+    pub enabled: bool,
+}
+
+pub struct LanePlugin;
+
+impl Plugin for LanePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LaneMode>()
+            .add_systems(
+                Update,
+                (assign_player_lane, hop_lanes, steer_to_lane)
+                    .chain()
+                    .in_set(GameSet::Input)
+                    .run_if(lanes_enabled.and(in_state(RunPhase::Alive))),
+            )
+            .add_systems(
+                Update,
+                draw_lane_guides.run_if(lanes_enabled.and(in_state(GameState::Playing))),
+            );
+    }
+}
+
+pub fn lanes_enabled(lane_mode: Res<LaneMode>) -> bool {
+    lane_mode.enabled
+}
+
+/// Horizontal centre of a lane on a window `width` wide.
+pub fn lane_center(lane: usize, width: f32) -> f32 {
+    let lane_width = width / LANE_COUNT as f32;
+    -width / 2.0 + lane_width * (lane as f32 + 0.5)
+}
+
+/// The lane at a fraction of the way across the screen.
+pub fn lane_at_fraction(fraction: f32) -> usize {
+    ((fraction * LANE_COUNT as f32).max(0.0) as usize).min(LANE_COUNT - 1)
+}
+
+/// Size of an enemy in a lane, narrowed if it would spill into the next one.
+pub fn fit_to_lane(size: Vec2, width: f32) -> Vec2 {
+    Vec2::new(size.x.min(width / LANE_COUNT as f32 * LANE_FILL), size.y)
+}
+
+/// System that puts a new (or restored) player in the lane they stand in
+fn assign_player_lane(
+    mut commands: Commands,
+    player_query: Query<(Entity, &Transform), (With<Player>, Without<Lane>)>,
+    window: Res<WindowMetrics>,
+) {
+    for (entity, transform) in &player_query {
+        let fraction = transform.translation.x / window.width() + 0.5;
+        commands
+            .entity(entity)
+            .insert(Lane(lane_at_fraction(fraction)));
+    }
+}
+
+/// System that hops the player one lane per tap of Left or Right
+fn hop_lanes(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    difficulty: Res<Difficulty>,
+    mut player_query: Query<&mut Lane, With<Player>>,
+) {
+    let Ok(mut lane) = player_query.single_mut() else {
+        return;
+    };
+    let mut step: isize = 0;
+    if keyboard_input.just_pressed(KeyCode::ArrowLeft) {
+        step -= 1;
+    }
+    if keyboard_input.just_pressed(KeyCode::ArrowRight) {
+        step += 1;
+    }
+    if difficulty.config.mirrored_controls {
+        step = -step;
+    }
+    let target = lane.0.saturating_add_signed(step).min(LANE_COUNT - 1);
+    if target != lane.0 {
+        lane.0 = target;
+    }
+}
+
+/// System that slides the player to the centre of their lane
+fn steer_to_lane(
+    mut player_query: Query<(&Transform, &Lane, &mut Velocity), With<Player>>,
+    window: Res<WindowMetrics>,
+) {
+    let Ok((transform, lane, mut velocity)) = player_query.single_mut() else {
+        return;
+    };
+    let offset = lane_center(lane.0, window.width()) - transform.translation.x;
+    velocity.0 = Vec2::new(offset * HOP_RATE, 0.0);
+}
+
+/// System that draws faint lines between the lanes
+fn draw_lane_guides(mut gizmos: Gizmos, window: Res<WindowMetrics>) {
+    let lane_width = window.width() / LANE_COUNT as f32;
+    let half_height = window.height() / 2.0;
+    for divider in 1..LANE_COUNT {
+        let x = -window.width() / 2.0 + lane_width * divider as f32;
+        gizmos.line_2d(
+            Vec2::new(x, -half_height),
+            Vec2::new(x, half_height),
+            GUIDE_COLOR,
+        );
+    }
+}
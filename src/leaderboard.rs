@@ -0,0 +1,287 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::prelude::*;
+use bevy::tasks::{IoTaskPool, Task, block_on, futures_lite::future};
+use serde::{Deserialize, Serialize};
+
+use crate::GameState;
+use crate::difficulty::{Difficulty, DifficultyPreset};
+use crate::hud_layout::{HudAnchor, HudSlot};
+use crate::mutator::{Mutator, RunMutators};
+use crate::practice::practice_enabled;
+use crate::profile::{self, ActiveProfile};
+use crate::rng::RunSeed;
+use crate::save;
+use crate::score::Score;
+use crate::settings::Settings;
+use crate::text_style::TextStyleLibrary;
+
+// Leaderboard constants
+const LEADERBOARD_CONFIG_FILE: &str = "leaderboard.ron"; // In the data directory
+const OUTBOX_FILE: &str = "leaderboard_outbox.ron"; // In the data directory, shared by all profiles
+const FIRST_RETRY_SECONDS: f32 = 5.0;
+const MAX_RETRY_SECONDS: f32 = 300.0;
+const STATUS_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.6);
+
+/// Contents of `leaderboard.ron`; leaving `endpoint` unset keeps scores local.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+struct LeaderboardConfig {
+    endpoint: Option<String>,
+    token: Option<String>,
+}
+
+/// One finished run waiting to be POSTed to `{endpoint}/scores`.
+#[derive(Clone, Serialize, Deserialize)]
+struct Submission {
+    player: String,
+    score: u32,
+    difficulty: DifficultyPreset,
+    mutators: Vec<Mutator>,
+    seed: u64,
+    /// Unix time (seconds) the run ended.
+    finished: u64,
+}
+
+/// How an upload attempt went.
+enum UploadOutcome {
+    Sent,
+    /// The server refused the score; sending it again won't help.
+    Rejected(u16),
+    /// Offline, timed out or the server is down; worth another try later.
+    Failed(String),
+}
+
+fn upload(endpoint: &str, token: Option<&str>, submission: &Submission) -> UploadOutcome {
+    let body = match ron::to_string(submission) {
+        Ok(body) => body,
+        Err(err) => return UploadOutcome::Failed(err.to_string()),
+    };
+    let mut request = ureq::post(&format!("{}/scores", endpoint.trim_end_matches('/')));
+    if let Some(token) = token {
+        request = request.set("Authorization", &format!("Bearer {token}"));
+    }
+    match request.send_string(&body) {
+        Ok(_) => UploadOutcome::Sent,
+        Err(ureq::Error::Status(code, _)) if (400..500).contains(&code) => {
+            UploadOutcome::Rejected(code)
+        }
+        Err(err) => UploadOutcome::Failed(err.to_string()),
+    }
+}
+
+// --- Resources ---
+
+/// Scores not yet accepted by the leaderboard, oldest first. Saved after every
+/// change so nothing is lost when the game closes offline.
+#[derive(Resource, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct Outbox {
+    pending: Vec<Submission>,
+}
+
+impl Outbox {
+    fn load() -> Self {
+        save::load_or_default(&save::data_dir().join(OUTBOX_FILE))
+    }
+
+    fn save(&self) {
+        let path = save::data_dir().join(OUTBOX_FILE);
+        if let Err(err) = save::store(&path, self) {
+            warn!(path = %path.display(), "Could not save the score outbox: {err}");
+        }
+    }
+}
+
+/// Works through the outbox one score at a time in the background, backing
+/// off while the server can't be reached.
+#[derive(Resource)]
+struct Uploader {
+    config: LeaderboardConfig,
+    in_flight: Option<Task<UploadOutcome>>,
+    retry: Timer,
+}
+
+impl Default for Uploader {
+    fn default() -> Self {
+        let config = save::load_or_default(&save::data_dir().join(LEADERBOARD_CONFIG_FILE));
+        // Finished, so whatever was left over from last session goes out straight away
+        let mut retry = Timer::from_seconds(FIRST_RETRY_SECONDS, TimerMode::Once);
+        retry.tick(retry.duration());
+        Self {
+            config,
+            in_flight: None,
+            retry,
+        }
+    }
+}
+
+impl Uploader {
+    fn enabled(&self) -> bool {
+        self.config.endpoint.is_some()
+    }
+
+    fn retry_now(&mut self) {
+        self.retry = Timer::from_seconds(FIRST_RETRY_SECONDS, TimerMode::Once);
+        let duration = self.retry.duration();
+        self.retry.tick(duration);
+    }
+
+    /// Waits twice as long as last time before the next try, up to a limit.
+    fn back_off(&mut self) {
+        let seconds = (self.retry.duration().as_secs_f32() * 2.0).min(MAX_RETRY_SECONDS);
+        self.retry = Timer::from_seconds(seconds, TimerMode::Once);
+    }
+}
+
+// --- Components ---
+
+#[derive(Component)]
+struct UploadStatus;
+
+pub struct LeaderboardPlugin;
+
+impl Plugin for LeaderboardPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Outbox::load())
+            .init_resource::<Uploader>()
+            .add_systems(Startup, spawn_upload_status)
+            .add_systems(
+                OnEnter(GameState::GameOver),
+                queue_submission
+                    .after(profile::record_run)
+                    .run_if(uploads_enabled.and(not(practice_enabled))),
+            )
+            .add_systems(
+                Update,
+                (drive_uploads, update_upload_status)
+                    .chain()
+                    .run_if(uploads_enabled),
+            );
+    }
+}
+
+fn uploads_enabled(uploader: Res<Uploader>) -> bool {
+    uploader.enabled()
+}
+
+/// System that puts the finished run in the outbox and sends it right away
+fn queue_submission(
+    profile: Res<ActiveProfile>,
+    score: Res<Score>,
+    difficulty: Res<Difficulty>,
+    mutators: Res<RunMutators>,
+    seed: Res<RunSeed>,
+    settings: Res<Settings>,
+    mut outbox: ResMut<Outbox>,
+    mut uploader: ResMut<Uploader>,
+) {
+    // Rubber-banded runs aren't comparable with the rest of the table
+    if settings.adaptive_difficulty || profile.name.is_empty() {
+        return;
+    }
+    let finished = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+    outbox.pending.push(Submission {
+        player: profile.name.clone(),
+        score: score.points(),
+        difficulty: difficulty.preset,
+        mutators: mutators.0.clone(),
+        seed: seed.seed,
+        finished,
+    });
+    outbox.save();
+    if uploader.in_flight.is_none() {
+        uploader.retry_now();
+    }
+}
+
+/// System that collects the upload in flight and starts the next one when due
+fn drive_uploads(
+    real_time: Res<Time<Real>>,
+    mut outbox: ResMut<Outbox>,
+    mut uploader: ResMut<Uploader>,
+) {
+    if let Some(task) = uploader.in_flight.as_mut() {
+        let Some(outcome) = block_on(future::poll_once(task)) else {
+            return;
+        };
+        uploader.in_flight = None;
+        match outcome {
+            UploadOutcome::Sent => {
+                outbox.pending.remove(0);
+                outbox.save();
+                uploader.retry_now();
+            }
+            UploadOutcome::Rejected(code) => {
+                let dropped = outbox.pending.remove(0);
+                outbox.save();
+                warn!(
+                    score = dropped.score,
+                    code, "Leaderboard refused a score, dropping it"
+                );
+                uploader.retry_now();
+            }
+            UploadOutcome::Failed(err) => {
+                uploader.back_off();
+                let retry = uploader.retry.duration().as_secs_f32();
+                warn!(
+                    pending = outbox.pending.len(),
+                    "Could not upload score, retrying in {retry:.0}s: {err}"
+                );
+            }
+        }
+        return;
+    }
+
+    uploader.retry.tick(real_time.delta());
+    if !uploader.retry.finished() {
+        return;
+    }
+    let (Some(submission), Some(endpoint)) = (
+        outbox.pending.first().cloned(),
+        uploader.config.endpoint.clone(),
+    ) else {
+        return;
+    };
+    let token = uploader.config.token.clone();
+    uploader.in_flight = Some(
+        IoTaskPool::get().spawn(async move { upload(&endpoint, token.as_deref(), &submission) }),
+    );
+}
+
+/// System to spawn the upload status line, empty until something is waiting
+fn spawn_upload_status(mut commands: Commands, styles: Res<TextStyleLibrary>) {
+    commands.spawn((
+        Text::default(),
+        styles.body.ui(),
+        TextColor(STATUS_COLOR),
+        HudSlot::new(HudAnchor::BottomRight, 100),
+        UploadStatus,
+    ));
+}
+
+/// System that shows how many scores are waiting and when the next try is
+fn update_upload_status(
+    outbox: Res<Outbox>,
+    uploader: Res<Uploader>,
+    mut query: Query<&mut Text, With<UploadStatus>>,
+) {
+    let Ok(mut text) = query.single_mut() else {
+        return;
+    };
+    let waiting = outbox.pending.len();
+    let status = if waiting == 0 {
+        String::new()
+    } else if uploader.in_flight.is_some() {
+        format!("Uploading scores ({waiting} left)")
+    } else {
+        let remaining = uploader.retry.remaining_secs();
+        format!("Scores waiting to upload: {waiting} (retrying in {remaining:.0}s)")
+    };
+    if text.0 != status {
+        text.0 = status;
+    }
+}
@@ -0,0 +1,3 @@
+//! The game's pure math, kept apart from the ECS so it can be unit tested.
+
+pub mod math;
@@ -0,0 +1,160 @@
+use bevy::asset::LoadState;
+use bevy::prelude::*;
+
+use crate::GameState;
+use crate::fallback::{self, MissingAssets};
+use crate::golden;
+use crate::shield;
+use crate::text_style::{FONT_BOLD, FONT_REGULAR};
+
+// Loading constants
+const BAR_WIDTH: f32 = 320.0;
+const BAR_HEIGHT: f32 = 12.0;
+const BAR_BACKGROUND: Color = Color::srgb(0.2, 0.2, 0.2);
+const BAR_FILL: Color = Color::srgb(0.3, 0.8, 1.0);
+
+/// What an asset is, so it gets the right loader.
+#[derive(Clone, Copy)]
+enum AssetKind {
+    Font,
+    Sound,
+}
+
+/// Every file the game loads through the asset server. Sprites are plain
+/// colours, so there are no textures here yet.
+const PRELOAD: &[(&str, AssetKind)] = &[
+    (FONT_REGULAR, AssetKind::Font),
+    (FONT_BOLD, AssetKind::Font),
+    (shield::BREAK_SOUND, AssetKind::Sound),
+    (golden::SPAWN_SOUND, AssetKind::Sound),
+];
+
+// --- Components ---
+
+#[derive(Component)]
+struct LoadingScreen;
+
+#[derive(Component)]
+struct LoadingBarFill;
+
+// --- Resources ---
+
+/// Handles to every preloaded asset. Kept for the whole session so the assets
+/// stay in memory.
+#[derive(Resource, Default)]
+struct PreloadedAssets {
+    handles: Vec<(&'static str, AssetKind, UntypedHandle)>,
+}
+
+pub struct LoadingPlugin;
+
+impl Plugin for LoadingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PreloadedAssets>()
+            .add_systems(
+                OnEnter(GameState::Loading),
+                (start_loading, spawn_loading_screen),
+            )
+            .add_systems(Update, track_loading.run_if(in_state(GameState::Loading)))
+            .add_systems(OnExit(GameState::Loading), despawn_loading_screen);
+    }
+}
+
+/// System that starts loading every asset in `PRELOAD`
+fn start_loading(asset_server: Res<AssetServer>, mut preloaded: ResMut<PreloadedAssets>) {
+    preloaded.handles = PRELOAD
+        .iter()
+        .map(|&(path, kind)| {
+            let handle = match kind {
+                AssetKind::Font => asset_server.load::<Font>(path).untyped(),
+                AssetKind::Sound => asset_server.load::<AudioSource>(path).untyped(),
+            };
+            (path, kind, handle)
+        })
+        .collect();
+}
+
+/// System to spawn the loading screen's text and progress bar
+fn spawn_loading_screen(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Px(12.0),
+                ..default()
+            },
+            LoadingScreen,
+        ))
+        .with_children(|parent| {
+            parent.spawn(Text::new("Loading..."));
+            parent
+                .spawn((
+                    Node {
+                        width: Val::Px(BAR_WIDTH),
+                        height: Val::Px(BAR_HEIGHT),
+                        ..default()
+                    },
+                    BackgroundColor(BAR_BACKGROUND),
+                ))
+                .with_child((
+                    Node {
+                        width: Val::Percent(0.0),
+                        height: Val::Percent(100.0),
+                        ..default()
+                    },
+                    BackgroundColor(BAR_FILL),
+                    LoadingBarFill,
+                ));
+        });
+}
+
+/// System that fills the bar as assets arrive and moves on once all of them
+/// have. Files that fail to load are swapped for the embedded placeholders.
+fn track_loading(
+    asset_server: Res<AssetServer>,
+    preloaded: Res<PreloadedAssets>,
+    mut missing: ResMut<MissingAssets>,
+    mut fonts: ResMut<Assets<Font>>,
+    mut sounds: ResMut<Assets<AudioSource>>,
+    mut fill_query: Query<&mut Node, With<LoadingBarFill>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let mut done = 0;
+    for (path, kind, handle) in &preloaded.handles {
+        match asset_server.get_load_state(handle.id()) {
+            Some(LoadState::Loaded) => done += 1,
+            Some(LoadState::Failed(err)) => {
+                done += 1;
+                if missing.0.contains(path) {
+                    continue;
+                }
+                warn!(%path, "Using a placeholder for an asset that failed to load: {err}");
+                match kind {
+                    AssetKind::Font => fallback::replace_font(&mut fonts, handle.id().typed()),
+                    AssetKind::Sound => fallback::replace_sound(&mut sounds, handle.id().typed()),
+                }
+                missing.0.push(path);
+            }
+            _ => {}
+        }
+    }
+
+    let total = preloaded.handles.len().max(1);
+    if let Ok(mut fill) = fill_query.single_mut() {
+        fill.width = Val::Percent(100.0 * done as f32 / total as f32);
+    }
+    if done == preloaded.handles.len() {
+        next_state.set(GameState::ProfileSelect);
+    }
+}
+
+/// System to remove the loading screen
+fn despawn_loading_screen(mut commands: Commands, query: Query<Entity, With<LoadingScreen>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
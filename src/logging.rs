@@ -0,0 +1,52 @@
+use std::env;
+
+use bevy::log::tracing_subscriber::Layer;
+use bevy::log::tracing_subscriber::fmt;
+use bevy::log::{BoxedLayer, Level, LogPlugin};
+use bevy::prelude::*;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+
+use crate::save;
+
+// Logging constants
+const LOGS_DIR: &str = "logs";
+const LOG_FILE_PREFIX: &str = "rusty_dodger";
+const MAX_LOG_FILES: usize = 7; // One per day, older ones are deleted
+const VERBOSE_FLAG: &str = "--verbose";
+const DEFAULT_FILTER: &str = "wgpu=error,naga=warn";
+const VERBOSE_FILTER: &str = "wgpu=warn,naga=info,rusty_dodger=trace";
+
+/// Bevy's log plugin set up for the game: console output plus a daily
+/// rotating file in the data directory, more detailed with `--verbose`.
+pub fn log_plugin() -> LogPlugin {
+    let verbose = env::args().any(|arg| arg == VERBOSE_FLAG);
+    LogPlugin {
+        level: if verbose { Level::DEBUG } else { Level::INFO },
+        filter: if verbose {
+            VERBOSE_FILTER
+        } else {
+            DEFAULT_FILTER
+        }
+        .to_string(),
+        custom_layer: file_layer,
+        ..default()
+    }
+}
+
+/// Writes everything that reaches the console to the log file as well.
+fn file_layer(_app: &mut App) -> Option<BoxedLayer> {
+    let appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(LOG_FILE_PREFIX)
+        .filename_suffix("log")
+        .max_log_files(MAX_LOG_FILES)
+        .build(save::data_dir().join(LOGS_DIR));
+    match appender {
+        Ok(appender) => Some(fmt::layer().with_ansi(false).with_writer(appender).boxed()),
+        Err(err) => {
+            // The subscriber isn't installed yet, so this can't go through tracing
+            eprintln!("Could not open the log file: {err}");
+            None
+        }
+    }
+}
@@ -1,100 +1,433 @@
+use std::time::Duration;
+
 use bevy::prelude::*;
 use rand::prelude::*;
+use rusty_dodger::math::{self, clamp_to_area, collide};
+
+mod adaptive;
+mod animated_number;
+mod appearance;
+mod arena;
+mod audio_cues;
+#[cfg(feature = "dev")]
+mod balance;
+mod bomb;
+mod bug_report;
+mod camera;
+mod challenge;
+mod changelog;
+mod collision;
+mod crash;
+mod danger_zone;
+mod data;
+mod density;
+mod despawn;
+mod difficulty;
+mod dying;
+mod elite;
+mod enemy;
+mod export;
+mod fallback;
+mod flash;
+mod floating_text;
+mod focus;
+mod golden;
+mod gravity_well;
+mod graze;
+mod heat;
+mod heatmap;
+mod hud;
+mod hud_layout;
+#[cfg(feature = "dev")]
+mod inspector;
+mod input_buffer;
+mod kill_cam;
+mod kiosk;
+mod lanes;
+mod leaderboard;
+mod loading;
+mod logging;
+mod menu;
+mod mixer;
+mod mutator;
+mod objective;
+mod overlay;
+mod patterns;
+mod party;
+mod pause;
+mod photo;
+mod power;
+mod practice;
+mod practice_overlay;
+mod projectile;
+mod prompts;
+mod recording;
+mod replay;
+mod profile;
+mod profiler;
+mod progression;
+mod reset;
+mod rng;
+mod scenery;
+mod segmented_bar;
+mod save;
+mod score;
+mod sets;
+mod settings;
+mod shapes;
+mod shield;
+mod snapshot;
+mod spatial_audio;
+mod splits;
+mod stats;
+mod stats_screen;
+mod stress;
+mod swarm;
+mod sync;
+mod text_input;
+mod text_style;
+mod threat;
+#[cfg(feature = "twitch")]
+mod twitch;
+mod watchdog;
+mod weather;
+mod window;
+mod zoom;
+
+use adaptive::{AdaptiveDifficulty, AdaptivePlugin};
+use animated_number::AnimatedNumberPlugin;
+use appearance::{AppearancePlugin, PlayerAppearance};
+use arena::{Arena, ArenaPlugin};
+use audio_cues::AudioCuePlugin;
+use bomb::BombPlugin;
+use bug_report::BugReportPlugin;
+use camera::CameraPlugin;
+use challenge::ChallengePlugin;
+use changelog::ChangelogPlugin;
+use collision::{COLLISION_GRACE, Collider, CollisionPlugin, Overlap, Sweep};
+use crash::CrashPlugin;
+use danger_zone::{DangerZone, DangerZonePlugin};
+use density::DensityPlugin;
+use despawn::{DespawnPlugin, DespawnQueue};
+use difficulty::Difficulty;
+use dying::{Dying, DyingPlugin};
+use elite::{ElitePlugin, Intangible};
+use enemy::{EnemyMotion, EnemyPlugin, RolledMotion, Spin};
+use fallback::FallbackPlugin;
+use floating_text::FloatingTextPlugin;
+use flash::FlashPlugin;
+use focus::{FocusActivated, FocusPlugin, MenuNav};
+use golden::GoldenPlugin;
+use gravity_well::GravityWellPlugin;
+use graze::GrazePlugin;
+use heat::HeatPlugin;
+use heatmap::HeatmapPlugin;
+use hud::HudPlugin;
+use hud_layout::HudLayoutPlugin;
+use input_buffer::{BufferedAction, InputBuffer, InputBufferPlugin};
+use kill_cam::{KILL_CAM_DURATION, KILL_CAM_TIME_SCALE, KillCam, KillCamPlugin};
+use kiosk::KioskPlugin;
+use lanes::{Lane, LaneMode, LanePlugin};
+use leaderboard::LeaderboardPlugin;
+use loading::LoadingPlugin;
+use menu::MenuPlugin;
+use mixer::MixerPlugin;
+use mutator::MutatorPlugin;
+use objective::ObjectivePlugin;
+use overlay::OverlayPlugin;
+use party::{Party, PartyPlugin};
+use pause::PausePlugin;
+use photo::PhotoPlugin;
+use power::PowerPlugin;
+use practice::PracticePlugin;
+use practice_overlay::PracticeOverlayPlugin;
+use projectile::{EnemyBullet, ProjectilePlugin};
+use prompts::{ButtonPrompt, PromptAction, PromptPlugin};
+use recording::RecordingPlugin;
+use replay::ReplayPlugin;
+use profile::ProfilePlugin;
+use profiler::{ProfilerPlugin, timed};
+use progression::ProgressionPlugin;
+use reset::{ResetPlugin, RunScoped, RunSetup};
+use rng::{GameRng, RngPlugin, RunSeed};
+use scenery::SceneryPlugin;
+use segmented_bar::SegmentedBarPlugin;
+use score::{Score, ScorePlugin};
+use sets::{GameSet, SetsPlugin};
+use settings::{Settings, SettingsPlugin};
+use shapes::ShapePlugin;
+use shield::{BubbleBroken, KnockedBack, ShieldBubble, ShieldPlugin, TemporaryShield};
+use snapshot::SnapshotPlugin;
+use spatial_audio::SpatialAudioPlugin;
+use splits::SplitsPlugin;
+use stats::{RunStats, StatsPlugin};
+use stats_screen::StatsScreenPlugin;
+use stress::{StressPlugin, StressTest};
+use swarm::SwarmPlugin;
+use sync::SyncPlugin;
+use text_style::{TextStyleLibrary, TextStylePlugin};
+use threat::ThreatPlugin;
+use watchdog::WatchdogPlugin;
+use weather::WeatherPlugin;
+use window::{GameWindowPlugin, WindowMetrics};
+use zoom::ZoomPlugin;
 
 // Game constants
 const PLAYER_SIZE: Vec2 = Vec2::new(50.0, 50.0);
-const PLAYER_SPEED: f32 = 500.0;
-const ENEMY_SIZE: Vec2 = Vec2::new(40.0, 40.0);
-const ENEMY_SPEED: f32 = 300.0;
-const ENEMY_SPAWN_TIME: f32 = 0.75; // Spawn a new enemy every 0.75 seconds
+const GAME_OVER_DRIFT: f32 = 0.3; // Fraction of their speed enemies keep behind the Game Over screen
+const GAME_OVER_FADE: f32 = 1.5; // How quickly they fade out there, per second
+// Player speed, enemy speeds and spawn timing come from the selected difficulty
 
 // --- Components ---
 // Components are data that you attach to entities.
 
-#[derive(Component)]
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+#[require(RunScoped)]
 struct Player;
 
-#[derive(Component)]
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+#[require(RunScoped)]
 struct Enemy;
 
-#[derive(Component)]
+#[derive(Component, Reflect)]
+#[reflect(Component)]
 struct Velocity(Vec2);
 
+#[derive(Component)]
+#[require(RunScoped)]
+struct GameOverScreen;
+
+/// An entry of the Game Over screen.
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+enum GameOverEntry {
+    Restart,
+    Menu,
+    SwitchProfile,
+}
+
 // --- Resources ---
 // Resources are global data that can be accessed by any system.
 
-#[derive(Resource)]
+#[derive(Resource, Reflect)]
+#[reflect(Resource)]
 struct EnemySpawnTimer(Timer);
 
+/// Where across the screen the next enemy will spawn, as a fraction of the
+/// width. Rolled a spawn ahead so the threat bar can show it coming.
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource)]
+struct UpcomingSpawn(Option<f32>);
+
 // Game state to control flow (e.g., Playing vs. GameOver)
 #[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, States)]
 enum GameState {
     #[default]
+    Loading,
+    ProfileSelect,
+    Menu,
+    Settings,
+    Stats,
+    Progression,
+    Changelog,
+    PartySetup,
     Playing,
     GameOver,
+    PartyResults,
 }
-fn collide(
-    pos_a: Vec3,
-    size_a: Vec2,
-    pos_b: Vec3,
-    size_b: Vec2,
-) -> bool {
-    let a_min = pos_a.truncate() - size_a / 2.0;
-    let a_max = pos_a.truncate() + size_a / 2.0;
-    let b_min = pos_b.truncate() - size_b / 2.0;
-    let b_max = pos_b.truncate() + size_b / 2.0;
 
-    a_min.x < b_max.x && a_max.x > b_min.x &&
-    a_min.y < b_max.y && a_max.y > b_min.y
+// Phases of a run: `Dying` plays the kill cam before switching to Game Over
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, SubStates)]
+#[source(GameState = GameState::Playing)]
+enum RunPhase {
+    #[default]
+    Alive,
+    Paused,
+    Dying,
 }
 
 fn main() {
-    App::new()
-        .add_plugins(DefaultPlugins)
+    let crash_info = crash::start_session();
+
+    let mut app = App::new();
+    app
+        .add_plugins(
+            DefaultPlugins
+                .set(WindowPlugin {
+                    primary_window: Some(
+                        replay::render_window().unwrap_or_else(window::primary_window),
+                    ),
+                    // Closing mid-run asks first, see `pause`
+                    close_when_requested: false,
+                    ..default()
+                })
+                .set(logging::log_plugin()),
+        )
+        // Asset loading
+        .add_plugins((FallbackPlugin, LoadingPlugin, TextStylePlugin))
+        // Gameplay
+        .add_plugins((
+            AdaptivePlugin,
+            BombPlugin,
+            CollisionPlugin,
+            DespawnPlugin,
+            DyingPlugin,
+            EnemyPlugin,
+            GrazePlugin,
+            HeatmapPlugin,
+            KillCamPlugin,
+            PracticePlugin,
+            RngPlugin,
+            ScorePlugin,
+            ShieldPlugin,
+            StatsPlugin,
+            SwarmPlugin,
+        ))
+        // Enemy variants, attacks and modes
+        .add_plugins((
+            ArenaPlugin,
+            ChallengePlugin,
+            DangerZonePlugin,
+            DensityPlugin,
+            ElitePlugin,
+            GoldenPlugin,
+            GravityWellPlugin,
+            LanePlugin,
+            MutatorPlugin,
+            ProjectilePlugin,
+            ShapePlugin,
+        ))
+        // Objectives and progression
+        .add_plugins((ObjectivePlugin, ProgressionPlugin))
+        // What's new
+        .add_plugins(ChangelogPlugin)
+        // Menus, HUD and saves
+        .add_plugins((
+            BugReportPlugin,
+            CrashPlugin,
+            FlashPlugin,
+            FocusPlugin,
+            GameWindowPlugin,
+            HudPlugin,
+            InputBufferPlugin,
+            MenuPlugin,
+            PausePlugin,
+            PowerPlugin,
+            ProfilePlugin,
+            ProfilerPlugin,
+            SettingsPlugin,
+            SnapshotPlugin,
+            StatsScreenPlugin,
+        ))
+        // Cloud saves and the online leaderboard
+        .add_plugins((LeaderboardPlugin, SyncPlugin))
+        // Run lifecycle
+        .add_plugins((
+            KioskPlugin,
+            PartyPlugin,
+            PhotoPlugin,
+            PracticeOverlayPlugin,
+            RecordingPlugin,
+            ReplayPlugin,
+            ResetPlugin,
+            SetsPlugin,
+            StressPlugin,
+            WatchdogPlugin,
+        ))
+        // Camera
+        .add_plugins((CameraPlugin, ZoomPlugin))
+        // HUD widgets
+        .add_plugins((
+            AnimatedNumberPlugin,
+            FloatingTextPlugin,
+            HudLayoutPlugin,
+            OverlayPlugin,
+            PromptPlugin,
+            SegmentedBarPlugin,
+            SplitsPlugin,
+            ThreatPlugin,
+        ))
+        // Backdrop and the ship's looks
+        .add_plugins((AppearancePlugin, HeatPlugin, SceneryPlugin, WeatherPlugin))
+        // Sound
+        .add_plugins((AudioCuePlugin, MixerPlugin, SpatialAudioPlugin))
         .init_state::<GameState>() // Correctly initialize the game state
+        .add_sub_state::<RunPhase>()
+        .insert_resource(crash_info)
+        .init_resource::<Difficulty>()
+        .init_resource::<UpcomingSpawn>()
         .insert_resource(EnemySpawnTimer(Timer::from_seconds(
-            ENEMY_SPAWN_TIME,
+            Difficulty::default().config.spawn.start_interval,
             TimerMode::Repeating,
         )))
-        .add_systems(Startup, setup_camera)
-        .add_systems(OnEnter(GameState::Playing), setup_game)
+        .add_systems(RunSetup, setup_game.after(mutator::start_run_mutators))
         .add_systems(
             Update,
             (
-                player_movement,
-                move_entities,
-                enemy_spawner,
-                check_collisions,
+                timed("player_movement", player_movement)
+                    .run_if(not(lanes::lanes_enabled).and(not(kiosk::demo_running)))
+                    .in_set(GameSet::Input),
+                timed("enemy_spawner", enemy_spawner)
+                    .run_if(swarm::swarm_idle.and(density::below_cap))
+                    .in_set(GameSet::Simulation),
+                timed("check_collisions", check_collisions).in_set(GameSet::Collision),
+                timed("despawn_offscreen_enemies", despawn_offscreen_enemies)
+                    .in_set(GameSet::Cleanup),
             )
-                .run_if(in_state(GameState::Playing)),
+                .run_if(in_state(RunPhase::Alive)),
         )
-        .add_systems(Update, restart_game.run_if(in_state(GameState::GameOver)))
-        .add_systems(OnEnter(GameState::GameOver), game_over_message)
-        .add_systems(OnExit(GameState::GameOver), despawn_all_entities)
-        .run();
-}
+        // Keep moving while dying so the kill cam plays out in slow motion
+        .add_systems(
+            Update,
+            timed("move_entities", move_entities)
+                .run_if(in_state(GameState::Playing))
+                .in_set(GameSet::Simulation),
+        )
+        .add_systems(
+            Update,
+            (restart_game, drift_remaining_enemies).run_if(in_state(GameState::GameOver)),
+        )
+        .add_systems(
+            OnEnter(GameState::GameOver),
+            (despawn_player, game_over_message, forget_restart_press),
+        );
+
+    #[cfg(feature = "dev")]
+    app.add_plugins((balance::BalancePlugin, inspector::InspectorPlugin));
+    #[cfg(feature = "twitch")]
+    app.add_plugins(twitch::TwitchPlugin);
+    app.run();
 
-/// System to set up the 2D camera
-fn setup_camera(mut commands: Commands) {
-    // Spawning a 2D camera is now done by just spawning the component
-    commands.spawn(Camera2d::default());
+    crash::end_session();
 }
 
 /// System to set up the initial game state (player)
-fn setup_game(mut commands: Commands) {
+fn setup_game(
+    mut commands: Commands,
+    difficulty: Res<Difficulty>,
+    appearance: Res<PlayerAppearance>,
+    mut spawn_timer: ResMut<EnemySpawnTimer>,
+    mut upcoming: ResMut<UpcomingSpawn>,
+) {
+    spawn_timer.0.reset();
+    upcoming.0 = None;
+    let player_size = PLAYER_SIZE * difficulty.config.player_scale;
+
     // Spawn player
     commands.spawn((
     Sprite {
-        color: Color::srgb(0.2, 0.4, 0.8),
+        color: appearance.ship,
         ..default()
     },
     Transform {
         translation: Vec3::new(0.0, -250.0, 0.0),
-        scale: PLAYER_SIZE.extend(1.0),
+        scale: player_size.extend(1.0),
         ..default()
     },
     Visibility::Visible,
     Player,
+    Collider::new(player_size),
     Velocity(Vec2::ZERO),
 ));
 }
@@ -102,6 +435,8 @@ fn setup_game(mut commands: Commands) {
 /// System to handle player input for movement
 fn player_movement(
     keyboard_input: Res<ButtonInput<KeyCode>>,
+    difficulty: Res<Difficulty>,
+    arena: Res<Arena>,
     mut query: Query<&mut Velocity, With<Player>>,
 ) {
     if let Ok(mut player_velocity) = query.single_mut() {
@@ -113,9 +448,21 @@ fn player_movement(
         if keyboard_input.pressed(KeyCode::ArrowRight) {
             direction.x += 1.0;
         }
+        if difficulty.config.mirrored_controls {
+            direction.x = -direction.x;
+        }
+        // Arena runs free up the whole screen
+        if arena.enabled {
+            if keyboard_input.pressed(KeyCode::ArrowUp) {
+                direction.y += 1.0;
+            }
+            if keyboard_input.pressed(KeyCode::ArrowDown) {
+                direction.y -= 1.0;
+            }
+        }
 
         // Normalize to ensure consistent speed in all directions and apply speed
-        player_velocity.0 = direction.normalize_or_zero() * PLAYER_SPEED;
+        player_velocity.0 = direction.normalize_or_zero() * difficulty.config.player_speed;
     }
 }
 
@@ -123,21 +470,47 @@ fn player_movement(
 fn move_entities(
     time: Res<Time>,
     mut query: Query<(&mut Transform, &Velocity, Option<&Player>)>,
-    window_query: Query<&Window>,
+    window: Res<WindowMetrics>,
 ) {
-    let window = window_query.single().expect("Window not found");
-    let half_player_width = PLAYER_SIZE.x / 2.0;
-    let x_min = -window.width() / 2.0 + half_player_width;
-    let x_max = window.width() / 2.0 - half_player_width;
-
     for (mut transform, velocity, maybe_player) in &mut query {
         // Apply velocity to move the entity using the updated Time API
         transform.translation += velocity.0.extend(0.0) * time.delta().as_secs_f32();
 
         // If the entity is the player, clamp its position to the screen bounds
         if maybe_player.is_some() {
-            transform.translation.x = transform.translation.x.clamp(x_min, x_max);
+            // Mutators can resize the player, so go by its actual size
+            let clamped = clamp_to_area(
+                transform.translation.truncate(),
+                transform.scale.truncate(),
+                window.size,
+            );
+            transform.translation = clamped.extend(transform.translation.z);
+        }
+    }
+}
+
+/// One enemy of a spawn: where it comes in, how it moves and its lane, if any.
+struct Placement {
+    x: f32,
+    motion: RolledMotion,
+    lane: Option<usize>,
+}
+
+impl Placement {
+    /// The same enemy reflected across the middle of the screen, or `None`
+    /// when it's `width` wide and so close to the middle the two would overlap.
+    fn mirrored(&self, width: f32) -> Option<Self> {
+        if self.x.abs() < width / 2.0 {
+            return None;
         }
+        Some(Self {
+            x: -self.x,
+            motion: RolledMotion {
+                velocity: Vec2::new(-self.motion.velocity.x, self.motion.velocity.y),
+                spin: -self.motion.spin,
+            },
+            lane: self.lane.map(|lane| lanes::LANE_COUNT - 1 - lane),
+        })
     }
 }
 
@@ -145,90 +518,383 @@ fn move_entities(
 fn enemy_spawner(
     mut commands: Commands,
     time: Res<Time>,
+    difficulty: Res<Difficulty>,
+    stats: Res<RunStats>,
+    settings: Res<Settings>,
+    adaptive: Res<AdaptiveDifficulty>,
+    enemy_motion: Res<EnemyMotion>,
+    lane_mode: Res<LaneMode>,
+    mut rng: ResMut<GameRng>,
     mut spawn_timer: ResMut<EnemySpawnTimer>,
-    window_query: Query<&Window>,
+    mut upcoming: ResMut<UpcomingSpawn>,
+    window: Res<WindowMetrics>,
+    player_query: Query<&Transform, With<Player>>,
 ) {
+    if upcoming.0.is_none() {
+        upcoming.0 = Some(rng.0.random());
+    }
+
+    // Spawns speed up over the run following the difficulty's curve
+    let mut interval = difficulty.config.spawn.interval(stats.elapsed());
+    if settings.adaptive_difficulty {
+        interval *= adaptive.spawn_scale(stats.elapsed());
+    }
+    spawn_timer.0.set_duration(Duration::from_secs_f32(interval));
+
     // Tick the timer
     spawn_timer.0.tick(time.delta());
 
-    // If the timer just finished, spawn an enemy
-    if spawn_timer.0.just_finished() {
-        let window = window_query.single().expect("Window not found");
-        let half_enemy_width = ENEMY_SIZE.x / 2.0;
-        let x_spawn_range =
-            -window.width() / 2.0 + half_enemy_width..window.width() / 2.0 - half_enemy_width;
+    // A long frame can finish the timer several times over; spawn every enemy
+    // the elapsed time warrants instead of just one
+    let count = spawn_timer.0.times_finished_this_tick();
+    if count == 0 {
+        return;
+    }
+    let overdue = spawn_timer.0.elapsed_secs();
+    let safety = &difficulty.config.spawn_safety;
+    let safe_from = player_query
+        .single()
+        .ok()
+        .filter(|_| settings.safe_spawns && stats.elapsed() < safety.seconds)
+        .map(|transform| transform.translation.truncate());
+
+    for index in 0..count {
+        let kind = difficulty.config.pick_kind(&mut rng.0);
+        let mut size = kind.size();
+        if lane_mode.enabled {
+            size = lanes::fit_to_lane(size, window.width());
+        }
+
+        let (x_min, x_max) = math::spawn_x_range(window.width(), size.x);
         let y_spawn_pos = window.height() / 2.0;
 
-        let mut rng = rand::rng();
-        let x_spawn = rng.random_range(x_spawn_range);
-        commands.spawn((
-            Sprite {
-                color: Color::srgb(0.9, 0.2, 0.2),
-                ..default()
-            },
-            Transform {
-                translation: Vec3::new(x_spawn, y_spawn_pos, 0.0),
-                scale: ENEMY_SIZE.extend(1.0),
-                ..default()
-            },
-            Visibility::Visible,
-            Enemy,
-            Velocity(Vec2::new(0.0, -ENEMY_SPEED)),
-        ));
+        // Roll a fraction of the width rather than a position, so a seed gives
+        // the same enemy stream whatever size the window is. Catch-up spawns
+        // each get their own slice of the width so they don't bunch up.
+        let roll = match upcoming.0.take() {
+            Some(roll) => roll,
+            None => rng.0.random(),
+        };
+        let fraction = (index as f32 + roll) / count as f32;
+        let mut x_spawn = math::spawn_x(window.width(), size.x, fraction);
+        let mut motion = enemy_motion.roll(kind, difficulty.config.enemy_speed, &mut rng.0);
+        // Lane enemies fall straight down the middle of their lane
+        let lane = lane_mode.enabled.then(|| lanes::lane_at_fraction(fraction));
+        if let Some(lane) = lane {
+            x_spawn = lanes::lane_center(lane, window.width());
+            motion.velocity.x = 0.0;
+            motion.spin = 0.0;
+        } else if let Some(player) = safe_from {
+            // How far the enemy drifts sideways on its way down to the player
+            let fall_time = (y_spawn_pos - player.y) / (-motion.velocity.y).max(f32::EPSILON);
+            let drift = motion.velocity.x * fall_time;
+            x_spawn = safety.keep_clear(x_spawn, size.x / 2.0, drift, player.x, x_min, x_max);
+        }
+
+        // Start each enemy where it would be had it spawned on time
+        let age = overdue + (count - 1 - index) as f32 * interval;
+        if y_spawn_pos + motion.velocity.y * age < -window.height() / 2.0 - size.y {
+            continue;
+        }
+
+        let modifiers = elite::roll(&mut rng.0, difficulty.config.elite_chance);
+        let placement = Placement {
+            x: x_spawn,
+            motion,
+            lane,
+        };
+        // Mirrored spawns send down a twin on the other side of the screen
+        let mirror = if difficulty.config.mirrored_spawns {
+            placement.mirrored(size.x)
+        } else {
+            None
+        };
+        for placement in std::iter::once(placement).chain(mirror) {
+            let translation = Vec3::new(placement.x, y_spawn_pos, 0.0)
+                + (placement.motion.velocity * age).extend(0.0);
+            let mut enemy = commands.spawn((
+                Sprite {
+                    color: kind.color(),
+                    ..default()
+                },
+                Transform {
+                    translation,
+                    scale: size.extend(1.0),
+                    ..default()
+                },
+                Visibility::Visible,
+                Enemy,
+                kind,
+                Collider::new(kind.shape().hitbox(size)),
+                Velocity(placement.motion.velocity),
+                Spin(placement.motion.spin),
+            ));
+            if let Some(lane) = placement.lane {
+                enemy.insert(Lane(lane));
+            }
+            elite::make_elite(&mut enemy, &modifiers);
+            projectile::arm(
+                &mut enemy,
+                kind,
+                &difficulty.config,
+                stats.elapsed(),
+                &mut rng.0,
+            );
+        }
+    }
+    upcoming.0 = Some(rng.0.random());
+}
+
+/// System to remove enemies once they have fallen past the bottom of the screen
+fn despawn_offscreen_enemies(
+    mut despawn_queue: ResMut<DespawnQueue>,
+    query: Query<(Entity, &Transform), With<Enemy>>,
+    window: Res<WindowMetrics>,
+) {
+    let bottom = -window.height() / 2.0;
+
+    for (entity, transform) in &query {
+        if transform.translation.y + transform.scale.y / 2.0 < bottom {
+            despawn_queue.push(entity);
+        }
     }
 }
 
-/// System to check for collisions between the player and enemies
+/// System to check for collisions between the player and enemies, their
+/// bullets or the danger zone
 fn check_collisions(
     mut commands: Commands,
-    player_query: Query<(&Transform, Entity), With<Player>>,
-    enemy_query: Query<&Transform, With<Enemy>>,
-    mut next_state: ResMut<NextState<GameState>>,
+    time: Res<Time>,
+    settings: Res<Settings>,
+    mut stress: ResMut<StressTest>,
+    mut player_query: Query<
+        (
+            Entity,
+            &Transform,
+            &Collider,
+            &mut Velocity,
+            Has<ShieldBubble>,
+            Option<&Lane>,
+        ),
+        (With<Player>, Without<TemporaryShield>),
+    >,
+    mut enemy_query: Query<
+        (
+            Entity,
+            &Transform,
+            &Collider,
+            Option<&Velocity>,
+            Option<&mut Overlap>,
+            Option<&Lane>,
+            Has<Enemy>,
+        ),
+        (
+            Or<(With<Enemy>, With<EnemyBullet>, With<DangerZone>)>,
+            Without<Dying>,
+            Without<Intangible>,
+            Without<KnockedBack>,
+        ),
+    >,
+    mut bubble_broken: EventWriter<BubbleBroken>,
+    mut next_state: ResMut<NextState<RunPhase>>,
 ) {
-    if let Ok((player_transform, player_entity)) = player_query.single() {
-        for enemy_transform in &enemy_query {
-            if collide(
-                player_transform.translation,
-                player_transform.scale.truncate(),
-                enemy_transform.translation,
-                enemy_transform.scale.truncate(),
-            ) {
-                // Collision detected! Despawn player and end game.
-                println!("Collision! Game Over.");
-                commands.entity(player_entity).despawn();
-                next_state.set(GameState::GameOver);
+    if let Ok((
+        player_entity,
+        player_transform,
+        player_collider,
+        mut player_velocity,
+        bubble,
+        player_lane,
+    )) = player_query.single_mut()
+    {
+        let delta = time.delta_secs();
+        let player_sweep = Sweep {
+            position: player_transform.translation,
+            displacement: player_velocity.0 * delta,
+            size: player_collider.effective_size(),
+            rotation: Quat::IDENTITY,
+        };
+        for (
+            enemy_entity,
+            enemy_transform,
+            enemy_collider,
+            enemy_velocity,
+            overlap,
+            enemy_lane,
+            is_enemy,
+        ) in &mut enemy_query
+        {
+            // In lane runs only things sharing a lane can touch, even mid-hop
+            let other_lane = matches!((player_lane, enemy_lane), (Some(a), Some(b)) if a != b);
+            let enemy_sweep = Sweep {
+                position: enemy_transform.translation,
+                displacement: enemy_velocity.map_or(Vec2::ZERO, |velocity| velocity.0 * delta),
+                size: enemy_collider.effective_size(),
+                rotation: enemy_transform.rotation,
+            };
+            let impact_time = if other_lane {
+                None
+            } else {
+                collision::time_of_impact(&player_sweep, &enemy_sweep)
+            };
+            let Some(impact_time) = impact_time else {
+                if overlap.is_some() {
+                    commands.entity(enemy_entity).remove::<Overlap>();
+                }
+                continue;
+            };
+
+            // The forgiving hitbox lets brief overlaps slide
+            if settings.forgiving_hitbox {
+                let overlapped_for = match overlap {
+                    Some(mut overlap) => {
+                        overlap.0 += time.delta_secs();
+                        overlap.0
+                    }
+                    None => {
+                        commands.entity(enemy_entity).insert(Overlap(0.0));
+                        0.0
+                    }
+                };
+                if overlapped_for < COLLISION_GRACE {
+                    continue;
+                }
+            }
+
+            // Halfway between the two where they first touched
+            let impact = ((player_sweep.position_at(impact_time)
+                + enemy_sweep.position_at(impact_time))
+                / 2.0)
+                .truncate();
+            // The bubble takes the hit instead, and bats enemies away
+            if bubble {
+                shield::pop_bubble(&mut commands, &mut bubble_broken, player_entity, impact);
+                if is_enemy {
+                    shield::knock_back(
+                        &mut commands,
+                        enemy_entity,
+                        enemy_velocity.map_or(Vec2::ZERO, |velocity| velocity.0),
+                        player_transform.translation.truncate(),
+                        enemy_transform.translation.truncate(),
+                    );
+                }
                 break;
             }
+            // Stress runs keep going, so every hit is still tested
+            if stress.enabled {
+                stress.hits += 1;
+                continue;
+            }
+
+            // Collision detected! Freeze the player and play the kill cam.
+            info!(x = impact.x, y = impact.y, "Collision! Game Over.");
+            player_velocity.0 = Vec2::ZERO;
+            // Time is slowed during the kill cam, so scale the animation to match it
+            commands
+                .entity(player_entity)
+                .insert(Dying::new(KILL_CAM_DURATION * KILL_CAM_TIME_SCALE));
+            commands.insert_resource(KillCam::new(impact));
+            next_state.set(RunPhase::Dying);
+            break;
         }
     }
 }
 
-/// System that shows the "Game Over" message using the modern Text2dBundle
-fn game_over_message(mut commands: Commands) {
-    commands.spawn((
-        Text("Game Over!\nPress 'R' to Restart".to_string()),
-        Transform::from_xyz(0.0, 0.0, 1.0),
-        GlobalTransform::default(),
-        Visibility::Visible,
-    ));
+/// System to remove the player once the kill cam has finished
+fn despawn_player(mut commands: Commands, query: Query<Entity, With<Player>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
 }
 
-/// System to restart the game
-fn restart_game(
-    keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut next_state: ResMut<NextState<GameState>>,
+/// System that shows the "Game Over" message and what to do next
+fn game_over_message(
+    mut commands: Commands,
+    score: Res<Score>,
+    seed: Res<RunSeed>,
+    styles: Res<TextStyleLibrary>,
+) {
+    let entries = [
+        ("Restart (R)", GameOverEntry::Restart),
+        ("Menu (M)", GameOverEntry::Menu),
+        ("Switch profile (P)", GameOverEntry::SwitchProfile),
+    ];
+    commands
+        .spawn((
+            Node {
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(4.0),
+                ..default()
+            },
+            GameOverScreen,
+        ))
+        .with_children(|parent| {
+            parent.spawn((Text::new("Game Over!"), styles.title.ui()));
+            parent.spawn((
+                Text(format!(
+                    "Score: {}\nSeed: {}\n",
+                    score.points(),
+                    rng::format_seed(seed.seed)
+                )),
+                styles.body.ui(),
+            ));
+            for (order, (label, entry)) in entries.into_iter().enumerate() {
+                parent.spawn((focus::entry(label, order), entry));
+            }
+            parent.spawn(ButtonPrompt::new(PromptAction::Confirm, "to select"));
+            parent.spawn(ButtonPrompt::new(PromptAction::Restart, "to restart"));
+        });
+}
+
+/// System that lets the enemies left on screen drift on slowly and fade out
+/// behind the Game Over screen
+fn drift_remaining_enemies(
+    time: Res<Time>,
+    mut query: Query<
+        (&mut Transform, &Velocity, &mut Sprite),
+        (Or<(With<Enemy>, With<EnemyBullet>)>, Without<Dying>),
+    >,
 ) {
-    if keyboard_input.just_pressed(KeyCode::KeyR) {
-        next_state.set(GameState::Playing);
+    let delta = time.delta_secs();
+    let fade = (-GAME_OVER_FADE * delta).exp();
+    for (mut transform, velocity, mut sprite) in &mut query {
+        transform.translation += (velocity.0 * GAME_OVER_DRIFT * delta).extend(0.0);
+        let alpha = sprite.color.alpha();
+        sprite.color.set_alpha(alpha * fade);
     }
 }
 
-/// System to despawn all entities (enemies and text) when restarting
-fn despawn_all_entities(
-    mut commands: Commands,
-    query: Query<Entity, Or<(With<Enemy>, With<Text>)>>,
+/// System that drops a restart pressed before Game Over, so a key held
+/// through the kill cam doesn't skip the results
+fn forget_restart_press(mut input_buffer: ResMut<InputBuffer>) {
+    input_buffer.clear(BufferedAction::Restart);
+}
+
+/// System to restart the game or leave it from the Game Over screen
+fn restart_game(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut input_buffer: ResMut<InputBuffer>,
+    mut activated: EventReader<FocusActivated>,
+    nav: Res<MenuNav>,
+    entry_query: Query<&GameOverEntry>,
+    party: Option<Res<Party>>,
+    mut next_state: ResMut<NextState<GameState>>,
 ) {
-    for entity in &query {
-        commands.entity(entity).despawn();
+    // In a party, restarting hands over to the next player
+    let restart = party::after_game_over(party.as_deref());
+    // Buffered, but only presses made on this screen count
+    if input_buffer.consume(BufferedAction::Restart) {
+        next_state.set(restart);
+    }
+    if keyboard_input.just_pressed(KeyCode::KeyM) || nav.back {
+        next_state.set(GameState::Menu);
+    }
+    for event in activated.read() {
+        match entry_query.get(event.0) {
+            Ok(GameOverEntry::Restart) => next_state.set(restart),
+            Ok(GameOverEntry::Menu) => next_state.set(GameState::Menu),
+            Ok(GameOverEntry::SwitchProfile) => next_state.set(GameState::ProfileSelect),
+            Err(_) => {}
+        }
     }
 }
\ No newline at end of file
@@ -0,0 +1,352 @@
+use bevy::math::{Quat, Vec2, Vec3};
+
+/// Separating-axis test between an axis-aligned box `a` and a box `b` that
+/// is rotated around Z, for enemies that spin. Boxes that only touch don't
+/// collide, and neither does a box with a zero or negative size.
+pub fn collide(pos_a: Vec3, size_a: Vec2, pos_b: Vec3, size_b: Vec2, rotation_b: Quat) -> bool {
+    if size_a.min_element() <= 0.0 || size_b.min_element() <= 0.0 {
+        return false;
+    }
+    let u = (rotation_b * Vec3::X).truncate();
+    let v = (rotation_b * Vec3::Y).truncate();
+    let offset = (pos_b - pos_a).truncate();
+    let half_a = size_a / 2.0;
+    let half_b = size_b / 2.0;
+
+    [Vec2::X, Vec2::Y, u, v].into_iter().all(|axis| {
+        let reach_a = half_a.x * axis.x.abs() + half_a.y * axis.y.abs();
+        let reach_b = half_b.x * axis.dot(u).abs() + half_b.y * axis.dot(v).abs();
+        offset.dot(axis).abs() < reach_a + reach_b
+    })
+}
+
+/// Keeps a box of `size` centred at `position` inside an `area` centred on
+/// the origin. A box bigger than the area is held at its centre instead, and
+/// a negative size counts as zero.
+pub fn clamp_to_area(position: Vec2, size: Vec2, area: Vec2) -> Vec2 {
+    let max = ((area - size.max(Vec2::ZERO)) / 2.0).max(Vec2::ZERO);
+    position.clamp(-max, max)
+}
+
+/// The lowest and highest centre an object `width` wide can spawn at and
+/// still be fully inside an `area_width` wide area centred on the origin.
+/// The range never inverts: something wider than the area can only spawn in
+/// the middle.
+pub fn spawn_x_range(area_width: f32, width: f32) -> (f32, f32) {
+    let half = ((area_width - width) / 2.0).max(0.0);
+    (-half, half)
+}
+
+/// The spawn centre a rolled `fraction` of the way across the spawn range
+/// lands on.
+pub fn spawn_x(area_width: f32, width: f32, fraction: f32) -> f32 {
+    let (min, max) = spawn_x_range(area_width, width);
+    min + (max - min) * fraction.clamp(0.0, 1.0)
+}
+
+/// Eases from `start` to `end` over the first `duration` seconds, holding
+/// `end` afterwards. A zero duration jumps to `end` as soon as any time has
+/// passed.
+pub fn ramp(start: f32, end: f32, elapsed: f32, duration: f32) -> f32 {
+    let t = (elapsed / duration.max(f32::EPSILON)).clamp(0.0, 1.0);
+    start + (end - start) * t
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_4;
+
+    use proptest::prelude::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+
+    const UNIT: Vec2 = Vec2::splat(10.0);
+
+    fn at(x: f32, y: f32) -> Vec3 {
+        Vec3::new(x, y, 0.0)
+    }
+
+    #[test]
+    fn overlapping_boxes_collide() {
+        assert!(collide(
+            at(0.0, 0.0),
+            UNIT,
+            at(9.0, 0.0),
+            UNIT,
+            Quat::IDENTITY
+        ));
+        assert!(collide(
+            at(0.0, 0.0),
+            UNIT,
+            at(-9.0, -9.0),
+            UNIT,
+            Quat::IDENTITY
+        ));
+    }
+
+    #[test]
+    fn apart_boxes_miss() {
+        assert!(!collide(
+            at(0.0, 0.0),
+            UNIT,
+            at(11.0, 0.0),
+            UNIT,
+            Quat::IDENTITY
+        ));
+        assert!(!collide(
+            at(0.0, 0.0),
+            UNIT,
+            at(0.0, -20.0),
+            UNIT,
+            Quat::IDENTITY
+        ));
+    }
+
+    #[test]
+    fn touching_edges_do_not_collide() {
+        assert!(!collide(
+            at(0.0, 0.0),
+            UNIT,
+            at(10.0, 0.0),
+            UNIT,
+            Quat::IDENTITY
+        ));
+        assert!(!collide(
+            at(0.0, 0.0),
+            UNIT,
+            at(0.0, 10.0),
+            UNIT,
+            Quat::IDENTITY
+        ));
+        assert!(!collide(
+            at(0.0, 0.0),
+            UNIT,
+            at(10.0, 10.0),
+            UNIT,
+            Quat::IDENTITY
+        ));
+    }
+
+    #[test]
+    fn contained_boxes_collide_both_ways() {
+        let big = Vec2::splat(100.0);
+        assert!(collide(
+            at(0.0, 0.0),
+            big,
+            at(20.0, -20.0),
+            UNIT,
+            Quat::IDENTITY
+        ));
+        assert!(collide(
+            at(20.0, -20.0),
+            UNIT,
+            at(0.0, 0.0),
+            big,
+            Quat::IDENTITY
+        ));
+        assert!(collide(
+            at(5.0, 5.0),
+            UNIT,
+            at(5.0, 5.0),
+            UNIT,
+            Quat::IDENTITY
+        ));
+    }
+
+    #[test]
+    fn collision_is_symmetric_for_unrotated_boxes() {
+        let wide = Vec2::new(30.0, 4.0);
+        for x in [-20.0, -16.9, -17.0, 0.0, 16.9, 17.0, 20.0] {
+            assert_eq!(
+                collide(at(0.0, 0.0), wide, at(x, 0.0), UNIT, Quat::IDENTITY),
+                collide(at(x, 0.0), UNIT, at(0.0, 0.0), wide, Quat::IDENTITY),
+                "x = {x}"
+            );
+        }
+    }
+
+    #[test]
+    fn rotated_boxes_reach_further_along_their_diagonal() {
+        let rotated = Quat::from_rotation_z(FRAC_PI_4);
+        // A box turned 45 degrees reaches 5 * sqrt(2) along X
+        assert!(collide(at(0.0, 0.0), UNIT, at(11.5, 0.0), UNIT, rotated));
+        assert!(!collide(
+            at(0.0, 0.0),
+            UNIT,
+            at(11.5, 0.0),
+            UNIT,
+            Quat::IDENTITY
+        ));
+        assert!(!collide(at(0.0, 0.0), UNIT, at(12.5, 0.0), UNIT, rotated));
+    }
+
+    #[test]
+    fn rotated_boxes_miss_in_the_corner_gap() {
+        // Both boxes' bounding boxes overlap, but the turned one's corner
+        // points away from the other box
+        let rotated = Quat::from_rotation_z(FRAC_PI_4);
+        assert!(!collide(at(0.0, 0.0), UNIT, at(11.0, 11.0), UNIT, rotated));
+    }
+
+    #[test]
+    fn zero_and_negative_sizes_never_collide() {
+        assert!(!collide(
+            at(0.0, 0.0),
+            Vec2::ZERO,
+            at(0.0, 0.0),
+            Vec2::ZERO,
+            Quat::IDENTITY
+        ));
+        assert!(!collide(
+            at(0.0, 0.0),
+            -UNIT,
+            at(0.0, 0.0),
+            -UNIT,
+            Quat::IDENTITY
+        ));
+        assert!(!collide(
+            at(0.0, 0.0),
+            -UNIT,
+            at(1.0, 0.0),
+            UNIT,
+            Quat::IDENTITY
+        ));
+        assert!(!collide(
+            at(0.0, 0.0),
+            UNIT,
+            at(1.0, 0.0),
+            -UNIT,
+            Quat::IDENTITY
+        ));
+        // Even when the other box is big enough to make up for it
+        assert!(!collide(
+            at(0.0, 0.0),
+            -UNIT,
+            at(0.0, 0.0),
+            UNIT * 3.0,
+            Quat::IDENTITY
+        ));
+        assert!(!collide(
+            at(0.0, 0.0),
+            UNIT * 3.0,
+            at(0.0, 0.0),
+            Vec2::new(10.0, -1.0),
+            Quat::from_rotation_z(FRAC_PI_4)
+        ));
+    }
+
+    #[test]
+    fn clamping_leaves_boxes_inside_alone() {
+        let area = Vec2::new(100.0, 50.0);
+        let position = Vec2::new(20.0, -10.0);
+        assert_eq!(clamp_to_area(position, UNIT, area), position);
+    }
+
+    #[test]
+    fn clamping_stops_boxes_flush_with_the_edges() {
+        let area = Vec2::new(100.0, 50.0);
+        assert_eq!(
+            clamp_to_area(Vec2::new(80.0, -40.0), UNIT, area),
+            Vec2::new(45.0, -20.0)
+        );
+        assert_eq!(
+            clamp_to_area(Vec2::new(-45.0, 20.0), UNIT, area),
+            Vec2::new(-45.0, 20.0)
+        );
+    }
+
+    #[test]
+    fn boxes_bigger_than_the_area_stay_centred() {
+        let area = Vec2::new(100.0, 50.0);
+        assert_eq!(
+            clamp_to_area(Vec2::new(30.0, 30.0), Vec2::new(120.0, 10.0), area),
+            Vec2::new(0.0, 20.0)
+        );
+        assert_eq!(
+            clamp_to_area(Vec2::new(30.0, 30.0), Vec2::splat(200.0), area),
+            Vec2::ZERO
+        );
+    }
+
+    #[test]
+    fn clamping_a_negative_size_keeps_the_centre_inside() {
+        let area = Vec2::new(100.0, 50.0);
+        let clamped = clamp_to_area(Vec2::new(500.0, -500.0), -UNIT, area);
+        assert_eq!(clamped, Vec2::new(50.0, -25.0));
+    }
+
+    #[test]
+    fn ramp_runs_from_start_to_end() {
+        assert_eq!(ramp(2.0, 0.5, 0.0, 60.0), 2.0);
+        assert_eq!(ramp(2.0, 0.5, 30.0, 60.0), 1.25);
+        assert_eq!(ramp(2.0, 0.5, 60.0, 60.0), 0.5);
+    }
+
+    #[test]
+    fn ramp_holds_outside_its_duration() {
+        assert_eq!(ramp(2.0, 0.5, -5.0, 60.0), 2.0);
+        assert_eq!(ramp(2.0, 0.5, 600.0, 60.0), 0.5);
+    }
+
+    #[test]
+    fn zero_duration_ramps_jump_to_the_end() {
+        assert_eq!(ramp(2.0, 0.5, 0.0, 0.0), 2.0);
+        assert_eq!(ramp(2.0, 0.5, 1.0, 0.0), 0.5);
+        assert_eq!(ramp(2.0, 0.5, 1.0, -3.0), 0.5);
+    }
+
+    proptest! {
+        #[test]
+        fn spawn_ranges_never_invert(area_width in 0.0f32..4000.0, width in 0.0f32..1000.0) {
+            let (min, max) = spawn_x_range(area_width, width);
+            prop_assert!(min <= max, "{min} > {max}");
+        }
+
+        #[test]
+        fn rolling_in_a_spawn_range_never_panics(
+            area_width in 0.0f32..4000.0,
+            width in 0.0f32..1000.0,
+            seed in any::<u64>(),
+        ) {
+            let (min, max) = spawn_x_range(area_width, width);
+            let x = StdRng::seed_from_u64(seed).random_range(min..=max);
+            prop_assert!((min..=max).contains(&x));
+        }
+
+        #[test]
+        fn spawns_stay_fully_on_screen(
+            area_width in 1.0f32..4000.0,
+            width in 0.0f32..1000.0,
+            fraction in 0.0f32..=1.0,
+        ) {
+            prop_assume!(width <= area_width);
+            let x = spawn_x(area_width, width, fraction);
+            let tolerance = area_width * 1e-5;
+            let (left, right) = (x - width / 2.0, x + width / 2.0);
+            prop_assert!(left >= -area_width / 2.0 - tolerance, "left edge at {left}");
+            prop_assert!(right <= area_width / 2.0 + tolerance, "right edge at {right}");
+        }
+
+        #[test]
+        fn oversized_spawns_are_centred(
+            area_width in 0.0f32..1000.0,
+            extra in 0.0f32..1000.0,
+            fraction in any::<f32>(),
+        ) {
+            prop_assert_eq!(spawn_x(area_width, area_width + extra, fraction), 0.0);
+        }
+
+        #[test]
+        fn spawns_move_across_the_range_in_order(
+            area_width in 0.0f32..4000.0,
+            width in 0.0f32..1000.0,
+            a in 0.0f32..=1.0,
+            b in 0.0f32..=1.0,
+        ) {
+            let (low, high) = if a <= b { (a, b) } else { (b, a) };
+            prop_assert!(spawn_x(area_width, width, low) <= spawn_x(area_width, width, high));
+        }
+    }
+}
@@ -0,0 +1,482 @@
+use bevy::ecs::system::SystemParam;
+use bevy::input::keyboard::KeyboardInput;
+use bevy::prelude::*;
+
+use crate::GameState;
+use crate::arena::Arena;
+use crate::challenge::{self, Challenge};
+use crate::changelog;
+use crate::crash::{self, CrashInfo};
+use crate::difficulty::Difficulty;
+use crate::focus::{self, FocusActivated, MenuNav, TextEntryActive};
+use crate::input_buffer::{BufferedAction, InputBuffer};
+use crate::kiosk::Kiosk;
+use crate::lanes::{LANE_COUNT, LaneMode};
+use crate::mutator::{self, Mutator, MutatorSelection};
+use crate::pause::{self, ResumeRequested};
+use crate::practice::Practice;
+use crate::profile::{ActiveProfile, Progress};
+use crate::progression::Unlocks;
+use crate::rng::{self, RunSeed};
+use crate::score::HighScores;
+use crate::settings::Settings;
+use crate::text_input::{self, TextInputAction};
+use crate::weather::WeatherChoice;
+
+const MAX_SEED_LEN: usize = 16; // Hex digits in a u64
+
+// --- Resources ---
+
+/// The menu's text fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MenuField {
+    Seed,
+    Challenge,
+}
+
+/// Field being typed into on the menu, and what's typed so far.
+#[derive(Resource, Default)]
+struct MenuTextEntry(Option<(MenuField, String)>);
+
+/// Everything the main menu can do, from its entries or their shortcut keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MenuAction {
+    Play,
+    Continue,
+    NextDifficulty,
+    PreviousDifficulty,
+    TogglePractice,
+    CycleCheckpoint,
+    ToggleArena,
+    ToggleLanes,
+    ToggleWeekly,
+    CycleWeather,
+    Party,
+    Settings,
+    Stats,
+    Progression,
+    WhatsNew,
+    Profiles,
+}
+
+// --- Components ---
+
+#[derive(Component)]
+struct MenuScreen;
+
+#[derive(Component)]
+struct MenuText;
+
+#[derive(Component)]
+struct MenuEntry(MenuAction);
+
+/// What the next run will be, as picked on the menu.
+#[derive(SystemParam)]
+struct RunChoices<'w> {
+    difficulty: ResMut<'w, Difficulty>,
+    seed: ResMut<'w, RunSeed>,
+    practice: ResMut<'w, Practice>,
+    arena: ResMut<'w, Arena>,
+    lane_mode: ResMut<'w, LaneMode>,
+    mutators: ResMut<'w, MutatorSelection>,
+    weather: ResMut<'w, WeatherChoice>,
+}
+
+pub struct MenuPlugin;
+
+impl Plugin for MenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MenuTextEntry>()
+            .add_systems(OnEnter(GameState::Menu), spawn_menu)
+            .add_systems(
+                Update,
+                (menu_input, update_menu_text)
+                    .chain()
+                    .run_if(in_state(GameState::Menu)),
+            )
+            .add_systems(OnExit(GameState::Menu), despawn_menu);
+    }
+}
+
+/// System to spawn the main menu text and entries
+fn spawn_menu(
+    mut commands: Commands,
+    profile: Res<ActiveProfile>,
+    progress: Res<Progress>,
+    kiosk: Res<Kiosk>,
+) {
+    let mut entries = vec![("Play", MenuAction::Play)];
+    if pause::has_suspended_run(&profile) {
+        entries.push(("Continue saved run", MenuAction::Continue));
+    }
+    entries.extend([
+        ("Change difficulty", MenuAction::NextDifficulty),
+        ("Practice mode", MenuAction::TogglePractice),
+        ("Practice checkpoint", MenuAction::CycleCheckpoint),
+        ("Arena mode", MenuAction::ToggleArena),
+        ("Lane mode", MenuAction::ToggleLanes),
+        ("Weekly mutators", MenuAction::ToggleWeekly),
+        ("Weather", MenuAction::CycleWeather),
+        ("Party mode", MenuAction::Party),
+        ("Settings", MenuAction::Settings),
+        ("Stats", MenuAction::Stats),
+        ("Progression", MenuAction::Progression),
+        (
+            if changelog::has_news(&progress) {
+                "What's new [NEW]"
+            } else {
+                "What's new"
+            },
+            MenuAction::WhatsNew,
+        ),
+        ("Profiles", MenuAction::Profiles),
+    ]);
+    // Visitors at a kiosk can't change how the machine is set up
+    if kiosk.enabled {
+        entries
+            .retain(|&(_, action)| !matches!(action, MenuAction::Settings | MenuAction::Profiles));
+    }
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(40.0),
+                left: Val::Px(40.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(4.0),
+                ..default()
+            },
+            MenuScreen,
+        ))
+        .with_children(|parent| {
+            parent.spawn((Text::default(), MenuText));
+            for (order, (label, action)) in entries.into_iter().enumerate() {
+                parent.spawn((focus::entry(label, order), MenuEntry(action)));
+            }
+        });
+}
+
+/// System to pick a difficulty and seed, and start the run
+fn menu_input(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut activated: EventReader<FocusActivated>,
+    nav: Res<MenuNav>,
+    mut input_buffer: ResMut<InputBuffer>,
+    choices: RunChoices,
+    mut text_entry: ResMut<MenuTextEntry>,
+    profile: Res<ActiveProfile>,
+    unlocks: Res<Unlocks>,
+    kiosk: Res<Kiosk>,
+    entry_query: Query<&MenuEntry>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let RunChoices {
+        mut difficulty,
+        mut seed,
+        mut practice,
+        mut arena,
+        mut lane_mode,
+        mut mutators,
+        mut weather,
+    } = choices;
+    let events: Vec<KeyboardInput> = keyboard_events.read().cloned().collect();
+    if let Some((field, buffer)) = text_entry.bypass_change_detection().0.as_mut() {
+        let field = *field;
+        let (max_len, accept): (usize, fn(char) -> bool) = match field {
+            MenuField::Seed => (MAX_SEED_LEN, rng::is_seed_char),
+            MenuField::Challenge => (challenge::MAX_CODE_LEN, challenge::is_code_char),
+        };
+        let mut edited = false;
+        for event in &events {
+            match text_input::apply_key(buffer, event, max_len, accept) {
+                TextInputAction::Submit => {
+                    match field {
+                        // An empty seed goes back to random runs
+                        MenuField::Seed => seed.fixed = rng::parse_seed(buffer),
+                        MenuField::Challenge if buffer.trim().is_empty() => {}
+                        MenuField::Challenge => match Challenge::parse(buffer) {
+                            Some(challenge) => challenge.apply(
+                                &mut difficulty,
+                                &mut arena,
+                                &mut lane_mode,
+                                &mut mutators,
+                                &mut seed,
+                            ),
+                            // A mistyped code stays open to be fixed
+                            None => continue,
+                        },
+                    }
+                    input_buffer.clear(BufferedAction::Confirm);
+                    text_entry.0 = None;
+                    commands.remove_resource::<TextEntryActive>();
+                    return;
+                }
+                TextInputAction::Cancel => {
+                    text_entry.0 = None;
+                    commands.remove_resource::<TextEntryActive>();
+                    return;
+                }
+                TextInputAction::Edited => edited = true,
+                TextInputAction::None => {}
+            }
+        }
+        if edited {
+            text_entry.set_changed();
+        }
+        return;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::KeyE) {
+        let buffer = seed.fixed.map(rng::format_seed).unwrap_or_default();
+        text_entry.0 = Some((MenuField::Seed, buffer));
+        commands.insert_resource(TextEntryActive);
+        return;
+    }
+    if keyboard_input.just_pressed(KeyCode::KeyK) {
+        text_entry.0 = Some((MenuField::Challenge, String::new()));
+        commands.insert_resource(TextEntryActive);
+        return;
+    }
+    let mutator_keys = [
+        KeyCode::Digit1,
+        KeyCode::Digit2,
+        KeyCode::Digit3,
+        KeyCode::Digit4,
+        KeyCode::Digit5,
+    ];
+    for (key, mutator) in mutator_keys.into_iter().zip(Mutator::ALL) {
+        // Locked mutators can still be dropped, say after loading a challenge
+        let allowed = mutators.is_chosen(mutator) || unlocks.mutator(mutator);
+        if keyboard_input.just_pressed(key) && allowed {
+            mutators.weekly = false;
+            mutators.toggle(mutator);
+        }
+    }
+
+    let shortcuts = [
+        (KeyCode::KeyC, MenuAction::Continue),
+        (KeyCode::KeyT, MenuAction::TogglePractice),
+        (KeyCode::KeyO, MenuAction::CycleCheckpoint),
+        (KeyCode::KeyA, MenuAction::ToggleArena),
+        (KeyCode::KeyL, MenuAction::ToggleLanes),
+        (KeyCode::KeyW, MenuAction::ToggleWeekly),
+        (KeyCode::KeyH, MenuAction::CycleWeather),
+        (KeyCode::KeyG, MenuAction::Party),
+        (KeyCode::KeyS, MenuAction::Settings),
+        (KeyCode::KeyI, MenuAction::Stats),
+        (KeyCode::KeyX, MenuAction::Progression),
+        (KeyCode::KeyN, MenuAction::WhatsNew),
+        (KeyCode::KeyP, MenuAction::Profiles),
+    ];
+    let mut actions: Vec<MenuAction> = shortcuts
+        .into_iter()
+        .filter(|&(key, _)| keyboard_input.just_pressed(key))
+        .map(|(_, action)| action)
+        .collect();
+    if nav.left {
+        actions.push(MenuAction::PreviousDifficulty);
+    }
+    if nav.right {
+        actions.push(MenuAction::NextDifficulty);
+    }
+    actions.extend(
+        activated
+            .read()
+            .filter_map(|event| entry_query.get(event.0).ok())
+            .map(|entry| entry.0),
+    );
+
+    for action in actions {
+        match action {
+            MenuAction::Play => next_state.set(GameState::Playing),
+            MenuAction::Continue => {
+                if pause::has_suspended_run(&profile) {
+                    practice.enabled = false;
+                    commands.insert_resource(ResumeRequested);
+                    next_state.set(GameState::Playing);
+                }
+            }
+            MenuAction::NextDifficulty => {
+                *difficulty = Difficulty::load(difficulty.preset.next());
+            }
+            MenuAction::PreviousDifficulty => {
+                *difficulty = Difficulty::load(difficulty.preset.previous());
+            }
+            MenuAction::TogglePractice => practice.enabled = !practice.enabled,
+            // Checkpoints only make sense without a score to count
+            MenuAction::CycleCheckpoint => {
+                practice.next_checkpoint();
+                practice.enabled = true;
+            }
+            // Arena and lanes both change how the player moves, so only one
+            // can be on at a time
+            MenuAction::ToggleArena => {
+                arena.enabled = !arena.enabled;
+                if arena.enabled {
+                    lane_mode.enabled = false;
+                }
+            }
+            MenuAction::ToggleLanes => {
+                lane_mode.enabled = !lane_mode.enabled;
+                if lane_mode.enabled {
+                    arena.enabled = false;
+                }
+            }
+            MenuAction::ToggleWeekly => mutators.weekly = !mutators.weekly,
+            MenuAction::CycleWeather => *weather = weather.next(),
+            MenuAction::Party => next_state.set(GameState::PartySetup),
+            MenuAction::Settings if !kiosk.enabled => next_state.set(GameState::Settings),
+            MenuAction::Settings => {}
+            MenuAction::Stats => next_state.set(GameState::Stats),
+            MenuAction::Progression => next_state.set(GameState::Progression),
+            MenuAction::WhatsNew => next_state.set(GameState::Changelog),
+            MenuAction::Profiles if !kiosk.enabled => next_state.set(GameState::ProfileSelect),
+            MenuAction::Profiles => {}
+        }
+    }
+}
+
+/// System that shows the selected difficulty and the profile's leaderboard
+fn update_menu_text(
+    difficulty: Res<Difficulty>,
+    profile: Res<ActiveProfile>,
+    high_scores: Res<HighScores>,
+    seed: Res<RunSeed>,
+    text_entry: Res<MenuTextEntry>,
+    practice: Res<Practice>,
+    arena: Res<Arena>,
+    lane_mode: Res<LaneMode>,
+    mutators: Res<MutatorSelection>,
+    weather: Res<WeatherChoice>,
+    settings: Res<Settings>,
+    crash_info: Res<CrashInfo>,
+    mut query: Query<&mut Text, With<MenuText>>,
+) {
+    let Ok(mut text) = query.single_mut() else {
+        return;
+    };
+    let changed = difficulty.is_changed()
+        || profile.is_changed()
+        || high_scores.is_changed()
+        || seed.is_changed()
+        || text_entry.is_changed()
+        || practice.is_changed()
+        || arena.is_changed()
+        || lane_mode.is_changed()
+        || mutators.is_changed()
+        || weather.is_changed()
+        || settings.is_changed();
+    if !changed && !text.0.is_empty() {
+        return;
+    }
+
+    let seed_line = match (&text_entry.0, seed.fixed) {
+        (Some((MenuField::Seed, buffer)), _) => {
+            format!("Seed: {buffer}_  (Enter to confirm, empty for random)")
+        }
+        (_, Some(fixed)) => format!("Seed: {}", rng::format_seed(fixed)),
+        (_, None) => "Seed: random".to_string(),
+    };
+    let challenge_line = match &text_entry.0 {
+        Some((MenuField::Challenge, buffer)) if Challenge::parse(buffer).is_some() => {
+            format!("Challenge code: {buffer}_  (Enter to load)")
+        }
+        Some((MenuField::Challenge, buffer)) => {
+            format!("Challenge code: {buffer}_  (Esc or empty Enter to cancel)")
+        }
+        _ => match Challenge::from_menu(&difficulty, &arena, &lane_mode, &mutators, &seed) {
+            Some(challenge) => format!("Challenge code: {}", challenge.code()),
+            None => "Challenge code: set a seed to get one".to_string(),
+        },
+    };
+
+    let mut lines = vec![
+        format!("Rusty Dodger v{}", changelog::VERSION),
+        format!("Profile: {}", profile.name),
+        String::new(),
+        format!("Difficulty: < {} >", difficulty.preset.name()),
+        seed_line,
+        challenge_line,
+        format!(
+            "Mode: {}",
+            if practice.enabled {
+                "Practice (F5 quicksave, F8 quickload, not recorded)"
+            } else if settings.adaptive_difficulty {
+                "Adaptive difficulty (not on the leaderboard)"
+            } else {
+                "Normal"
+            }
+        ),
+        format!("Practice checkpoint: {}", practice.checkpoint_name()),
+        format!(
+            "Arena: {}",
+            if arena.enabled {
+                "On (Up/Down move too, higher scores faster)"
+            } else {
+                "Off"
+            }
+        ),
+        if lane_mode.enabled {
+            format!("Lanes: On ({LANE_COUNT} lanes, Left/Right to hop)")
+        } else {
+            "Lanes: Off".to_string()
+        },
+        mutator_line(&mutators),
+        format!("Weather: {}", weather.name()),
+        String::new(),
+        "High Scores".to_string(),
+    ];
+    if crash::safe_mode() {
+        let reason = if crash_info.last_session_crashed {
+            "the last session crashed, see the crashes folder for its log"
+        } else {
+            "started with --safe-mode"
+        };
+        lines.insert(
+            2,
+            format!("SAFE MODE ({reason}): default settings, built-in data"),
+        );
+    }
+    if high_scores.entries.is_empty() {
+        lines.push("  No runs yet".to_string());
+    }
+    for (rank, entry) in high_scores.entries.iter().enumerate() {
+        let mut line = format!(
+            "  {:>2}. {:>6}  {}",
+            rank + 1,
+            entry.score,
+            entry.difficulty.name()
+        );
+        if let Some(names) = mutator::describe(&entry.mutators) {
+            line.push_str(&format!("  [{names}]"));
+        }
+        lines.push(line);
+    }
+    lines.push(String::new());
+    lines.push(
+        "Up/Down: Select   Enter: Confirm   Left/Right: Difficulty   E: Seed   K: Challenge code   1-5: Toggle mutators"
+            .to_string(),
+    );
+
+    text.0 = lines.join("\n");
+}
+
+/// Describes the mutators the next run will use.
+fn mutator_line(mutators: &MutatorSelection) -> String {
+    let names = mutator::describe(&mutators.stack()).unwrap_or_else(|| "None".to_string());
+    if mutators.weekly {
+        format!("Mutators: Weekly #{} ({names})", mutator::current_week())
+    } else {
+        format!("Mutators: {names}")
+    }
+}
+
+/// System to remove the main menu
+fn despawn_menu(mut commands: Commands, query: Query<Entity, With<MenuScreen>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
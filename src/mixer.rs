@@ -0,0 +1,248 @@
+use bevy::audio::{AudioPlaySet, AudioSinkPlayback, Volume};
+use bevy::prelude::*;
+
+use crate::GameState;
+use crate::bomb::Shockwave;
+use crate::settings::Settings;
+use crate::spatial_audio;
+
+// Mixer constants
+const VOLUME_STEP: u32 = 10; // Percent the settings screen moves a volume by
+const VOLUME_LABELS: [&str; 11] = [
+    "0%", "10%", "20%", "30%", "40%", "50%", "60%", "70%", "80%", "90%", "100%",
+];
+const DUCKED_MUSIC: f32 = 0.3; // Music level while ducked
+const DUCK_FADE: f32 = 4.0; // How fast the music fades down and back up, per second
+const BOMB_DUCK: f32 = 1.5; // Seconds the music is ducked under a bomb
+const GAME_OVER_DUCK: f32 = 3.0; // Seconds the music is ducked under the game-over sting
+
+/// The next volume up on the settings screen, wrapping round to silent.
+pub fn next_volume(percent: u32) -> u32 {
+    if percent >= 100 {
+        0
+    } else {
+        (percent / VOLUME_STEP + 1) * VOLUME_STEP
+    }
+}
+
+pub fn volume_label(percent: u32) -> &'static str {
+    VOLUME_LABELS[(percent / VOLUME_STEP).min(10) as usize]
+}
+
+// --- Components ---
+
+/// The mixer bus a sound plays on. Sounds spawned without one are effects.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Bus {
+    Music,
+    #[default]
+    Sfx,
+    Ui,
+}
+
+impl Bus {
+    fn volume(self, settings: &Settings) -> u32 {
+        match self {
+            Bus::Music => settings.music_volume,
+            Bus::Sfx => settings.sfx_volume,
+            Bus::Ui => settings.ui_volume,
+        }
+    }
+}
+
+/// What a sound tells the player, so it can be shown as well as heard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SoundCue {
+    /// Something turned up where the sound was played.
+    Arrival,
+    /// Something big happened, wherever it was.
+    Alarm,
+}
+
+// --- Events ---
+
+/// Plays a sound on a bus, where it happened if it happened somewhere. Every
+/// game sound goes through this, so visual cues can show what's heard.
+#[derive(Event, Clone)]
+pub struct PlaySound {
+    pub sound: Handle<AudioSource>,
+    pub bus: Bus,
+    pub at: Option<Vec2>,
+    pub cue: SoundCue,
+}
+
+/// Turns the music down for a while, to let a big moment through.
+#[derive(Event)]
+pub struct Duck {
+    pub seconds: f32,
+}
+
+// --- Resources ---
+
+/// How far the music is currently turned down, and for how much longer.
+#[derive(Resource)]
+struct Ducking {
+    remaining: f32,
+    level: f32,
+}
+
+impl Default for Ducking {
+    fn default() -> Self {
+        Self {
+            remaining: 0.0,
+            level: 1.0,
+        }
+    }
+}
+
+impl Ducking {
+    /// Keeps the music down for at least another `seconds`.
+    fn hold(&mut self, seconds: f32) {
+        self.remaining = self.remaining.max(seconds);
+    }
+
+    /// Moves `delta` seconds on, fading the level towards ducked while
+    /// anything is still holding it down and back to full after.
+    fn advance(&mut self, delta: f32) {
+        self.remaining = (self.remaining - delta).max(0.0);
+        let target = if self.remaining > 0.0 {
+            DUCKED_MUSIC
+        } else {
+            1.0
+        };
+        let step = DUCK_FADE * delta;
+        self.level += (target - self.level).clamp(-step, step);
+    }
+}
+
+pub struct MixerPlugin;
+
+impl Plugin for MixerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<Duck>()
+            .add_event::<PlaySound>()
+            .init_resource::<Ducking>()
+            .add_systems(OnEnter(GameState::GameOver), duck_game_over)
+            .add_systems(Update, (play_sounds, (duck_bombs, update_ducking).chain()))
+            .add_systems(PostUpdate, mix_buses.after(AudioPlaySet));
+    }
+}
+
+/// System that starts every requested sound, placed in the world if it has a
+/// place
+fn play_sounds(mut commands: Commands, mut events: EventReader<PlaySound>) {
+    for event in events.read() {
+        let mut sound = commands.spawn((AudioPlayer::new(event.sound.clone()), event.bus));
+        match event.at {
+            Some(at) => sound.insert(spatial_audio::at(at)),
+            None => sound.insert(PlaybackSettings::DESPAWN),
+        };
+    }
+}
+
+/// System that ducks the music under every bomb going off
+fn duck_bombs(mut ducks: EventWriter<Duck>, query: Query<(), Added<Shockwave>>) {
+    if !query.is_empty() {
+        ducks.write(Duck { seconds: BOMB_DUCK });
+    }
+}
+
+/// System that ducks the music under the game-over sting
+fn duck_game_over(mut ducks: EventWriter<Duck>) {
+    ducks.write(Duck {
+        seconds: GAME_OVER_DUCK,
+    });
+}
+
+/// System that holds the music down while anything asks for it, fading it out
+/// and back in again in real time, so the kill cam's slow motion doesn't drag
+/// it out
+fn update_ducking(
+    time: Res<Time<Real>>,
+    mut events: EventReader<Duck>,
+    mut ducking: ResMut<Ducking>,
+) {
+    for event in events.read() {
+        ducking.hold(event.seconds);
+    }
+    ducking.advance(time.delta_secs());
+}
+
+/// System that sets every playing sound to its bus's volume under the master
+/// volume, with the music ducked as needed. Runs as soon as sounds start, so
+/// none of them is heard at full volume first.
+fn mix_buses(
+    settings: Res<Settings>,
+    global: Res<GlobalVolume>,
+    ducking: Res<Ducking>,
+    mut sinks: Query<(&mut AudioSink, Option<&Bus>)>,
+    mut spatial_sinks: Query<(&mut SpatialAudioSink, Option<&Bus>)>,
+) {
+    // The global volume is how the game mutes itself in the background
+    let master = global.volume.to_linear() * settings.master_volume as f32 / 100.0;
+    let gain = |bus: Option<&Bus>| {
+        let bus = bus.copied().unwrap_or_default();
+        let duck = if bus == Bus::Music {
+            ducking.level
+        } else {
+            1.0
+        };
+        master * bus.volume(&settings) as f32 / 100.0 * duck
+    };
+    for (mut sink, bus) in &mut sinks {
+        let volume = gain(bus);
+        if sink.volume().to_linear() != volume {
+            sink.set_volume(Volume::Linear(volume));
+        }
+    }
+    for (mut sink, bus) in &mut spatial_sinks {
+        let volume = gain(bus);
+        if sink.volume().to_linear() != volume {
+            sink.set_volume(Volume::Linear(volume));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ducking_fades_down_holds_and_comes_back() {
+        let mut ducking = Ducking::default();
+        ducking.hold(1.0);
+        ducking.advance(0.1);
+        assert!(ducking.level < 1.0 && ducking.level > DUCKED_MUSIC);
+        for _ in 0..4 {
+            ducking.advance(0.1);
+        }
+        assert!((ducking.level - DUCKED_MUSIC).abs() < 1e-6);
+        for _ in 0..10 {
+            ducking.advance(0.1);
+        }
+        assert_eq!(ducking.remaining, 0.0);
+        assert!((ducking.level - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn the_longest_duck_wins() {
+        let mut ducking = Ducking::default();
+        ducking.hold(GAME_OVER_DUCK);
+        ducking.hold(BOMB_DUCK);
+        assert_eq!(ducking.remaining, GAME_OVER_DUCK);
+    }
+
+    #[test]
+    fn buses_follow_their_own_volumes() {
+        let settings = Settings {
+            music_volume: 30,
+            sfx_volume: 60,
+            ui_volume: 90,
+            ..default()
+        };
+        assert_eq!(Bus::Music.volume(&settings), 30);
+        assert_eq!(Bus::Sfx.volume(&settings), 60);
+        assert_eq!(Bus::Ui.volume(&settings), 90);
+        assert_eq!(Bus::default(), Bus::Sfx);
+    }
+}
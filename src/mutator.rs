@@ -0,0 +1,179 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::prelude::*;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::IndexedRandom;
+use serde::{Deserialize, Serialize};
+
+use crate::difficulty::{Difficulty, DifficultyConfig};
+use crate::reset::RunSetup;
+use crate::sets::RunSetupSet;
+
+// Mutator constants
+const WEEKLY_MUTATORS: usize = 2; // Mutators in each week's rotation
+const SECONDS_PER_WEEK: u64 = 7 * 24 * 60 * 60;
+const LOW_GRAVITY_SCALE: f32 = 0.6; // Enemy fall speed under low gravity
+const TINY_PLAYER_SCALE: f32 = 0.6;
+
+/// A rule change layered on top of the difficulty for one run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Mutator {
+    LowGravity,
+    TinyPlayer,
+    DoubleSpawns,
+    MirroredControls,
+    MirroredSpawns,
+}
+
+impl Mutator {
+    pub const ALL: [Mutator; 5] = [
+        Mutator::LowGravity,
+        Mutator::TinyPlayer,
+        Mutator::DoubleSpawns,
+        Mutator::MirroredControls,
+        Mutator::MirroredSpawns,
+    ];
+
+    /// The mutators weekly rotations are drawn from. Fixed, so adding a
+    /// mutator doesn't change the rotation of weeks already played.
+    const WEEKLY_POOL: [Mutator; 4] = [
+        Mutator::LowGravity,
+        Mutator::TinyPlayer,
+        Mutator::DoubleSpawns,
+        Mutator::MirroredControls,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Mutator::LowGravity => "Low gravity",
+            Mutator::TinyPlayer => "Tiny player",
+            Mutator::DoubleSpawns => "Double spawns",
+            Mutator::MirroredControls => "Mirrored controls",
+            Mutator::MirroredSpawns => "Mirrored spawns",
+        }
+    }
+
+    fn apply(self, config: &mut DifficultyConfig) {
+        match self {
+            Mutator::LowGravity => config.enemy_speed *= LOW_GRAVITY_SCALE,
+            Mutator::TinyPlayer => config.player_scale *= TINY_PLAYER_SCALE,
+            Mutator::DoubleSpawns => {
+                config.spawn.start_interval /= 2.0;
+                config.spawn.min_interval /= 2.0;
+            }
+            Mutator::MirroredControls => {
+                config.mirrored_controls = !config.mirrored_controls;
+            }
+            Mutator::MirroredSpawns => config.mirrored_spawns = true,
+        }
+    }
+}
+
+/// Applies a stack of mutators to a difficulty's config, in order.
+pub fn apply(mutators: &[Mutator], config: &mut DifficultyConfig) {
+    for mutator in mutators {
+        mutator.apply(config);
+    }
+}
+
+/// Joins mutator names for display, or `None` when there are none.
+pub fn describe(mutators: &[Mutator]) -> Option<String> {
+    if mutators.is_empty() {
+        return None;
+    }
+    let names: Vec<&str> = mutators.iter().map(|m| m.name()).collect();
+    Some(names.join(", "))
+}
+
+/// Weeks since the Unix epoch, the index of the weekly rotation.
+pub fn current_week() -> u64 {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    now.as_secs() / SECONDS_PER_WEEK
+}
+
+/// The mutators of a week's rotation. Every player gets the same ones.
+pub fn weekly_mutators(week: u64) -> Vec<Mutator> {
+    let mut rng = StdRng::seed_from_u64(week);
+    let mut mutators: Vec<Mutator> = Mutator::WEEKLY_POOL
+        .choose_multiple(&mut rng, WEEKLY_MUTATORS)
+        .copied()
+        .collect();
+    mutators.sort();
+    mutators
+}
+
+// --- Resources ---
+
+/// Mutators picked on the menu for the next run.
+#[derive(Resource, Default)]
+pub struct MutatorSelection {
+    chosen: Vec<Mutator>,
+    /// Use this week's rotation instead of the hand-picked mutators.
+    pub weekly: bool,
+}
+
+impl MutatorSelection {
+    pub fn toggle(&mut self, mutator: Mutator) {
+        match self.chosen.iter().position(|&m| m == mutator) {
+            Some(index) => {
+                self.chosen.remove(index);
+            }
+            None => {
+                self.chosen.push(mutator);
+                self.chosen.sort();
+            }
+        }
+    }
+
+    pub fn is_chosen(&self, mutator: Mutator) -> bool {
+        self.chosen.contains(&mutator)
+    }
+
+    /// Replaces the hand-picked mutators.
+    pub fn set(&mut self, mutators: &[Mutator]) {
+        self.chosen = mutators.to_vec();
+        self.chosen.sort();
+        self.chosen.dedup();
+    }
+
+    /// The mutators the next run will use.
+    pub fn stack(&self) -> Vec<Mutator> {
+        if self.weekly {
+            weekly_mutators(current_week())
+        } else {
+            self.chosen.clone()
+        }
+    }
+}
+
+/// Mutators in effect for the current run.
+#[derive(Resource, Default)]
+pub struct RunMutators(pub Vec<Mutator>);
+
+pub struct MutatorPlugin;
+
+impl Plugin for MutatorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MutatorSelection>()
+            .init_resource::<RunMutators>()
+            .add_systems(RunSetup, start_run_mutators.in_set(RunSetupSet::Difficulty));
+    }
+}
+
+/// System that rebuilds the run's config from its preset and the selected mutators
+pub fn start_run_mutators(
+    selection: Res<MutatorSelection>,
+    mut run_mutators: ResMut<RunMutators>,
+    mut difficulty: ResMut<Difficulty>,
+) {
+    run_mutators.0 = selection.stack();
+    // Start from the preset each time so mutators don't pile up across runs
+    *difficulty = Difficulty::load(difficulty.preset);
+    apply(&run_mutators.0, &mut difficulty.config);
+    if let Some(names) = describe(&run_mutators.0) {
+        info!(mutators = %names, "Run mutated");
+    }
+}
@@ -0,0 +1,263 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::bomb::Bombs;
+use crate::data;
+use crate::graze::NearMiss;
+use crate::hud_layout::{HudAnchor, HudSlot};
+use crate::reset::{RunScoped, RunSetup};
+use crate::score::{ScoreEvent, ScoreReason};
+use crate::sets::GameSet;
+use crate::text_style::TextStyleLibrary;
+use crate::{Player, RunPhase};
+
+// Objective constants
+const OBJECTIVES_FILE: &str = "objectives.ron";
+const BUILTIN_OBJECTIVES: &str = include_str!("../assets/objectives.ron");
+const OBJECTIVE_GAP: f32 = 4.0; // Seconds the completion line stays up before the next objective
+const COMPLETE_COLOR: Color = Color::srgb(0.5, 1.0, 0.5);
+
+/// What an objective asks for, counted from when it was handed out.
+#[derive(Debug, Clone, Copy, Deserialize)]
+enum Goal {
+    Survive(f32),
+    Graze(u32),
+    DodgeElites(u32),
+    BombKills(u32),
+    Points(f32),
+}
+
+impl Goal {
+    /// How much of the goal is done and how much it needs.
+    fn progress(self, tally: &Tally) -> (f32, f32) {
+        match self {
+            Goal::Survive(seconds) => (tally.seconds, seconds),
+            Goal::Graze(count) => (tally.grazes as f32, count as f32),
+            Goal::DodgeElites(count) => (tally.elite_dodges as f32, count as f32),
+            Goal::BombKills(count) => (tally.bomb_kills as f32, count as f32),
+            Goal::Points(amount) => (tally.points, amount),
+        }
+    }
+
+    fn describe_progress(self, tally: &Tally) -> String {
+        let (done, needed) = self.progress(tally);
+        let done = done.min(needed).floor();
+        match self {
+            Goal::Survive(_) => format!("{done:.0}/{needed:.0}s"),
+            _ => format!("{done:.0}/{needed:.0}"),
+        }
+    }
+}
+
+/// A rule that starts the goal over whenever it's broken.
+#[derive(Debug, Clone, Copy, Deserialize)]
+enum Limit {
+    /// The player stays in the bottom half of the screen.
+    StayBelowCenter,
+    NoBombs,
+}
+
+impl Limit {
+    fn broken(self, player_y: f32, bomb_used: bool) -> bool {
+        match self {
+            Limit::StayBelowCenter => player_y > 0.0,
+            Limit::NoBombs => bomb_used,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ObjectiveDef {
+    text: String,
+    goal: Goal,
+    #[serde(default)]
+    limit: Option<Limit>,
+    /// Points awarded on completion.
+    bonus: f32,
+}
+
+/// Contents of `assets/objectives.ron`.
+#[derive(Deserialize)]
+struct ObjectivesConfig {
+    objectives: Vec<ObjectiveDef>,
+}
+
+/// Everything goals are measured in, since the current objective began.
+#[derive(Default)]
+struct Tally {
+    seconds: f32,
+    grazes: u32,
+    elite_dodges: u32,
+    bomb_kills: u32,
+    points: f32,
+}
+
+// --- Components ---
+
+#[derive(Component)]
+#[require(RunScoped)]
+struct ObjectiveText;
+
+// --- Resources ---
+
+#[derive(Resource)]
+struct Objectives(Vec<ObjectiveDef>);
+
+impl Default for Objectives {
+    fn default() -> Self {
+        let config: ObjectivesConfig = data::load_ron(OBJECTIVES_FILE, BUILTIN_OBJECTIVES);
+        Self(config.objectives)
+    }
+}
+
+/// The objective being worked on in this run. They're handed out in file
+/// order, so seeded runs get the same ones at the same times.
+#[derive(Resource, Default)]
+pub struct ActiveObjective {
+    /// Objectives completed so far this run.
+    pub completed: u32,
+    index: usize,
+    tally: Tally,
+    bombs_held: u32,
+    /// Counts down the pause after an objective is completed.
+    gap: Option<Timer>,
+}
+
+pub struct ObjectivePlugin;
+
+impl Plugin for ObjectivePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Objectives>()
+            .init_resource::<ActiveObjective>()
+            .add_systems(RunSetup, (reset_objectives, spawn_objective_text))
+            .add_systems(
+                Update,
+                (
+                    track_objective.in_set(GameSet::Collision),
+                    update_objective_text.in_set(GameSet::UiSync),
+                )
+                    .run_if(in_state(RunPhase::Alive).and(has_objectives)),
+            );
+    }
+}
+
+fn has_objectives(objectives: Res<Objectives>) -> bool {
+    !objectives.0.is_empty()
+}
+
+/// System to start every run on the first objective
+fn reset_objectives(mut active: ResMut<ActiveObjective>, bombs: Res<Bombs>) {
+    *active = ActiveObjective {
+        bombs_held: bombs.count,
+        ..default()
+    };
+}
+
+/// System to spawn the objective line under the speedrun splits
+fn spawn_objective_text(mut commands: Commands, styles: Res<TextStyleLibrary>) {
+    commands.spawn((
+        Text::default(),
+        styles.body.ui(),
+        HudSlot::new(HudAnchor::TopLeft, 10),
+        ObjectiveText,
+    ));
+}
+
+/// System that counts towards the current objective, starts it over when
+/// its limit is broken and pays out the bonus once it's met
+fn track_objective(
+    time: Res<Time>,
+    objectives: Res<Objectives>,
+    bombs: Res<Bombs>,
+    mut active: ResMut<ActiveObjective>,
+    mut near_misses: EventReader<NearMiss>,
+    mut score_events: ParamSet<(EventReader<ScoreEvent>, EventWriter<ScoreEvent>)>,
+    player_query: Query<&Transform, With<Player>>,
+) {
+    let grazes = near_misses.read().count() as u32;
+    let earned: Vec<(ScoreReason, f32)> = score_events
+        .p0()
+        .read()
+        .map(|event| (event.reason, event.amount))
+        .collect();
+    let bomb_used = bombs.count < active.bombs_held;
+    active.bombs_held = bombs.count;
+
+    if let Some(gap) = active.gap.as_mut() {
+        if gap.tick(time.delta()).finished() {
+            active.gap = None;
+            active.index = (active.index + 1) % objectives.0.len();
+        }
+        return;
+    }
+    let Ok(player_transform) = player_query.single() else {
+        return;
+    };
+    let objective = &objectives.0[active.index % objectives.0.len()];
+    if objective
+        .limit
+        .is_some_and(|limit| limit.broken(player_transform.translation.y, bomb_used))
+    {
+        active.tally = Tally::default();
+        return;
+    }
+
+    let tally = &mut active.tally;
+    tally.seconds += time.delta_secs();
+    tally.grazes += grazes;
+    for (reason, amount) in earned {
+        match reason {
+            ScoreReason::EliteDodge => tally.elite_dodges += 1,
+            ScoreReason::BombKill => tally.bomb_kills += 1,
+            ScoreReason::Survival | ScoreReason::Jackpot => {}
+            // Objective bonuses don't count towards the next objective
+            ScoreReason::Objective => continue,
+        }
+        tally.points += amount;
+    }
+
+    let (done, needed) = objective.goal.progress(tally);
+    if done >= needed {
+        info!(objective = %objective.text, bonus = objective.bonus, "Objective complete");
+        score_events.p1().write(ScoreEvent {
+            amount: objective.bonus,
+            reason: ScoreReason::Objective,
+            position: Some(player_transform.translation.truncate()),
+        });
+        active.completed += 1;
+        active.tally = Tally::default();
+        active.gap = Some(Timer::from_seconds(OBJECTIVE_GAP, TimerMode::Once));
+    }
+}
+
+/// System that shows the current objective and how far along it is
+fn update_objective_text(
+    objectives: Res<Objectives>,
+    active: Res<ActiveObjective>,
+    styles: Res<TextStyleLibrary>,
+    mut query: Query<(&mut Text, &mut TextColor), With<ObjectiveText>>,
+) {
+    let Ok((mut text, mut color)) = query.single_mut() else {
+        return;
+    };
+    let objective = &objectives.0[active.index % objectives.0.len()];
+    let (line, line_color) = if active.gap.is_some() {
+        (
+            format!("Objective complete! +{}", objective.bonus.round() as u32),
+            COMPLETE_COLOR,
+        )
+    } else {
+        (
+            format!(
+                "Objective: {} ({})",
+                objective.text,
+                objective.goal.describe_progress(&active.tally)
+            ),
+            styles.body.color,
+        )
+    };
+    if text.0 != line {
+        text.0 = line;
+    }
+    color.set_if_neq(TextColor(line_color));
+}
@@ -0,0 +1,130 @@
+use std::env;
+
+use bevy::prelude::*;
+use bevy::render::camera::RenderTarget;
+use bevy::render::view::RenderLayers;
+use bevy::window::{WindowCloseRequested, WindowRef, WindowResolution};
+
+use crate::GameState;
+use crate::score::{HighScores, Score};
+use crate::stats::RunStats;
+use crate::text_style::TextStyleLibrary;
+
+// Overlay constants
+const OVERLAY_FLAG: &str = "--overlay";
+const OVERLAY_TITLE: &str = "Rusty Dodger overlay";
+const OVERLAY_APP_ID: &str = "rusty_dodger_overlay"; // Lets capture tools tell the two windows apart
+const OVERLAY_SIZE: Vec2 = Vec2::new(320.0, 120.0);
+const OVERLAY_LAYER: usize = 31; // Render layer nothing but the overlay camera uses
+
+// --- Components ---
+
+/// The transparent overlay window and everything drawn into it.
+#[derive(Component)]
+struct StreamOverlay;
+
+#[derive(Component)]
+struct OverlayText;
+
+pub struct OverlayPlugin;
+
+impl Plugin for OverlayPlugin {
+    fn build(&self, app: &mut App) {
+        if !env::args().any(|arg| arg == OVERLAY_FLAG) {
+            return;
+        }
+        app.add_systems(Startup, open_overlay_window)
+            .add_systems(Update, (update_overlay, close_overlay_window));
+    }
+}
+
+/// System to open a borderless, transparent window with its own camera and
+/// score panel, for capturing on top of a stream layout
+fn open_overlay_window(mut commands: Commands, styles: Res<TextStyleLibrary>) {
+    let window = commands
+        .spawn((
+            Window {
+                title: OVERLAY_TITLE.to_string(),
+                name: Some(OVERLAY_APP_ID.to_string()),
+                resolution: WindowResolution::new(OVERLAY_SIZE.x, OVERLAY_SIZE.y),
+                transparent: true,
+                decorations: false,
+                resizable: false,
+                ..default()
+            },
+            StreamOverlay,
+        ))
+        .id();
+    // The camera sees none of the game world, so only the panel is drawn
+    let camera = commands
+        .spawn((
+            Camera2d,
+            Camera {
+                target: RenderTarget::Window(WindowRef::Entity(window)),
+                clear_color: ClearColorConfig::Custom(Color::NONE),
+                order: 1,
+                ..default()
+            },
+            RenderLayers::layer(OVERLAY_LAYER),
+            StreamOverlay,
+        ))
+        .id();
+    commands.spawn((
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            padding: UiRect::all(Val::Px(12.0)),
+            ..default()
+        },
+        UiTargetCamera(camera),
+        StreamOverlay,
+        children![(Text::default(), styles.hud.ui(), OverlayText)],
+    ));
+    info!("Stream overlay window opened");
+}
+
+/// System that mirrors the score, run time and best score into the overlay
+fn update_overlay(
+    state: Res<State<GameState>>,
+    score: Res<Score>,
+    stats: Res<RunStats>,
+    high_scores: Res<HighScores>,
+    mut query: Query<&mut Text, With<OverlayText>>,
+) {
+    let Ok(mut text) = query.single_mut() else {
+        return;
+    };
+    let best = high_scores.entries.first().map_or(0, |entry| entry.score);
+    let best = best.max(score.points());
+    text.0 = match state.get() {
+        GameState::Playing | GameState::GameOver => {
+            let seconds = stats.elapsed() as u32;
+            format!(
+                "Score: {}\nTime: {}:{:02}\nBest: {best}",
+                score.points(),
+                seconds / 60,
+                seconds % 60
+            )
+        }
+        _ => format!("Best: {best}"),
+    };
+}
+
+/// System that closes the overlay on its own, leaving the game running
+fn close_overlay_window(
+    mut commands: Commands,
+    mut close_events: EventReader<WindowCloseRequested>,
+    window_query: Query<(), (With<Window>, With<StreamOverlay>)>,
+    overlay_query: Query<Entity, With<StreamOverlay>>,
+) {
+    let closing = close_events
+        .read()
+        .any(|event| window_query.contains(event.window));
+    if !closing {
+        return;
+    }
+    for entity in &overlay_query {
+        commands.entity(entity).despawn();
+    }
+    info!("Stream overlay window closed");
+}
@@ -0,0 +1,403 @@
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::GameState;
+use crate::hud_layout::{HudAnchor, HudSlot};
+use crate::profile;
+use crate::reset::{RunScoped, RunSetup};
+use crate::rng::{self, RunSeed};
+use crate::score::Score;
+use crate::text_input::{self, TextInputAction};
+use crate::text_style::TextStyleLibrary;
+
+// Party constants
+const MIN_PLAYERS: usize = 2;
+const MAX_PLAYERS: usize = 8;
+const MAX_ROUNDS: u32 = 5;
+const MAX_NAME_LEN: usize = 12;
+
+// --- Components ---
+
+#[derive(Component)]
+struct PartyScreen;
+
+#[derive(Component)]
+struct PartyText;
+
+/// The current turn, shown at the top during party runs.
+#[derive(Component)]
+#[require(RunScoped)]
+struct TurnBanner;
+
+// --- Resources ---
+
+/// Players signing up for a party. Kept between parties so a rematch night
+/// doesn't mean typing everyone in again.
+#[derive(Resource)]
+struct PartyLobby {
+    names: Vec<String>,
+    entry: String,
+    rounds: u32,
+    /// Set on opening, so the key that opened the lobby isn't typed into it.
+    just_opened: bool,
+}
+
+impl Default for PartyLobby {
+    fn default() -> Self {
+        Self {
+            names: Vec::new(),
+            entry: String::new(),
+            rounds: 1,
+            just_opened: false,
+        }
+    }
+}
+
+struct PartyPlayer {
+    name: String,
+    scores: Vec<u32>,
+}
+
+impl PartyPlayer {
+    fn total(&self) -> u32 {
+        self.scores.iter().sum()
+    }
+
+    fn best(&self) -> u32 {
+        self.scores.iter().copied().max().unwrap_or(0)
+    }
+}
+
+/// A party in progress: players take turns on the same seed, round-robin,
+/// for a set number of rounds. Present only while a party is on.
+#[derive(Resource)]
+pub struct Party {
+    players: Vec<PartyPlayer>,
+    rounds: u32,
+    seed: u64,
+    /// Turns already played.
+    turn: usize,
+    /// The menu's seed before the party took it over.
+    previous_seed: Option<u64>,
+}
+
+impl Party {
+    fn total_turns(&self) -> usize {
+        self.players.len() * self.rounds as usize
+    }
+
+    /// Whether every player has had all their turns.
+    pub fn finished(&self) -> bool {
+        self.turn >= self.total_turns()
+    }
+
+    /// The player whose turn it is, or is next.
+    fn current(&self) -> &PartyPlayer {
+        &self.players[self.turn % self.players.len()]
+    }
+
+    fn round(&self) -> u32 {
+        (self.turn / self.players.len()) as u32 + 1
+    }
+}
+
+pub struct PartyPlugin;
+
+impl Plugin for PartyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PartyLobby>()
+            .add_systems(OnEnter(GameState::PartySetup), spawn_party_lobby)
+            .add_systems(
+                Update,
+                (party_lobby_input, update_party_lobby)
+                    .chain()
+                    .run_if(in_state(GameState::PartySetup)),
+            )
+            .add_systems(OnExit(GameState::PartySetup), despawn_party_screen)
+            .add_systems(RunSetup, spawn_turn_banner.run_if(resource_exists::<Party>))
+            .add_systems(
+                OnEnter(GameState::GameOver),
+                record_turn.run_if(resource_exists::<Party>),
+            )
+            .add_systems(OnEnter(GameState::PartyResults), spawn_party_results)
+            .add_systems(
+                Update,
+                party_results_input.run_if(in_state(GameState::PartyResults)),
+            )
+            .add_systems(OnExit(GameState::PartyResults), despawn_party_screen)
+            .add_systems(
+                OnEnter(GameState::Menu),
+                end_party.run_if(resource_exists::<Party>),
+            )
+            .add_systems(
+                OnEnter(GameState::ProfileSelect),
+                end_party.run_if(resource_exists::<Party>),
+            );
+    }
+}
+
+/// Where Restart on the Game Over screen leads: the next turn, or the
+/// results once the party is over.
+pub fn after_game_over(party: Option<&Party>) -> GameState {
+    match party {
+        Some(party) if party.finished() => GameState::PartyResults,
+        _ => GameState::Playing,
+    }
+}
+
+/// System to open the lobby for signing players up
+fn spawn_party_lobby(mut commands: Commands, mut lobby: ResMut<PartyLobby>) {
+    lobby.entry.clear();
+    lobby.just_opened = true;
+    spawn_party_screen(&mut commands);
+}
+
+fn spawn_party_screen(commands: &mut Commands) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(40.0),
+            left: Val::Px(40.0),
+            ..default()
+        },
+        PartyScreen,
+        children![(Text::default(), PartyText)],
+    ));
+}
+
+/// System to type in player names, pick the rounds and start the party
+fn party_lobby_input(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut lobby: ResMut<PartyLobby>,
+    mut seed: ResMut<RunSeed>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if lobby.just_opened {
+        lobby.just_opened = false;
+        keyboard_events.clear();
+        return;
+    }
+    if keyboard_input.just_pressed(KeyCode::ArrowLeft) {
+        lobby.rounds = (lobby.rounds - 1).max(1);
+    }
+    if keyboard_input.just_pressed(KeyCode::ArrowRight) {
+        lobby.rounds = (lobby.rounds + 1).min(MAX_ROUNDS);
+    }
+
+    for event in keyboard_events.read() {
+        let lobby = &mut *lobby;
+        // Backspace on an empty name takes the last player off the list
+        if event.logical_key == Key::Backspace && lobby.entry.is_empty() {
+            if event.state.is_pressed() {
+                lobby.names.pop();
+            }
+            continue;
+        }
+        match text_input::apply_key(&mut lobby.entry, event, MAX_NAME_LEN, profile::is_name_char) {
+            TextInputAction::Submit => {
+                let name = lobby.entry.trim().to_string();
+                if !name.is_empty() {
+                    if lobby.names.len() < MAX_PLAYERS {
+                        lobby.names.push(name);
+                    }
+                    lobby.entry.clear();
+                } else if lobby.names.len() >= MIN_PLAYERS {
+                    start_party(&mut commands, lobby, &mut seed);
+                    next_state.set(GameState::Playing);
+                    return;
+                }
+            }
+            TextInputAction::Cancel => {
+                next_state.set(GameState::Menu);
+                return;
+            }
+            TextInputAction::Edited | TextInputAction::None => {}
+        }
+    }
+}
+
+/// Starts a party for the lobby's players on the menu's seed, or a fresh one.
+fn start_party(commands: &mut Commands, lobby: &PartyLobby, seed: &mut RunSeed) {
+    let party_seed = seed
+        .fixed
+        .unwrap_or_else(|| u64::from(rand::rng().random::<u32>()));
+    info!(
+        players = lobby.names.len(),
+        rounds = lobby.rounds,
+        seed = %rng::format_seed(party_seed),
+        "Party started"
+    );
+    commands.insert_resource(Party {
+        players: lobby
+            .names
+            .iter()
+            .map(|name| PartyPlayer {
+                name: name.clone(),
+                scores: Vec::new(),
+            })
+            .collect(),
+        rounds: lobby.rounds,
+        seed: party_seed,
+        turn: 0,
+        previous_seed: seed.fixed,
+    });
+    seed.fixed = Some(party_seed);
+}
+
+/// System that redraws the lobby
+fn update_party_lobby(
+    lobby: Res<PartyLobby>,
+    seed: Res<RunSeed>,
+    mut query: Query<&mut Text, With<PartyText>>,
+) {
+    if !lobby.is_changed() && !seed.is_changed() {
+        return;
+    }
+    let Ok(mut text) = query.single_mut() else {
+        return;
+    };
+    let seed_line = match seed.fixed {
+        Some(fixed) => format!("Seed: {} (from the menu)", rng::format_seed(fixed)),
+        None => "Seed: random, the same for everyone".to_string(),
+    };
+    let mut lines = vec![
+        "Party Mode".to_string(),
+        String::new(),
+        seed_line,
+        format!("Rounds: < {} >", lobby.rounds),
+        String::new(),
+        format!("Players ({}/{MAX_PLAYERS}):", lobby.names.len()),
+    ];
+    for (index, name) in lobby.names.iter().enumerate() {
+        lines.push(format!("  {}. {name}", index + 1));
+    }
+    if lobby.names.len() < MAX_PLAYERS {
+        lines.push(format!("  > {}_", lobby.entry));
+    }
+    lines.push(String::new());
+    lines.push(if lobby.names.len() >= MIN_PLAYERS {
+        "Enter: Add player / Start with an empty name   Backspace: Remove   Esc: Back".to_string()
+    } else {
+        format!("Enter: Add player (at least {MIN_PLAYERS})   Backspace: Remove   Esc: Back")
+    });
+    text.0 = lines.join("\n");
+}
+
+/// System to show whose turn it is at the top of the screen
+fn spawn_turn_banner(mut commands: Commands, party: Res<Party>, styles: Res<TextStyleLibrary>) {
+    commands.spawn((
+        Text::new(format!(
+            "{}'s turn - round {}/{}",
+            party.current().name,
+            party.round(),
+            party.rounds
+        )),
+        styles.hud.ui(),
+        HudSlot::new(HudAnchor::Top, 0),
+        TurnBanner,
+    ));
+}
+
+/// System that writes the finished run down for the player whose turn it was
+fn record_turn(
+    mut commands: Commands,
+    mut party: ResMut<Party>,
+    score: Res<Score>,
+    styles: Res<TextStyleLibrary>,
+) {
+    let points = score.points();
+    let index = party.turn % party.players.len();
+    let player = &mut party.players[index];
+    player.scores.push(points);
+    let scored = format!("{} scored {points}.", player.name);
+    party.turn += 1;
+
+    let next = if party.finished() {
+        "That was the last turn. Press R for the results.".to_string()
+    } else {
+        format!("Next up: {} (R)", party.current().name)
+    };
+    commands.spawn((
+        Text::new(format!("{scored} {next}")),
+        styles.body.ui(),
+        HudSlot::new(HudAnchor::Bottom, 0),
+        RunScoped,
+    ));
+}
+
+/// System to spawn the final standings, best total first
+fn spawn_party_results(mut commands: Commands, party: Res<Party>) {
+    let mut standings: Vec<&PartyPlayer> = party.players.iter().collect();
+    standings.sort_by(|a, b| (b.total(), b.best()).cmp(&(a.total(), a.best())));
+
+    let rounds: String = (1..=party.rounds)
+        .map(|r| format!("{:>7}", format!("R{r}")))
+        .collect();
+    let mut lines = vec![
+        "Party Results".to_string(),
+        format!("Seed: {}", rng::format_seed(party.seed)),
+        String::new(),
+        format!(
+            "     {:<MAX_NAME_LEN$}{rounds}{:>8}{:>7}",
+            "Player", "Total", "Best"
+        ),
+    ];
+    for (place, player) in standings.iter().enumerate() {
+        let scores: String = player.scores.iter().map(|s| format!("{s:>7}")).collect();
+        lines.push(format!(
+            "  {}. {:<MAX_NAME_LEN$}{scores}{:>8}{:>7}",
+            place + 1,
+            player.name,
+            player.total(),
+            player.best()
+        ));
+    }
+    lines.push(String::new());
+    lines.push("R: Rematch on the same seed   Esc: Menu".to_string());
+
+    commands.spawn((
+        Text::new(lines.join("\n")),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(40.0),
+            left: Val::Px(40.0),
+            ..default()
+        },
+        PartyScreen,
+    ));
+}
+
+/// System to play the party again or leave it
+fn party_results_input(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut party: ResMut<Party>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::KeyR) {
+        for player in &mut party.players {
+            player.scores.clear();
+        }
+        party.turn = 0;
+        next_state.set(GameState::Playing);
+    }
+    if keyboard_input.any_just_pressed([KeyCode::Escape, KeyCode::Enter]) {
+        next_state.set(GameState::Menu);
+    }
+}
+
+/// System to remove the lobby or results screen
+fn despawn_party_screen(mut commands: Commands, query: Query<Entity, With<PartyScreen>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// System that ends the party and gives the menu its seed back
+fn end_party(mut commands: Commands, party: Res<Party>, mut seed: ResMut<RunSeed>) {
+    seed.fixed = party.previous_seed;
+    commands.remove_resource::<Party>();
+    info!("Party over");
+}
@@ -0,0 +1,211 @@
+use std::f32::consts::{PI, TAU};
+
+use bevy::log::debug;
+use rand::Rng;
+
+/// Building blocks composed patterns are made of.
+#[derive(Debug, Clone, Copy)]
+pub enum Primitive {
+    /// A single row with one gap.
+    Wall,
+    /// A narrow diagonal line bouncing between the edges.
+    Stream,
+    /// Two arms turning around the middle of the screen.
+    Spiral,
+}
+
+impl Primitive {
+    const ALL: [Primitive; 3] = [Primitive::Wall, Primitive::Stream, Primitive::Spiral];
+}
+
+/// A spawn pattern on a grid: rows fall one after another and each row is
+/// split into lanes across the screen width, `true` where an enemy goes.
+pub struct Pattern {
+    pub lanes: usize,
+    pub rows: Vec<Vec<bool>>,
+}
+
+/// How far the player can get between two rows, in the pattern's units.
+#[derive(Clone, Copy)]
+pub struct Reach {
+    /// Lanes the player's hitbox covers.
+    pub player_lanes: usize,
+    /// Lanes the player can move sideways between one row and the next.
+    pub max_shift: usize,
+}
+
+impl Pattern {
+    /// Rows with nothing in them, as breathing room between primitives.
+    pub fn empty(lanes: usize, rows: usize) -> Self {
+        Self {
+            lanes,
+            rows: vec![vec![false; lanes]; rows],
+        }
+    }
+
+    /// This pattern followed by `other`.
+    pub fn then(mut self, other: Pattern) -> Self {
+        self.rows.extend(other.rows);
+        self
+    }
+
+    /// Both patterns at once, blocked wherever either one is.
+    pub fn overlay(mut self, other: Pattern) -> Self {
+        if other.rows.len() > self.rows.len() {
+            self.rows.resize(other.rows.len(), vec![false; self.lanes]);
+        }
+        for (row, other_row) in self.rows.iter_mut().zip(other.rows) {
+            for (cell, other_cell) in row.iter_mut().zip(other_row) {
+                *cell |= other_cell;
+            }
+        }
+        self
+    }
+
+    /// Lane offsets where the player fits between the enemies of a row.
+    fn open_positions(&self, row: &[bool], player_lanes: usize) -> Vec<bool> {
+        (0..self.lanes)
+            .map(|start| {
+                start + player_lanes <= self.lanes
+                    && row[start..start + player_lanes]
+                        .iter()
+                        .all(|&blocked| !blocked)
+            })
+            .collect()
+    }
+
+    /// Walks the rows keeping every lane offset the player could be at while
+    /// each one passes, and checks at least one survives to the end.
+    pub fn is_solvable(&self, reach: Reach) -> bool {
+        let mut reachable = vec![true; self.lanes];
+        for row in &self.rows {
+            let open = self.open_positions(row, reach.player_lanes);
+            let next: Vec<bool> = (0..self.lanes)
+                .map(|lane| {
+                    open[lane] && {
+                        let from = lane.saturating_sub(reach.max_shift);
+                        let to = (lane + reach.max_shift).min(self.lanes - 1);
+                        reachable[from..=to].iter().any(|&r| r)
+                    }
+                })
+                .collect();
+            if !next.contains(&true) {
+                return false;
+            }
+            reachable = next;
+        }
+        true
+    }
+}
+
+/// A full-width curtain with one gap that wanders from row to row, never
+/// further than the player can follow.
+pub fn curtain(rng: &mut impl Rng, lanes: usize, rows: usize, gap: usize, reach: Reach) -> Pattern {
+    let gap = gap.clamp(1, lanes);
+    let mut start = rng.random_range(0..=lanes - gap);
+    let mut pattern_rows = Vec::with_capacity(rows);
+    for _ in 0..rows {
+        pattern_rows.push(
+            (0..lanes)
+                .map(|lane| lane < start || lane >= start + gap)
+                .collect(),
+        );
+
+        let shift = reach.max_shift as isize;
+        let step = rng.random_range(-shift..=shift);
+        start = (start as isize + step).clamp(0, (lanes - gap) as isize) as usize;
+    }
+    Pattern {
+        lanes,
+        rows: pattern_rows,
+    }
+}
+
+/// Rolls patterns until one passes the reachability check.
+pub fn first_solvable(
+    attempts: usize,
+    reach: Reach,
+    mut make: impl FnMut() -> Pattern,
+) -> Option<Pattern> {
+    for attempt in 0..attempts {
+        let pattern = make();
+        if pattern.is_solvable(reach) {
+            return Some(pattern);
+        }
+        debug!(attempt, "Rejected a pattern with no way through");
+    }
+    None
+}
+
+/// One row across the screen with a gap of `gap` lanes.
+pub fn wall(rng: &mut impl Rng, lanes: usize, gap: usize) -> Pattern {
+    let gap = gap.clamp(1, lanes);
+    let start = rng.random_range(0..=lanes - gap);
+    Pattern {
+        lanes,
+        rows: vec![
+            (0..lanes)
+                .map(|lane| lane < start || lane >= start + gap)
+                .collect(),
+        ],
+    }
+}
+
+/// A two-lane stream moving one lane per row and bouncing off the edges.
+pub fn stream(rng: &mut impl Rng, lanes: usize, rows: usize) -> Pattern {
+    let mut pattern = Pattern::empty(lanes, rows);
+    if lanes < 2 {
+        return pattern;
+    }
+    let last = lanes - 2;
+    let mut lane = rng.random_range(0..=last);
+    let mut step: isize = if rng.random_bool(0.5) { 1 } else { -1 };
+    for row in &mut pattern.rows {
+        row[lane] = true;
+        row[lane + 1] = true;
+        if (lane == 0 && step < 0) || (lane == last && step > 0) {
+            step = -step;
+        }
+        lane = (lane as isize + step) as usize;
+    }
+    pattern
+}
+
+/// Two opposite arms circling the middle lane, a little further each row.
+pub fn spiral(rng: &mut impl Rng, lanes: usize, rows: usize) -> Pattern {
+    let mut pattern = Pattern::empty(lanes, rows);
+    let center = (lanes as f32 - 1.0) / 2.0;
+    let radius = lanes as f32 / 3.0;
+    let phase = rng.random_range(0.0..TAU);
+    let turn = rng.random_range(0.25..0.5) * if rng.random_bool(0.5) { 1.0 } else { -1.0 };
+    for (index, row) in pattern.rows.iter_mut().enumerate() {
+        let angle = phase + index as f32 * turn;
+        for arm in [0.0, PI] {
+            let lane = (center + radius * (angle + arm).cos()).round();
+            let lane = lane.clamp(0.0, lanes as f32 - 1.0) as usize;
+            row[lane] = true;
+        }
+    }
+    pattern
+}
+
+/// A few random primitives one after another, sometimes with a stream laid
+/// over the top. May well be impossible; check it before use.
+pub fn compose(rng: &mut impl Rng, lanes: usize, gap: usize) -> Pattern {
+    let segments = rng.random_range(2..=4);
+    let mut pattern = Pattern::empty(lanes, 0);
+    for _ in 0..segments {
+        let primitive = Primitive::ALL[rng.random_range(0..Primitive::ALL.len())];
+        let mut segment = match primitive {
+            Primitive::Wall => wall(rng, lanes, gap).then(Pattern::empty(lanes, 1)),
+            Primitive::Stream => stream(rng, lanes, 6),
+            Primitive::Spiral => spiral(rng, lanes, 8),
+        };
+        if rng.random_bool(0.25) {
+            let rows = segment.rows.len();
+            segment = segment.overlay(stream(rng, lanes, rows));
+        }
+        pattern = pattern.then(segment).then(Pattern::empty(lanes, 1));
+    }
+    pattern
+}
@@ -0,0 +1,546 @@
+use std::fs;
+
+use bevy::input::gamepad::GamepadConnectionEvent;
+use bevy::prelude::*;
+use bevy::window::{PrimaryWindow, WindowCloseRequested, WindowFocused};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::bug_report::report_captured;
+use crate::difficulty::{Difficulty, DifficultyPreset};
+use crate::focus::{self, FocusActivated, MenuNav};
+use crate::input_buffer::{InputActivity, LastInputDevice};
+use crate::kiosk::{Kiosk, kiosk_enabled};
+use crate::mutator::{self, Mutator, RunMutators};
+use crate::photo::{self, EnterPhotoMode};
+use crate::practice::Practice;
+use crate::profile::ActiveProfile;
+use crate::prompts::{ButtonPrompt, PromptAction};
+use crate::replay::replay_rendering;
+use crate::reset::ResetRunEvent;
+use crate::rng::{GameRng, RunSeed};
+use crate::settings::Settings;
+use crate::snapshot;
+use crate::stress::stress_enabled;
+use crate::{GameState, RunPhase};
+
+const SUSPENDED_RUN_FILE: &str = "suspended_run.ron"; // In the profile directory
+
+/// A run put aside on quit, continued from the menu.
+#[derive(Serialize, Deserialize)]
+struct SuspendedRun {
+    difficulty: DifficultyPreset,
+    #[serde(default)]
+    mutators: Vec<Mutator>,
+    seed: u64,
+    /// The RNG can't be stored, so it is reseeded with this on save and resume.
+    resume_seed: u64,
+    /// The snapshot scene as RON.
+    scene: String,
+}
+
+/// What the pause screen entries do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PauseAction {
+    Resume,
+    Restart,
+    PhotoMode,
+    Quit,
+    ConfirmQuit,
+    SaveAndQuit,
+    KeepPlaying,
+}
+
+/// Where the player wants to go when leaving a run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum QuitTarget {
+    Menu,
+    Desktop,
+}
+
+// --- Resources ---
+
+/// Set while the "your run will be lost" confirmation is showing.
+#[derive(Resource, Default)]
+struct QuitPrompt(Option<QuitTarget>);
+
+/// Why the game paused itself, shown on the pause screen until it's left.
+#[derive(Resource)]
+struct AutoPaused(&'static str);
+
+/// Set while paused for a controller that disconnected mid-run, until it's
+/// back or the player carries on with the keyboard instead.
+#[derive(Resource)]
+struct WaitingForController;
+
+/// Inserted by the menu to continue the suspended run once the new run has been set up.
+#[derive(Resource)]
+pub struct ResumeRequested;
+
+// --- Components ---
+
+#[derive(Component)]
+struct PauseScreen;
+
+#[derive(Component)]
+struct PauseText;
+
+/// Holds the entries, which change with the quit prompt.
+#[derive(Component)]
+struct PauseEntries;
+
+#[derive(Component)]
+struct PauseEntry(PauseAction);
+
+pub struct PausePlugin;
+
+impl Plugin for PausePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<QuitPrompt>()
+            .add_systems(
+                Update,
+                (
+                    pause_input.run_if(not(report_captured)),
+                    auto_pause.run_if(
+                        not(stress_enabled)
+                            .and(not(kiosk_enabled))
+                            .and(not(replay_rendering)),
+                    ),
+                    pause_on_disconnect,
+                )
+                    .run_if(in_state(RunPhase::Alive)),
+            )
+            .add_systems(OnEnter(RunPhase::Paused), enter_pause)
+            .add_systems(
+                Update,
+                (
+                    paused_input.run_if(photo::photo_mode_inactive.and(not(report_captured))),
+                    resume_on_reconnect.run_if(resource_exists::<WaitingForController>),
+                    (update_pause_text, rebuild_pause_entries)
+                        .run_if(resource_changed::<QuitPrompt>),
+                )
+                    .chain()
+                    .run_if(in_state(RunPhase::Paused)),
+            )
+            .add_systems(OnExit(RunPhase::Paused), exit_pause)
+            .add_systems(Update, handle_close_requests)
+            .add_systems(
+                OnEnter(RunPhase::Alive),
+                resume_suspended_run.run_if(resource_exists::<ResumeRequested>),
+            );
+    }
+}
+
+/// Whether the active profile has a run waiting to be continued.
+pub fn has_suspended_run(profile: &ActiveProfile) -> bool {
+    profile.dir().join(SUSPENDED_RUN_FILE).exists()
+}
+
+/// System to pause the run with Escape or a gamepad's Start button
+fn pause_input(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    mut next_phase: ResMut<NextState<RunPhase>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Escape)
+        || gamepads
+            .iter()
+            .any(|gamepad| gamepad.just_pressed(GamepadButton::Start))
+    {
+        next_phase.set(RunPhase::Paused);
+    }
+}
+
+/// System that pauses a run nobody is playing: the window lost focus, or
+/// there's been no input for a while
+fn auto_pause(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    activity: Res<InputActivity>,
+    mut focus_events: EventReader<WindowFocused>,
+    primary_query: Query<(), With<PrimaryWindow>>,
+    mut next_phase: ResMut<NextState<RunPhase>>,
+) {
+    let unfocused = focus_events
+        .read()
+        .any(|event| !event.focused && primary_query.contains(event.window));
+    let idle = settings.afk_pause_seconds > 0 && activity.idle >= settings.afk_pause_seconds as f32;
+    let reason = if unfocused && settings.pause_on_focus_loss {
+        "the window lost focus"
+    } else if idle {
+        "no input for a while"
+    } else {
+        return;
+    };
+    info!(reason, "Auto-paused");
+    commands.insert_resource(AutoPaused(reason));
+    next_phase.set(RunPhase::Paused);
+}
+
+/// System that pauses the run when the controller being played with
+/// disconnects
+fn pause_on_disconnect(
+    mut commands: Commands,
+    device: Res<LastInputDevice>,
+    mut connection_events: EventReader<GamepadConnectionEvent>,
+    mut next_phase: ResMut<NextState<RunPhase>>,
+) {
+    let disconnected = connection_events.read().any(|event| event.disconnected());
+    if !disconnected || *device != LastInputDevice::Gamepad {
+        return;
+    }
+    info!("Auto-paused: the controller disconnected");
+    commands.insert_resource(AutoPaused("controller disconnected"));
+    commands.insert_resource(WaitingForController);
+    next_phase.set(RunPhase::Paused);
+}
+
+/// System that carries on once a controller is plugged back in, or the
+/// keyboard is used instead
+fn resume_on_reconnect(
+    device: Res<LastInputDevice>,
+    prompt: Res<QuitPrompt>,
+    mut connection_events: EventReader<GamepadConnectionEvent>,
+    mut next_phase: ResMut<NextState<RunPhase>>,
+) {
+    let reconnected = connection_events.read().any(|event| event.connected());
+    let keyboard = device.is_changed() && *device == LastInputDevice::Keyboard;
+    // Someone asking to quit gets their answer first
+    if (reconnected || keyboard) && prompt.0.is_none() {
+        next_phase.set(RunPhase::Alive);
+    }
+}
+
+/// System that freezes time and shows the pause screen
+fn enter_pause(
+    mut commands: Commands,
+    mut time: ResMut<Time<Virtual>>,
+    mut prompt: ResMut<QuitPrompt>,
+) {
+    time.pause();
+    // Fills in the heading and entries once they're spawned
+    prompt.set_changed();
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Percent(40.0),
+                width: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(12.0),
+                ..default()
+            },
+            PauseScreen,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::default(),
+                TextLayout::new_with_justify(JustifyText::Center),
+                PauseText,
+            ));
+            parent.spawn((
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(4.0),
+                    ..default()
+                },
+                PauseEntries,
+            ));
+            parent.spawn(ButtonPrompt::new(PromptAction::Back, "to resume"));
+        });
+}
+
+/// System to resume, or ask before quitting the run
+fn paused_input(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut activated: EventReader<FocusActivated>,
+    nav: Res<MenuNav>,
+    settings: Res<Settings>,
+    practice: Res<Practice>,
+    entry_query: Query<&PauseEntry>,
+    mut prompt: ResMut<QuitPrompt>,
+    mut next_phase: ResMut<NextState<RunPhase>>,
+    mut resets: EventWriter<ResetRunEvent>,
+    mut photo_mode: EventWriter<EnterPhotoMode>,
+) {
+    let shortcuts = if prompt.0.is_some() {
+        [
+            (KeyCode::KeyY, PauseAction::ConfirmQuit),
+            (KeyCode::KeyS, PauseAction::SaveAndQuit),
+            (KeyCode::KeyN, PauseAction::KeepPlaying),
+        ]
+        .as_slice()
+    } else {
+        [
+            (KeyCode::KeyR, PauseAction::Restart),
+            (KeyCode::KeyF, PauseAction::PhotoMode),
+            (KeyCode::KeyQ, PauseAction::Quit),
+        ]
+        .as_slice()
+    };
+    let mut actions: Vec<PauseAction> = shortcuts
+        .iter()
+        .filter(|&&(key, _)| keyboard_input.just_pressed(key))
+        .map(|&(_, action)| action)
+        .collect();
+    if nav.back {
+        actions.push(if prompt.0.is_some() {
+            PauseAction::KeepPlaying
+        } else {
+            PauseAction::Resume
+        });
+    }
+    actions.extend(
+        activated
+            .read()
+            .filter_map(|event| entry_query.get(event.0).ok())
+            .map(|entry| entry.0),
+    );
+
+    // One action per frame, the screen changes under the rest
+    let Some(action) = actions.first() else {
+        return;
+    };
+    match (action, prompt.0) {
+        (PauseAction::Resume, None) => next_phase.set(RunPhase::Alive),
+        (PauseAction::Restart, None) => {
+            resets.write(ResetRunEvent);
+        }
+        (PauseAction::PhotoMode, None) => {
+            photo_mode.write(EnterPhotoMode);
+        }
+        (PauseAction::Quit, None) => request_quit(
+            &mut commands,
+            &settings,
+            &practice,
+            &mut prompt,
+            QuitTarget::Menu,
+        ),
+        (PauseAction::ConfirmQuit, Some(target)) => quit(&mut commands, target, false),
+        (PauseAction::SaveAndQuit, Some(target)) if !practice.enabled => {
+            quit(&mut commands, target, true);
+        }
+        (PauseAction::KeepPlaying, Some(_)) => prompt.0 = None,
+        _ => {}
+    }
+}
+
+/// Quits straight away when runs are auto-saved, otherwise asks first.
+fn request_quit(
+    commands: &mut Commands,
+    settings: &Settings,
+    practice: &Practice,
+    prompt: &mut QuitPrompt,
+    target: QuitTarget,
+) {
+    if settings.autosave_on_quit && !practice.enabled {
+        quit(commands, target, true);
+    } else {
+        prompt.0 = Some(target);
+    }
+}
+
+/// Leaves the run, optionally saving it to continue later.
+fn quit(commands: &mut Commands, target: QuitTarget, suspend: bool) {
+    commands.queue(move |world: &mut World| {
+        if suspend {
+            suspend_run(world);
+        }
+        match target {
+            QuitTarget::Menu => world
+                .resource_mut::<NextState<GameState>>()
+                .set(GameState::Menu),
+            QuitTarget::Desktop => {
+                world.send_event(AppExit::Success);
+            }
+        }
+    });
+}
+
+/// System that redraws the pause screen heading
+fn update_pause_text(
+    prompt: Res<QuitPrompt>,
+    auto_paused: Option<Res<AutoPaused>>,
+    waiting: Option<Res<WaitingForController>>,
+    mut query: Query<&mut Text, With<PauseText>>,
+) {
+    let Ok(mut text) = query.single_mut() else {
+        return;
+    };
+    text.0 = match prompt.0 {
+        None => match (auto_paused, waiting) {
+            (Some(auto_paused), Some(_)) => format!(
+                "Paused: {}\nReconnect it, or press a key to carry on with the keyboard",
+                auto_paused.0
+            ),
+            (Some(auto_paused), None) => format!("Paused: {}", auto_paused.0),
+            (None, _) => "Paused".to_string(),
+        },
+        Some(QuitTarget::Menu) => "Quit to the menu? Your run will be lost.".to_string(),
+        Some(QuitTarget::Desktop) => "Quit the game? Your run will be lost.".to_string(),
+    };
+}
+
+/// System that swaps the entries between the pause menu and the quit prompt
+fn rebuild_pause_entries(
+    mut commands: Commands,
+    prompt: Res<QuitPrompt>,
+    practice: Res<Practice>,
+    container_query: Query<Entity, With<PauseEntries>>,
+) {
+    let Ok(container) = container_query.single() else {
+        return;
+    };
+    // Keeping the run is listed first, so a stray confirm never loses it
+    let mut entries = if prompt.0.is_some() {
+        vec![
+            ("Keep playing (N)", PauseAction::KeepPlaying),
+            ("Quit (Y)", PauseAction::ConfirmQuit),
+        ]
+    } else {
+        vec![
+            ("Resume (Esc)", PauseAction::Resume),
+            ("Restart run (R)", PauseAction::Restart),
+            ("Photo mode (F)", PauseAction::PhotoMode),
+            ("Quit to menu (Q)", PauseAction::Quit),
+        ]
+    };
+    if prompt.0.is_some() && !practice.enabled {
+        entries.push(("Save run and quit (S)", PauseAction::SaveAndQuit));
+    }
+
+    commands
+        .entity(container)
+        .despawn_related::<Children>()
+        .with_children(|parent| {
+            for (order, (label, action)) in entries.into_iter().enumerate() {
+                parent.spawn((focus::entry(label, order), PauseEntry(action)));
+            }
+        });
+}
+
+/// System that unfreezes time and removes the pause screen
+fn exit_pause(
+    mut commands: Commands,
+    mut time: ResMut<Time<Virtual>>,
+    mut prompt: ResMut<QuitPrompt>,
+    query: Query<Entity, With<PauseScreen>>,
+) {
+    time.unpause();
+    prompt.0 = None;
+    commands.remove_resource::<AutoPaused>();
+    commands.remove_resource::<WaitingForController>();
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// System that turns window close requests into a quit prompt during a run
+fn handle_close_requests(
+    mut commands: Commands,
+    mut close_events: EventReader<WindowCloseRequested>,
+    phase: Option<Res<State<RunPhase>>>,
+    settings: Res<Settings>,
+    practice: Res<Practice>,
+    kiosk: Res<Kiosk>,
+    mut prompt: ResMut<QuitPrompt>,
+    mut next_phase: ResMut<NextState<RunPhase>>,
+    mut app_exit: EventWriter<AppExit>,
+    primary_query: Query<(), With<PrimaryWindow>>,
+) {
+    // Other windows, like the stream overlay, close on their own
+    let closing = close_events
+        .read()
+        .any(|event| primary_query.contains(event.window));
+    // Exhibition machines are stopped by whoever runs them, not by visitors
+    if !closing || kiosk.enabled {
+        return;
+    }
+    match phase.map(|phase| *phase.get()) {
+        Some(RunPhase::Alive | RunPhase::Paused) => {
+            next_phase.set(RunPhase::Paused);
+            request_quit(
+                &mut commands,
+                &settings,
+                &practice,
+                &mut prompt,
+                QuitTarget::Desktop,
+            );
+        }
+        _ => {
+            app_exit.write(AppExit::Success);
+        }
+    }
+}
+
+/// Exclusive system that writes the run to the profile to continue later
+fn suspend_run(world: &mut World) {
+    let scene = snapshot::capture(world);
+    let scene = match snapshot::to_ron(world, &scene) {
+        Ok(scene) => scene,
+        Err(err) => {
+            error!("Could not save the run: {err}");
+            return;
+        }
+    };
+    // Reseed so the continued run rolls the same as this one would have
+    let resume_seed = world.resource_mut::<GameRng>().0.random();
+    world.resource_mut::<GameRng>().0 = StdRng::seed_from_u64(resume_seed);
+
+    let run = SuspendedRun {
+        difficulty: world.resource::<Difficulty>().preset,
+        mutators: world.resource::<RunMutators>().0.clone(),
+        seed: world.resource::<RunSeed>().seed,
+        resume_seed,
+        scene,
+    };
+    world
+        .resource::<ActiveProfile>()
+        .save(SUSPENDED_RUN_FILE, &run);
+    info!("Run saved to continue later");
+}
+
+/// Exclusive system that replaces the freshly set up run with the suspended one
+fn resume_suspended_run(world: &mut World) {
+    world.remove_resource::<ResumeRequested>();
+    let path = world
+        .resource::<ActiveProfile>()
+        .dir()
+        .join(SUSPENDED_RUN_FILE);
+    let run =
+        match fs::read_to_string(&path).map(|contents| ron::from_str::<SuspendedRun>(&contents)) {
+            Ok(Ok(run)) => run,
+            Ok(Err(err)) => {
+                error!("Ignoring corrupt suspended run: {err}");
+                let _ = fs::remove_file(&path);
+                return;
+            }
+            Err(err) => {
+                warn!("Could not read the suspended run: {err}");
+                return;
+            }
+        };
+    // A run can only be continued once
+    if let Err(err) = fs::remove_file(&path) {
+        warn!("Could not remove the suspended run: {err}");
+    }
+
+    match snapshot::from_ron(world, &run.scene) {
+        Ok(scene) => snapshot::restore(world, &scene),
+        Err(err) => {
+            error!("Could not continue the saved run: {err}");
+            return;
+        }
+    }
+    let mut difficulty = Difficulty::load(run.difficulty);
+    mutator::apply(&run.mutators, &mut difficulty.config);
+    *world.resource_mut::<Difficulty>() = difficulty;
+    world.resource_mut::<RunMutators>().0 = run.mutators;
+    world.resource_mut::<RunSeed>().seed = run.seed;
+    world.resource_mut::<GameRng>().0 = StdRng::seed_from_u64(run.resume_seed);
+    info!("Continuing saved run");
+}
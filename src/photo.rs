@@ -0,0 +1,194 @@
+use std::fs;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::prelude::*;
+use bevy::render::view::screenshot::{Screenshot, save_to_disk};
+use bevy::window::{PrimaryWindow, WindowCloseRequested};
+
+use crate::RunPhase;
+use crate::camera::GameCamera;
+use crate::save;
+
+// Photo mode constants
+const PHOTOS_DIR: &str = "photos";
+const PAN_SPEED: f32 = 400.0; // Pixels per second at normal zoom
+const ZOOM_STEP: f32 = 1.1; // Zoom factor per scroll line or key press
+const MIN_ZOOM: f32 = 0.25;
+const MAX_ZOOM: f32 = 3.0;
+const SHUTTER_KEY: KeyCode = KeyCode::Enter;
+
+// --- Resources ---
+
+/// Present while photo mode is on. Holds everything it changed, so leaving
+/// puts the camera and UI back exactly as they were.
+#[derive(Resource)]
+struct PhotoMode {
+    camera: (Transform, Projection),
+    hidden_ui: Vec<(Entity, Visibility)>,
+}
+
+// --- Events ---
+
+/// Sent by the pause screen to switch to the free photo camera.
+#[derive(Event)]
+pub struct EnterPhotoMode;
+
+pub struct PhotoPlugin;
+
+impl Plugin for PhotoPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<EnterPhotoMode>()
+            .add_systems(
+                Update,
+                (
+                    enter_photo_mode.run_if(on_event::<EnterPhotoMode>),
+                    (
+                        move_photo_camera,
+                        take_photo,
+                        restore_photo_mode.run_if(leave_requested),
+                    )
+                        .chain()
+                        .run_if(resource_exists::<PhotoMode>),
+                )
+                    .chain()
+                    .run_if(in_state(RunPhase::Paused)),
+            )
+            .add_systems(
+                OnExit(RunPhase::Paused),
+                restore_photo_mode.run_if(resource_exists::<PhotoMode>),
+            );
+    }
+}
+
+/// Run condition for the pause screen, which stands aside during photo mode.
+pub fn photo_mode_inactive(photo_mode: Option<Res<PhotoMode>>) -> bool {
+    photo_mode.is_none()
+}
+
+/// System that saves the camera, hides the UI and hands the camera over
+fn enter_photo_mode(
+    mut commands: Commands,
+    camera_query: Query<(&Transform, &Projection), With<GameCamera>>,
+    mut ui_query: Query<(Entity, &mut Visibility), (With<Node>, Without<ChildOf>)>,
+) {
+    let Ok((transform, projection)) = camera_query.single() else {
+        return;
+    };
+    let hidden_ui = ui_query
+        .iter_mut()
+        .map(|(entity, mut visibility)| {
+            (
+                entity,
+                std::mem::replace(&mut *visibility, Visibility::Hidden),
+            )
+        })
+        .collect();
+    commands.insert_resource(PhotoMode {
+        camera: (*transform, projection.clone()),
+        hidden_ui,
+    });
+    info!(
+        "Photo mode: arrows/WASD to pan, scroll or +/- to zoom, {SHUTTER_KEY:?} for a photo, Esc to leave"
+    );
+}
+
+/// System to pan and zoom the photo camera on real time, as the run is paused
+fn move_photo_camera(
+    real_time: Res<Time<Real>>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut wheel: EventReader<MouseWheel>,
+    mut camera_query: Query<(&mut Transform, &mut Projection), With<GameCamera>>,
+) {
+    let Ok((mut transform, mut projection)) = camera_query.single_mut() else {
+        return;
+    };
+    let Projection::Orthographic(ortho) = &mut *projection else {
+        return;
+    };
+
+    let mut zoom_steps: f32 = wheel
+        .read()
+        .map(|event| match event.unit {
+            MouseScrollUnit::Line => event.y,
+            MouseScrollUnit::Pixel => event.y / 40.0,
+        })
+        .sum();
+    if keyboard_input.just_pressed(KeyCode::Equal) {
+        zoom_steps += 1.0;
+    }
+    if keyboard_input.just_pressed(KeyCode::Minus) {
+        zoom_steps -= 1.0;
+    }
+    // Scrolling up zooms in, which shrinks the projection
+    ortho.scale = (ortho.scale * ZOOM_STEP.powf(-zoom_steps)).clamp(MIN_ZOOM, MAX_ZOOM);
+
+    let mut direction = Vec2::ZERO;
+    if keyboard_input.any_pressed([KeyCode::ArrowLeft, KeyCode::KeyA]) {
+        direction.x -= 1.0;
+    }
+    if keyboard_input.any_pressed([KeyCode::ArrowRight, KeyCode::KeyD]) {
+        direction.x += 1.0;
+    }
+    if keyboard_input.any_pressed([KeyCode::ArrowDown, KeyCode::KeyS]) {
+        direction.y -= 1.0;
+    }
+    if keyboard_input.any_pressed([KeyCode::ArrowUp, KeyCode::KeyW]) {
+        direction.y += 1.0;
+    }
+    // Pan the same distance on screen whatever the zoom
+    let step = direction.normalize_or_zero() * PAN_SPEED * ortho.scale * real_time.delta_secs();
+    transform.translation += step.extend(0.0);
+}
+
+/// System that saves a screenshot to the photos folder
+fn take_photo(mut commands: Commands, keyboard_input: Res<ButtonInput<KeyCode>>) {
+    if !keyboard_input.just_pressed(SHUTTER_KEY) {
+        return;
+    }
+    let taken = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis())
+        .unwrap_or_default();
+    let dir = save::data_dir().join(PHOTOS_DIR);
+    if let Err(err) = fs::create_dir_all(&dir) {
+        error!(path = %dir.display(), "Could not create the photos folder: {err}");
+        return;
+    }
+    let path = dir.join(format!("photo-{taken}.png"));
+    info!(path = %path.display(), "Photo taken");
+    commands
+        .spawn(Screenshot::primary_window())
+        .observe(save_to_disk(path));
+}
+
+/// Goes back to the pause screen on Escape, or when the window is being
+/// closed so the quit prompt can be seen.
+fn leave_requested(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut close_events: EventReader<WindowCloseRequested>,
+    primary_query: Query<(), With<PrimaryWindow>>,
+) -> bool {
+    let closing = close_events
+        .read()
+        .any(|event| primary_query.contains(event.window));
+    keyboard_input.just_pressed(KeyCode::Escape) || closing
+}
+
+/// System that puts the camera and UI back and ends photo mode
+fn restore_photo_mode(
+    mut commands: Commands,
+    photo_mode: Res<PhotoMode>,
+    mut camera_query: Query<(&mut Transform, &mut Projection), With<GameCamera>>,
+    mut visibility_query: Query<&mut Visibility>,
+) {
+    if let Ok((mut transform, mut projection)) = camera_query.single_mut() {
+        (*transform, *projection) = photo_mode.camera.clone();
+    }
+    for &(entity, visibility) in &photo_mode.hidden_ui {
+        if let Ok(mut current) = visibility_query.get_mut(entity) {
+            *current = visibility;
+        }
+    }
+    commands.remove_resource::<PhotoMode>();
+}
@@ -0,0 +1,120 @@
+use std::time::Duration;
+
+use bevy::audio::Volume;
+use bevy::prelude::*;
+use bevy::window::{PrimaryWindow, WindowFocused, WindowOccluded};
+use bevy::winit::{UpdateMode, WinitSettings};
+
+use crate::settings::Settings;
+
+// Power constants
+const BACKGROUND_FRAME_TIME: Duration = Duration::from_millis(100); // Ten updates a second while in the background
+
+// --- Resources ---
+
+/// Whether the game window is in the background: unfocused, minimized or
+/// hidden behind other windows.
+#[derive(Resource, Default)]
+struct Background {
+    unfocused: bool,
+    occluded: bool,
+}
+
+impl Background {
+    fn active(&self) -> bool {
+        self.unfocused || self.occluded
+    }
+}
+
+/// Volume to go back to once the window is in front again.
+#[derive(Resource)]
+struct MutedVolume(Volume);
+
+pub struct PowerPlugin;
+
+impl Plugin for PowerPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(WinitSettings::game())
+            .init_resource::<Background>()
+            .add_systems(
+                PreUpdate,
+                (
+                    track_background,
+                    (apply_update_mode, apply_background_mute)
+                        .run_if(resource_changed::<Background>.or(resource_changed::<Settings>)),
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// System that follows the primary window going to and coming back from the background
+fn track_background(
+    mut focus_events: EventReader<WindowFocused>,
+    mut occluded_events: EventReader<WindowOccluded>,
+    primary_query: Query<(), With<PrimaryWindow>>,
+    mut background: ResMut<Background>,
+) {
+    for event in focus_events.read() {
+        if primary_query.contains(event.window) {
+            background.unfocused = !event.focused;
+        }
+    }
+    for event in occluded_events.read() {
+        if primary_query.contains(event.window) {
+            background.occluded = event.occluded;
+        }
+    }
+}
+
+/// System that drops to a low update rate in the background. Window events
+/// still wake the game at once, so coming back is instant.
+fn apply_update_mode(
+    settings: Res<Settings>,
+    background: Res<Background>,
+    mut winit: ResMut<WinitSettings>,
+) {
+    let low_power = UpdateMode::reactive_low_power(BACKGROUND_FRAME_TIME);
+    let throttle = settings.background_throttle;
+    winit.unfocused_mode = if throttle {
+        low_power
+    } else {
+        UpdateMode::Continuous
+    };
+    // Minimized or covered windows can keep their focus, so the focused mode
+    // has to follow too
+    winit.focused_mode = if throttle && background.active() {
+        low_power
+    } else {
+        UpdateMode::Continuous
+    };
+}
+
+/// System that silences the game in the background and restores its volume after
+fn apply_background_mute(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    background: Res<Background>,
+    muted: Option<Res<MutedVolume>>,
+    mut volume: ResMut<GlobalVolume>,
+    mut sinks: Query<&mut AudioSink>,
+) {
+    let mute = settings.mute_in_background && background.active();
+    match (mute, muted) {
+        (true, None) => {
+            commands.insert_resource(MutedVolume(volume.volume));
+            volume.volume = Volume::SILENT;
+            for mut sink in &mut sinks {
+                sink.mute();
+            }
+        }
+        (false, Some(muted)) => {
+            volume.volume = muted.0;
+            commands.remove_resource::<MutedVolume>();
+            for mut sink in &mut sinks {
+                sink.unmute();
+            }
+        }
+        _ => {}
+    }
+}
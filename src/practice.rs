@@ -0,0 +1,124 @@
+use bevy::input::common_conditions::input_just_pressed;
+use bevy::prelude::*;
+use rand::rngs::StdRng;
+
+use crate::RunPhase;
+use crate::reset::RunSetup;
+use crate::rng::GameRng;
+use crate::snapshot;
+use crate::stats::{self, RunStats};
+
+// Practice constants
+const CHECKPOINTS: [u32; 5] = [0, 1, 2, 3, 5]; // Minutes into the ramp practice runs can start at
+
+// --- Resources ---
+
+/// Whether runs are practice runs: quicksaves are available and nothing is
+/// recorded to the profile.
+#[derive(Resource, Default)]
+pub struct Practice {
+    pub enabled: bool,
+    /// Index into `CHECKPOINTS` of where practice runs start.
+    checkpoint: usize,
+}
+
+impl Practice {
+    /// Minutes of the run skipped at the start of practice runs.
+    pub fn checkpoint_minutes(&self) -> u32 {
+        CHECKPOINTS[self.checkpoint]
+    }
+
+    pub fn checkpoint(&self) -> usize {
+        self.checkpoint
+    }
+
+    pub fn set_checkpoint(&mut self, checkpoint: usize) {
+        self.checkpoint = checkpoint.min(CHECKPOINTS.len() - 1);
+    }
+
+    pub fn next_checkpoint(&mut self) {
+        self.checkpoint = (self.checkpoint + 1) % CHECKPOINTS.len();
+    }
+
+    pub fn checkpoint_name(&self) -> String {
+        match self.checkpoint_minutes() {
+            0 => "Start".to_string(),
+            minutes => format!("Minute {minutes}"),
+        }
+    }
+}
+
+/// A snapshot of the simulation taken with F5 and restored with F8.
+#[derive(Resource)]
+struct QuickSave {
+    scene: DynamicScene,
+    // The RNG can't be reflected, so it's stored alongside the scene
+    rng: StdRng,
+}
+
+pub struct PracticePlugin;
+
+impl Plugin for PracticePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Practice>()
+            .add_systems(
+                RunSetup,
+                (
+                    clear_quicksave,
+                    start_at_checkpoint
+                        .after(stats::reset_run_stats)
+                        .run_if(practice_enabled),
+                ),
+            )
+            .add_systems(
+                Update,
+                (
+                    quicksave.run_if(input_just_pressed(KeyCode::F5)),
+                    quickload.run_if(input_just_pressed(KeyCode::F8)),
+                )
+                    .run_if(practice_enabled.and(in_state(RunPhase::Alive))),
+            );
+    }
+}
+
+pub fn practice_enabled(practice: Res<Practice>) -> bool {
+    practice.enabled
+}
+
+/// System to forget the previous run's quicksave
+fn clear_quicksave(mut commands: Commands) {
+    commands.remove_resource::<QuickSave>();
+}
+
+/// System that starts practice runs part way up the difficulty ramp, which
+/// follows the run's time
+fn start_at_checkpoint(practice: Res<Practice>, mut stats: ResMut<RunStats>) {
+    let minutes = practice.checkpoint_minutes();
+    if minutes > 0 {
+        stats.skip_to(minutes as f32 * 60.0);
+        info!(minutes, "Practice run starting at a checkpoint");
+    }
+}
+
+/// Exclusive system that snapshots gameplay entities and resources
+fn quicksave(world: &mut World) {
+    let scene = snapshot::capture(world);
+    let rng = world.resource::<GameRng>().0.clone();
+
+    info!(entities = scene.entities.len(), "Quicksaved");
+    world.insert_resource(QuickSave { scene, rng });
+}
+
+/// Exclusive system that replaces the current simulation with the quicksave
+fn quickload(world: &mut World) {
+    let Some(save) = world.remove_resource::<QuickSave>() else {
+        return;
+    };
+
+    snapshot::restore(world, &save.scene);
+    world.resource_mut::<GameRng>().0 = save.rng.clone();
+
+    // Keep the save so it can be loaded again
+    world.insert_resource(save);
+    info!("Quickloaded");
+}
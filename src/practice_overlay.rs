@@ -0,0 +1,210 @@
+use bevy::input::common_conditions::input_just_pressed;
+use bevy::prelude::*;
+
+use crate::bomb::Bombs;
+use crate::graze::GrazeMeter;
+use crate::hud_layout::{HudAnchor, HudSlot};
+use crate::practice::practice_enabled;
+use crate::recording::{InputFrame, InputRecording};
+use crate::reset::{RunScoped, RunSetup};
+use crate::shield::{ShieldBubble, TemporaryShield};
+use crate::text_style::TextStyleLibrary;
+use crate::{Enemy, GameState, Player, RunPhase, Velocity};
+
+// Practice overlay constants
+const SPEEDS: [f32; 4] = [1.0, 0.5, 0.25, 0.1]; // Game speeds F9 steps through
+const TIMELINE_FRAMES: usize = 40; // Recorded frames shown in the input timeline
+const MAX_THREATS: usize = 3;
+const THREAT_HORIZON: f32 = 3.0; // Seconds ahead an enemy counts as incoming
+const OVERLAY_BACKGROUND: Color = Color::srgba(0.0, 0.0, 0.0, 0.6);
+
+// --- Components ---
+
+#[derive(Component)]
+#[require(RunScoped)]
+struct PracticeOverlay;
+
+#[derive(Component)]
+struct PracticeOverlayText;
+
+// --- Resources ---
+
+/// Game speed of practice runs, slowed down to study patterns frame by frame.
+#[derive(Resource, Default)]
+struct PracticeSpeed {
+    /// Index into `SPEEDS`.
+    step: usize,
+}
+
+impl PracticeSpeed {
+    fn scale(&self) -> f32 {
+        SPEEDS[self.step]
+    }
+
+    fn slowed(&self) -> bool {
+        self.step > 0
+    }
+}
+
+pub struct PracticeOverlayPlugin;
+
+impl Plugin for PracticeOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PracticeSpeed>()
+            .add_systems(
+                RunSetup,
+                (
+                    reset_practice_speed,
+                    spawn_practice_overlay.run_if(practice_enabled),
+                ),
+            )
+            .add_systems(
+                Update,
+                (
+                    cycle_practice_speed.run_if(input_just_pressed(KeyCode::F9)),
+                    update_practice_overlay,
+                )
+                    .chain()
+                    .run_if(practice_enabled.and(in_state(RunPhase::Alive))),
+            )
+            .add_systems(OnExit(GameState::Playing), reset_practice_speed);
+    }
+}
+
+/// System that puts the game back to full speed
+fn reset_practice_speed(mut speed: ResMut<PracticeSpeed>, mut time: ResMut<Time<Virtual>>) {
+    speed.step = 0;
+    time.set_relative_speed(1.0);
+}
+
+/// System to spawn the overlay, hidden until the game is slowed down
+fn spawn_practice_overlay(mut commands: Commands, styles: Res<TextStyleLibrary>) {
+    commands.spawn((
+        Node {
+            padding: UiRect::all(Val::Px(6.0)),
+            ..default()
+        },
+        BackgroundColor(OVERLAY_BACKGROUND),
+        Visibility::Hidden,
+        HudSlot::new(HudAnchor::Left, 0),
+        PracticeOverlay,
+        children![(Text::default(), styles.body.ui(), PracticeOverlayText)],
+    ));
+}
+
+/// System to step down through the practice speeds, back to full speed
+fn cycle_practice_speed(mut speed: ResMut<PracticeSpeed>, mut time: ResMut<Time<Virtual>>) {
+    speed.step = (speed.step + 1) % SPEEDS.len();
+    time.set_relative_speed(speed.scale());
+    info!(speed = speed.scale(), "Practice speed");
+}
+
+/// System that shows the recorded input, the player's resources and the
+/// enemies about to reach the player's row while the game is slowed
+fn update_practice_overlay(
+    speed: Res<PracticeSpeed>,
+    recording: Res<InputRecording>,
+    bombs: Res<Bombs>,
+    meter: Res<GrazeMeter>,
+    player_query: Query<(&Transform, Has<ShieldBubble>, Option<&TemporaryShield>), With<Player>>,
+    enemy_query: Query<(&Transform, &Velocity), With<Enemy>>,
+    mut overlay_query: Query<&mut Visibility, With<PracticeOverlay>>,
+    mut text_query: Query<&mut Text, With<PracticeOverlayText>>,
+) {
+    let Ok(mut visibility) = overlay_query.single_mut() else {
+        return;
+    };
+    let shown = if speed.slowed() {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    visibility.set_if_neq(shown);
+    if !speed.slowed() {
+        return;
+    }
+    let (Ok(mut text), Ok((player, bubble, temporary))) =
+        (text_query.single_mut(), player_query.single())
+    else {
+        return;
+    };
+
+    let frames = recording.last(TIMELINE_FRAMES);
+    let current = frames.last().map(|&(_, frame)| frame).unwrap_or_default();
+    let timeline: String = frames
+        .iter()
+        .map(|&(_, frame)| input_glyph(frame))
+        .collect();
+    let shield = match (bubble, temporary) {
+        (true, _) => "bubble".to_string(),
+        (false, Some(shield)) => format!("{:.1}s", shield.remaining_secs()),
+        (false, None) => "none".to_string(),
+    };
+    let mut lines = vec![
+        format!("Practice speed {:.0}% (F9)", speed.scale() * 100.0),
+        format!(
+            "Input: {} {} {} {} {}",
+            held(current.left, "Left"),
+            held(current.right, "Right"),
+            held(current.up, "Up"),
+            held(current.down, "Down"),
+            held(current.bomb, "Bomb")
+        ),
+        format!("Last {TIMELINE_FRAMES} frames: {timeline}"),
+        format!(
+            "Bombs: {}   Graze: {:.0}%   Shield: {shield}",
+            bombs.count,
+            meter.value * 100.0
+        ),
+        "Incoming:".to_string(),
+    ];
+
+    // How far to the side each enemy will be when it reaches the player's row
+    let position = player.translation.truncate();
+    let mut threats: Vec<(f32, f32)> = enemy_query
+        .iter()
+        .filter_map(|(transform, velocity)| {
+            let offset = transform.translation.truncate() - position;
+            if velocity.0.y >= 0.0 || offset.y <= 0.0 {
+                return None;
+            }
+            let arrival = offset.y / -velocity.0.y;
+            (arrival <= THREAT_HORIZON).then(|| (arrival, offset.x + velocity.0.x * arrival))
+        })
+        .collect();
+    threats.sort_by(|a, b| a.0.total_cmp(&b.0));
+    if threats.is_empty() {
+        lines.push("  nothing".to_string());
+    }
+    for (arrival, side) in threats.into_iter().take(MAX_THREATS) {
+        lines.push(format!("  in {arrival:.2}s, {side:+.0}px to the side"));
+    }
+    text.0 = lines.join("\n");
+}
+
+fn held(pressed: bool, name: &str) -> String {
+    if pressed {
+        format!("[{name}]")
+    } else {
+        format!(" {name} ")
+    }
+}
+
+/// One character per frame: the direction held, `B` for a bomb, `.` for nothing.
+fn input_glyph(frame: InputFrame) -> char {
+    if frame.bomb {
+        return 'B';
+    }
+    match (
+        frame.left != frame.right,
+        frame.left,
+        frame.up != frame.down,
+        frame.up,
+    ) {
+        (true, true, _, _) => '<',
+        (true, false, _, _) => '>',
+        (false, _, true, true) => '^',
+        (false, _, true, false) => 'v',
+        _ => '.',
+    }
+}
@@ -0,0 +1,496 @@
+use std::fs;
+use std::path::PathBuf;
+
+use bevy::ecs::system::NonSendMarker;
+use bevy::input::keyboard::KeyboardInput;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::GameState;
+use crate::crash;
+use crate::difficulty::Difficulty;
+use crate::export::{self, EXPORT_EXTENSION};
+use crate::heatmap::DeathHeatmap;
+use crate::input_buffer::{BufferedAction, InputBuffer};
+use crate::kiosk::kiosk_enabled;
+use crate::mutator::RunMutators;
+use crate::practice::practice_enabled;
+use crate::save::{self, Versioned};
+use crate::score::{HighScoreEntry, HighScores, Score};
+use crate::settings::Settings;
+use crate::splits::PersonalBests;
+use crate::stats::RunStats;
+use crate::text_input::{self, TextInputAction};
+
+// Profile constants
+const PROFILES_DIR: &str = "profiles";
+pub const SETTINGS_FILE: &str = "settings.ron";
+pub const PROGRESS_FILE: &str = "progress.ron";
+pub const HIGH_SCORES_FILE: &str = "high_scores.ron";
+pub const DEATHS_FILE: &str = "deaths.ron";
+pub const SPLITS_FILE: &str = "splits.ron";
+const DEFAULT_PROFILE: &str = "Player";
+pub const MAX_NAME_LEN: usize = 16;
+
+// --- Resources ---
+
+/// Profile the current session plays and saves under.
+#[derive(Resource, Default)]
+pub struct ActiveProfile {
+    pub name: String,
+}
+
+impl ActiveProfile {
+    pub fn dir(&self) -> PathBuf {
+        profile_dir(&self.name)
+    }
+
+    /// Writes one of this profile's save files, reporting failures.
+    pub fn save<T: Serialize>(&self, file: &str, value: &T) {
+        if self.name.is_empty() {
+            return;
+        }
+        if let Err(err) = save::store(&self.dir().join(file), value) {
+            warn!(profile = %self.name, file, "Failed to save: {err}");
+        }
+    }
+}
+
+/// Long-term progress of a profile across runs.
+#[derive(Resource, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Progress {
+    #[serde(default)]
+    version: u32,
+    pub runs_played: u32,
+    pub best_score: u32,
+    pub time_played: f32,
+    /// Total XP towards the progression track.
+    pub xp: u32,
+    /// Version whose changelog the profile last read.
+    pub seen_version: String,
+}
+
+impl Default for Progress {
+    fn default() -> Self {
+        Self {
+            version: Self::VERSION,
+            runs_played: 0,
+            best_score: 0,
+            time_played: 0.0,
+            xp: 0,
+            seen_version: String::new(),
+        }
+    }
+}
+
+impl Versioned for Progress {
+    const VERSION: u32 = 1;
+
+    fn version(&self) -> u32 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u32) {
+        self.version = version;
+    }
+
+    // Version 1 only added the version field
+    fn migrate(&mut self, _from: u32) {}
+}
+
+/// State of the profile-selection screen.
+#[derive(Resource, Default)]
+struct ProfileMenu {
+    profiles: Vec<ProfileSummary>,
+    selected: usize,
+    editing: Option<NameEdit>,
+    confirm_delete: bool,
+    loading: bool,
+    error: Option<String>,
+    notice: Option<String>,
+}
+
+struct ProfileSummary {
+    name: String,
+    best_score: u32,
+}
+
+/// A profile name being typed, either for a new profile or to rename one.
+struct NameEdit {
+    buffer: String,
+    renaming: Option<String>,
+}
+
+// --- Events ---
+
+/// Sent when the player picks a profile to play with.
+#[derive(Event)]
+pub struct ProfileChosen(pub String);
+
+// --- Components ---
+
+#[derive(Component)]
+struct ProfileMenuText;
+
+pub struct ProfilePlugin;
+
+impl Plugin for ProfilePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ProfileChosen>()
+            .init_resource::<ActiveProfile>()
+            .init_resource::<Progress>()
+            .add_systems(OnEnter(GameState::ProfileSelect), open_profile_menu)
+            .add_systems(
+                Update,
+                (
+                    // Kiosks pick their own profile and keep visitors out of the rest
+                    (profile_menu_input, transfer_profiles).run_if(not(kiosk_enabled)),
+                    update_profile_menu_text.run_if(resource_changed::<ProfileMenu>),
+                )
+                    .chain()
+                    .run_if(in_state(GameState::ProfileSelect)),
+            )
+            .add_systems(OnExit(GameState::ProfileSelect), close_profile_menu)
+            .add_systems(
+                OnEnter(GameState::GameOver),
+                record_run.run_if(not(practice_enabled)),
+            )
+            .add_systems(Update, switch_profile.run_if(in_state(GameState::GameOver)));
+    }
+}
+
+fn profiles_root() -> PathBuf {
+    save::data_dir().join(PROFILES_DIR)
+}
+
+pub fn profile_dir(name: &str) -> PathBuf {
+    profiles_root().join(name)
+}
+
+pub fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, ' ' | '-' | '_')
+}
+
+/// Lists the profiles on disk, creating the default one on first launch.
+fn list_profiles() -> Vec<ProfileSummary> {
+    let mut names: Vec<String> = fs::read_dir(profiles_root())
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter(|entry| entry.path().is_dir())
+                .filter_map(|entry| entry.file_name().into_string().ok())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if names.is_empty() && fs::create_dir_all(profile_dir(DEFAULT_PROFILE)).is_ok() {
+        names.push(DEFAULT_PROFILE.to_string());
+    }
+    names.sort();
+
+    names
+        .into_iter()
+        .map(|name| {
+            let progress: Progress = save::load_versioned(&profile_dir(&name).join(PROGRESS_FILE));
+            ProfileSummary {
+                name,
+                best_score: progress.best_score,
+            }
+        })
+        .collect()
+}
+
+/// System to build the profile list and its text
+fn open_profile_menu(mut commands: Commands) {
+    commands.insert_resource(ProfileMenu {
+        profiles: list_profiles(),
+        ..default()
+    });
+    commands.spawn((
+        Text::default(),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(40.0),
+            left: Val::Px(40.0),
+            ..default()
+        },
+        ProfileMenuText,
+    ));
+}
+
+/// System to handle navigation, selection and management of profiles
+fn profile_menu_input(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut input_buffer: ResMut<InputBuffer>,
+    mut menu: ResMut<ProfileMenu>,
+    mut chosen: EventWriter<ProfileChosen>,
+) {
+    // Always drain the events so keys pressed before editing don't leak into the name
+    let events: Vec<KeyboardInput> = keyboard_events.read().cloned().collect();
+    if menu.loading {
+        return;
+    }
+
+    if let Some(mut edit) = menu.bypass_change_detection().editing.take() {
+        for event in &events {
+            match text_input::apply_key(&mut edit.buffer, event, MAX_NAME_LEN, is_name_char) {
+                TextInputAction::Submit => {
+                    // This Enter finished the name; it shouldn't also pick the profile
+                    input_buffer.clear(BufferedAction::Confirm);
+                    menu.error = commit_name_edit(&edit).err();
+                    menu.profiles = list_profiles();
+                    return;
+                }
+                TextInputAction::Cancel => {
+                    menu.set_changed();
+                    return;
+                }
+                TextInputAction::Edited | TextInputAction::None => {}
+            }
+        }
+        menu.editing = Some(edit);
+        return;
+    }
+
+    let count = menu.profiles.len();
+    if keyboard_input.just_pressed(KeyCode::ArrowUp) && count > 0 {
+        menu.selected = (menu.selected + count - 1) % count;
+        menu.confirm_delete = false;
+    }
+    if keyboard_input.just_pressed(KeyCode::ArrowDown) && count > 0 {
+        menu.selected = (menu.selected + 1) % count;
+        menu.confirm_delete = false;
+    }
+    if keyboard_input.just_pressed(KeyCode::KeyN) {
+        menu.editing = Some(NameEdit {
+            buffer: String::new(),
+            renaming: None,
+        });
+        menu.error = None;
+    }
+
+    let Some(selected) = menu.profiles.get(menu.selected).map(|p| p.name.clone()) else {
+        return;
+    };
+
+    if keyboard_input.just_pressed(KeyCode::KeyE) {
+        menu.editing = Some(NameEdit {
+            buffer: selected.clone(),
+            renaming: Some(selected.clone()),
+        });
+        menu.error = None;
+    }
+    if keyboard_input.just_pressed(KeyCode::Delete) {
+        // Deleting takes a second press to confirm
+        if menu.confirm_delete {
+            if let Err(err) = fs::remove_dir_all(profile_dir(&selected)) {
+                menu.error = Some(format!("Could not delete '{selected}': {err}"));
+            }
+            menu.profiles = list_profiles();
+            menu.selected = menu.selected.min(menu.profiles.len().saturating_sub(1));
+            menu.confirm_delete = false;
+        } else {
+            menu.confirm_delete = true;
+        }
+    }
+    // Consumed so the press doesn't carry over and start a run from the menu
+    if input_buffer.consume(BufferedAction::Confirm) {
+        // The sync layer loads the profile once it is up to date
+        menu.loading = true;
+        chosen.write(ProfileChosen(selected));
+    }
+}
+
+/// System to export the selected profile to a file, or import one.
+///
+/// File dialogs have to be opened from the main thread on some platforms.
+fn transfer_profiles(
+    _main_thread: NonSendMarker,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut menu: ResMut<ProfileMenu>,
+) {
+    if menu.loading || menu.editing.is_some() {
+        return;
+    }
+    if keyboard_input.just_pressed(KeyCode::KeyX)
+        && let Some(selected) = menu.profiles.get(menu.selected).map(|p| p.name.clone())
+    {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("Rusty Dodger profile", &[EXPORT_EXTENSION])
+            .set_file_name(format!("{selected}.{EXPORT_EXTENSION}"))
+            .save_file()
+        else {
+            return;
+        };
+        match export::export_profile(&selected, &path) {
+            Ok(()) => {
+                menu.error = None;
+                menu.notice = Some(format!("Exported to {}", path.display()));
+            }
+            Err(err) => menu.error = Some(err),
+        }
+    }
+    if keyboard_input.just_pressed(KeyCode::KeyI) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("Rusty Dodger profile", &[EXPORT_EXTENSION])
+            .pick_file()
+        else {
+            return;
+        };
+        match export::import_profile(&path) {
+            Ok(name) => {
+                info!(profile = %name, "Imported profile");
+                menu.profiles = list_profiles();
+                menu.selected = menu
+                    .profiles
+                    .iter()
+                    .position(|p| p.name == name)
+                    .unwrap_or(0);
+                menu.error = None;
+                menu.notice = Some(format!("Imported '{name}'"));
+            }
+            Err(err) => menu.error = Some(err),
+        }
+    }
+}
+
+/// Loads a profile's save files and makes it the active one.
+pub fn activate(commands: &mut Commands, name: &str) {
+    let dir = profile_dir(name);
+    if crash::safe_mode() {
+        commands.insert_resource(Settings::default());
+    } else {
+        commands.insert_resource(save::load_versioned::<Settings>(&dir.join(SETTINGS_FILE)));
+    }
+    commands.insert_resource(save::load_versioned::<Progress>(&dir.join(PROGRESS_FILE)));
+    commands.insert_resource(save::load_versioned::<HighScores>(
+        &dir.join(HIGH_SCORES_FILE),
+    ));
+    commands.insert_resource(save::load_or_default::<DeathHeatmap>(
+        &dir.join(DEATHS_FILE),
+    ));
+    commands.insert_resource(save::load_versioned::<PersonalBests>(
+        &dir.join(SPLITS_FILE),
+    ));
+    commands.insert_resource(ActiveProfile {
+        name: name.to_string(),
+    });
+}
+
+/// Creates or renames a profile directory from a finished name edit.
+fn commit_name_edit(edit: &NameEdit) -> Result<(), String> {
+    let name = edit.buffer.trim();
+    if name.is_empty() {
+        return Err("Profile names can't be empty".to_string());
+    }
+    if edit.renaming.as_deref() == Some(name) {
+        return Ok(());
+    }
+    let dir = profile_dir(name);
+    if dir.exists() {
+        return Err(format!("A profile named '{name}' already exists"));
+    }
+
+    let result = match &edit.renaming {
+        Some(old_name) => fs::rename(profile_dir(old_name), &dir),
+        None => fs::create_dir_all(&dir),
+    };
+    result.map_err(|err| format!("Could not save profile '{name}': {err}"))
+}
+
+/// System that redraws the profile list whenever the menu changes
+fn update_profile_menu_text(
+    menu: Res<ProfileMenu>,
+    mut text_query: Query<&mut Text, With<ProfileMenuText>>,
+) {
+    let Ok(mut text) = text_query.single_mut() else {
+        return;
+    };
+
+    let mut lines = vec!["Select Profile".to_string(), String::new()];
+    for (index, profile) in menu.profiles.iter().enumerate() {
+        let cursor = if index == menu.selected { ">" } else { " " };
+        lines.push(format!(
+            "{cursor} {}  (best {})",
+            profile.name, profile.best_score
+        ));
+    }
+    lines.push(String::new());
+
+    if menu.loading {
+        lines.push("Loading profile...".to_string());
+    } else if let Some(edit) = &menu.editing {
+        let action = if edit.renaming.is_some() {
+            "Rename to"
+        } else {
+            "New profile"
+        };
+        lines.push(format!("{action}: {}_", edit.buffer));
+        lines.push("Enter to confirm, Esc to cancel".to_string());
+    } else if menu.confirm_delete {
+        lines.push("Press Delete again to delete this profile".to_string());
+    } else {
+        lines.push("Enter: Play   N: New   E: Rename   Delete: Delete".to_string());
+        lines.push("X: Export to file   I: Import from file".to_string());
+    }
+    if let Some(error) = &menu.error {
+        lines.push(error.clone());
+    } else if let Some(notice) = &menu.notice {
+        lines.push(notice.clone());
+    }
+
+    text.0 = lines.join("\n");
+}
+
+/// System to tear down the profile-selection screen
+fn close_profile_menu(mut commands: Commands, query: Query<Entity, With<ProfileMenuText>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+    commands.remove_resource::<ProfileMenu>();
+}
+
+/// System that stores the finished run in the active profile
+pub fn record_run(
+    profile: Res<ActiveProfile>,
+    score: Res<Score>,
+    stats: Res<RunStats>,
+    difficulty: Res<Difficulty>,
+    settings: Res<Settings>,
+    mutators: Res<RunMutators>,
+    mut progress: ResMut<Progress>,
+    mut high_scores: ResMut<HighScores>,
+) {
+    let points = score.points();
+    progress.runs_played += 1;
+    progress.best_score = progress.best_score.max(points);
+    progress.time_played += stats.elapsed();
+    profile.save(PROGRESS_FILE, &*progress);
+
+    // Rubber-banded runs aren't comparable with the rest of the table
+    if settings.adaptive_difficulty {
+        return;
+    }
+    if high_scores
+        .submit(HighScoreEntry {
+            score: points,
+            difficulty: difficulty.preset,
+            mutators: mutators.0.clone(),
+        })
+        .is_some()
+    {
+        profile.save(HIGH_SCORES_FILE, &*high_scores);
+    }
+}
+
+/// System to go back to the profile-selection screen from Game Over
+fn switch_profile(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::KeyP) {
+        next_state.set(GameState::ProfileSelect);
+    }
+}
@@ -0,0 +1,157 @@
+#[cfg(feature = "profiling")]
+use std::time::Instant;
+
+#[cfg(feature = "profiling")]
+use bevy::diagnostic::DiagnosticMeasurement;
+use bevy::diagnostic::{
+    Diagnostic, DiagnosticPath, DiagnosticsStore, FrameTimeDiagnosticsPlugin,
+    SystemInformationDiagnosticsPlugin,
+};
+use bevy::input::common_conditions::input_just_pressed;
+use bevy::prelude::*;
+
+use crate::watchdog::EntityCensus;
+
+// Profiler constants
+const TOGGLE_KEY: KeyCode = KeyCode::F4;
+const SYSTEM_PREFIX: &str = "gameplay/"; // Diagnostic paths of timed systems start with this
+
+// --- Components ---
+
+#[derive(Component)]
+struct ProfilerOverlay;
+
+pub struct ProfilerPlugin;
+
+impl Plugin for ProfilerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            FrameTimeDiagnosticsPlugin::default(),
+            SystemInformationDiagnosticsPlugin,
+        ))
+        .add_systems(
+            Update,
+            (
+                toggle_overlay.run_if(input_just_pressed(TOGGLE_KEY)),
+                update_overlay,
+            )
+                .chain(),
+        );
+    }
+}
+
+/// Wraps a system so every run is timed into a `gameplay/<name>` diagnostic,
+/// shown on the F4 overlay. Timed systems run exclusively, so this only
+/// happens in `profiling` builds.
+#[cfg(feature = "profiling")]
+pub fn timed<M>(name: &'static str, system: impl IntoSystem<(), (), M>) -> impl FnMut(&mut World) {
+    let mut system = IntoSystem::into_system(system);
+    let path = DiagnosticPath::new(format!("{SYSTEM_PREFIX}{name}"));
+    let mut initialized = false;
+
+    move |world: &mut World| {
+        if !initialized {
+            system.initialize(world);
+            let mut store = world.resource_mut::<DiagnosticsStore>();
+            if store.get(&path).is_none() {
+                store.add(Diagnostic::new(path.clone()).with_suffix("ms"));
+            }
+            initialized = true;
+        }
+
+        let start = Instant::now();
+        system.run((), world);
+        let elapsed = start.elapsed();
+
+        if let Some(diagnostic) = world.resource_mut::<DiagnosticsStore>().get_mut(&path) {
+            diagnostic.add_measurement(DiagnosticMeasurement {
+                time: Instant::now(),
+                value: elapsed.as_secs_f64() * 1000.0,
+            });
+        }
+    }
+}
+
+/// Leaves the system as it is outside `profiling` builds.
+#[cfg(not(feature = "profiling"))]
+pub fn timed<M, S: IntoSystem<(), (), M>>(_name: &'static str, system: S) -> S {
+    system
+}
+
+/// System to show or hide the profiler overlay
+fn toggle_overlay(mut commands: Commands, query: Query<Entity, With<ProfilerOverlay>>) {
+    if let Ok(entity) = query.single() {
+        commands.entity(entity).despawn();
+        return;
+    }
+    commands.spawn((
+        Text::default(),
+        TextFont::from_font_size(14.0),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.0),
+            left: Val::Px(10.0),
+            padding: UiRect::all(Val::Px(6.0)),
+            ..default()
+        },
+        BackgroundColor(Color::BLACK.with_alpha(0.6)),
+        GlobalZIndex(i32::MAX),
+        ProfilerOverlay,
+    ));
+}
+
+/// System that fills the overlay with frame, process and per-system timings,
+/// and the latest entity count
+fn update_overlay(
+    diagnostics: Res<DiagnosticsStore>,
+    census: Res<EntityCensus>,
+    mut query: Query<&mut Text, With<ProfilerOverlay>>,
+) {
+    let Ok(mut text) = query.single_mut() else {
+        return;
+    };
+    let smoothed = |path: &DiagnosticPath| {
+        diagnostics
+            .get(path)
+            .and_then(Diagnostic::smoothed)
+            .unwrap_or(0.0)
+    };
+
+    let mut lines = vec![
+        format!(
+            "FPS {:.0}   frame {:.2} ms",
+            smoothed(&FrameTimeDiagnosticsPlugin::FPS),
+            smoothed(&FrameTimeDiagnosticsPlugin::FRAME_TIME)
+        ),
+        format!(
+            "Process CPU {:.1}%   memory {:.2} GiB",
+            smoothed(&SystemInformationDiagnosticsPlugin::PROCESS_CPU_USAGE),
+            smoothed(&SystemInformationDiagnosticsPlugin::PROCESS_MEM_USAGE)
+        ),
+        String::new(),
+    ];
+
+    let mut systems: Vec<(&str, f64)> = diagnostics
+        .iter()
+        .filter_map(|diagnostic| {
+            let name = diagnostic.path().as_str().strip_prefix(SYSTEM_PREFIX)?;
+            Some((name, diagnostic.smoothed().unwrap_or(0.0)))
+        })
+        .collect();
+    // Slowest first, so regressions stand out at the top
+    systems.sort_by(|a, b| b.1.total_cmp(&a.1));
+    for (name, ms) in systems {
+        lines.push(format!("{ms:7.3} ms  {name}"));
+    }
+    if !cfg!(feature = "profiling") {
+        lines.push("Build with --features profiling for system timings".to_string());
+    }
+
+    lines.push(String::new());
+    lines.push(format!("Entities {}", census.total));
+    for (components, count) in &census.archetypes {
+        lines.push(format!("{count:7}  {components}"));
+    }
+
+    text.0 = lines.join("\n");
+}
@@ -0,0 +1,247 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::GameState;
+use crate::appearance::ShipColor;
+use crate::data;
+use crate::focus::MenuNav;
+use crate::hud_layout::{HudAnchor, HudSlot};
+use crate::mutator::Mutator;
+use crate::objective::ActiveObjective;
+use crate::practice::practice_enabled;
+use crate::profile::{self, Progress};
+use crate::reset::RunScoped;
+use crate::score::Score;
+use crate::text_style::TextStyleLibrary;
+
+// Progression constants
+const TRACK_FILE: &str = "progression.ron";
+const BUILTIN_TRACK: &str = include_str!("../assets/progression.ron");
+const POINTS_PER_XP: u32 = 10; // Score points worth one XP
+const OBJECTIVE_XP: u32 = 50; // XP for each objective completed in a run
+const UNLOCKED_COLOR: Color = Color::srgb(0.5, 1.0, 0.5);
+const LOCKED_COLOR: Color = Color::srgb(0.55, 0.55, 0.55);
+
+/// What reaching a tier unlocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+enum Reward {
+    ShipColor(ShipColor),
+    Mutator(Mutator),
+}
+
+impl Reward {
+    fn describe(self) -> String {
+        match self {
+            Reward::ShipColor(color) => format!("{} ship", color.name()),
+            Reward::Mutator(mutator) => format!("{} mutator", mutator.name()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Tier {
+    /// Total XP needed to reach the tier.
+    xp: u32,
+    reward: Reward,
+}
+
+/// Contents of `assets/progression.ron`.
+#[derive(Deserialize)]
+struct TrackConfig {
+    tiers: Vec<Tier>,
+}
+
+// --- Resources ---
+
+/// The tiers of the XP track, cheapest first.
+#[derive(Resource)]
+struct ProgressionTrack(Vec<Tier>);
+
+impl Default for ProgressionTrack {
+    fn default() -> Self {
+        let mut config: TrackConfig = data::load_ron(TRACK_FILE, BUILTIN_TRACK);
+        config.tiers.sort_by_key(|tier| tier.xp);
+        Self(config.tiers)
+    }
+}
+
+impl ProgressionTrack {
+    /// Number of tiers reached with `xp`.
+    fn tiers_reached(&self, xp: u32) -> usize {
+        self.0.iter().filter(|tier| tier.xp <= xp).count()
+    }
+}
+
+/// Rewards the active profile hasn't unlocked yet, kept in step with its XP.
+#[derive(Resource, Default)]
+pub struct Unlocks {
+    locked: Vec<Reward>,
+}
+
+impl Unlocks {
+    pub fn ship_color(&self, color: ShipColor) -> bool {
+        !self.locked.contains(&Reward::ShipColor(color))
+    }
+
+    pub fn mutator(&self, mutator: Mutator) -> bool {
+        !self.locked.contains(&Reward::Mutator(mutator))
+    }
+
+    /// `color` if it's unlocked, otherwise the next unlocked colour after it.
+    pub fn next_ship_color(&self, color: ShipColor) -> ShipColor {
+        let mut next = color;
+        while !self.ship_color(next) {
+            next = next.next();
+            if next == color {
+                return ShipColor::default();
+            }
+        }
+        next
+    }
+}
+
+// --- Components ---
+
+#[derive(Component)]
+#[require(RunScoped)]
+struct XpSummary;
+
+#[derive(Component)]
+struct ProgressionScreen;
+
+pub struct ProgressionPlugin;
+
+impl Plugin for ProgressionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ProgressionTrack>()
+            .init_resource::<Unlocks>()
+            .add_systems(Update, update_unlocks.run_if(resource_changed::<Progress>))
+            // Before the run is recorded, so the XP is saved along with it
+            .add_systems(
+                OnEnter(GameState::GameOver),
+                earn_xp
+                    .before(profile::record_run)
+                    .run_if(not(practice_enabled)),
+            )
+            .add_systems(OnEnter(GameState::Progression), spawn_progression_screen)
+            .add_systems(
+                Update,
+                progression_screen_input.run_if(in_state(GameState::Progression)),
+            )
+            .add_systems(OnExit(GameState::Progression), despawn_progression_screen);
+    }
+}
+
+/// System that works out which rewards the profile's XP has unlocked
+fn update_unlocks(
+    track: Res<ProgressionTrack>,
+    progress: Res<Progress>,
+    mut unlocks: ResMut<Unlocks>,
+) {
+    unlocks.locked = track
+        .0
+        .iter()
+        .filter(|tier| tier.xp > progress.xp)
+        .map(|tier| tier.reward)
+        .collect();
+}
+
+/// System that adds the run's XP to the profile and shows what it earned
+fn earn_xp(
+    mut commands: Commands,
+    score: Res<Score>,
+    objectives: Res<ActiveObjective>,
+    track: Res<ProgressionTrack>,
+    styles: Res<TextStyleLibrary>,
+    mut progress: ResMut<Progress>,
+) {
+    let earned = score.points() / POINTS_PER_XP + objectives.completed * OBJECTIVE_XP;
+    let before = track.tiers_reached(progress.xp);
+    progress.xp += earned;
+    let after = track.tiers_reached(progress.xp);
+
+    let mut summary = format!(
+        "+{earned} XP  (tier {after}/{}, {} XP total)",
+        track.0.len(),
+        progress.xp
+    );
+    for tier in &track.0[before..after] {
+        summary.push_str(&format!("\nUnlocked: {}", tier.reward.describe()));
+    }
+    commands.spawn((
+        Text::new(summary),
+        styles.body.ui(),
+        HudSlot::new(HudAnchor::Bottom, 20),
+        XpSummary,
+    ));
+}
+
+/// System to spawn the XP track with every tier and whether it's reached
+fn spawn_progression_screen(
+    mut commands: Commands,
+    track: Res<ProgressionTrack>,
+    progress: Res<Progress>,
+    styles: Res<TextStyleLibrary>,
+) {
+    let next = track.0.iter().find(|tier| tier.xp > progress.xp);
+    let header = match next {
+        Some(tier) => format!(
+            "Progression\n\n{} XP, {} XP to the next tier\n",
+            progress.xp,
+            tier.xp - progress.xp
+        ),
+        None => format!("Progression\n\n{} XP, every tier reached\n", progress.xp),
+    };
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(40.0),
+                left: Val::Px(40.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(4.0),
+                ..default()
+            },
+            ProgressionScreen,
+        ))
+        .with_children(|parent| {
+            parent.spawn((Text::new(header), styles.body.ui()));
+            for (index, tier) in track.0.iter().enumerate() {
+                let reached = tier.xp <= progress.xp;
+                parent.spawn((
+                    Text::new(format!(
+                        "Tier {:>2}  {:>5} XP  {}{}",
+                        index + 1,
+                        tier.xp,
+                        tier.reward.describe(),
+                        if reached { "" } else { "  (locked)" }
+                    )),
+                    styles.body.ui(),
+                    TextColor(if reached { UNLOCKED_COLOR } else { LOCKED_COLOR }),
+                ));
+            }
+            parent.spawn((
+                Text::new(format!(
+                    "\n1 XP per {POINTS_PER_XP} points, {OBJECTIVE_XP} XP per objective\n\nEsc: Back"
+                )),
+                styles.body.ui(),
+            ));
+        });
+}
+
+/// System to leave the progression screen
+fn progression_screen_input(nav: Res<MenuNav>, mut next_state: ResMut<NextState<GameState>>) {
+    if nav.back {
+        next_state.set(GameState::Menu);
+    }
+}
+
+/// System to remove the progression screen
+fn despawn_progression_screen(
+    mut commands: Commands,
+    query: Query<Entity, With<ProgressionScreen>>,
+) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
@@ -0,0 +1,205 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::collision::Collider;
+use crate::difficulty::DifficultyConfig;
+use crate::dying::Dying;
+use crate::enemy::EnemyKind;
+use crate::reset::RunCleanup;
+use crate::sets::GameSet;
+use crate::shapes::Shape;
+use crate::window::WindowMetrics;
+use crate::{Player, RunPhase, Velocity};
+
+// Projectile constants
+const BULLET_SIZE: Vec2 = Vec2::new(10.0, 10.0);
+const BULLET_HITBOX: Vec2 = Vec2::new(6.0, 6.0); // Smaller than the sprite so near misses read fairly
+const BULLET_COLOR: Color = Color::srgb(1.0, 0.95, 0.4);
+pub const BULLET_SHAPE: Shape = Shape::Circle;
+const MAX_BULLETS: usize = 96; // Volleys are skipped while this many are in flight
+const SPREAD_ANGLE: f32 = 0.3; // Radians between the bullets of a spread shot
+const RING_BULLETS: usize = 8;
+
+/// How a shooter lays out each volley.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum FirePattern {
+    /// One bullet straight at the player.
+    Aimed,
+    /// Three bullets fanned around the player.
+    Spread,
+    /// A ring of bullets in every direction.
+    Ring,
+}
+
+impl FirePattern {
+    const ALL: [FirePattern; 3] = [FirePattern::Aimed, FirePattern::Spread, FirePattern::Ring];
+
+    /// Directions of one volley, given the direction to the player.
+    fn directions(self, aim: Vec2) -> Vec<Vec2> {
+        match self {
+            FirePattern::Aimed => vec![aim],
+            FirePattern::Spread => [-SPREAD_ANGLE, 0.0, SPREAD_ANGLE]
+                .iter()
+                .map(|&angle| Vec2::from_angle(angle).rotate(aim))
+                .collect(),
+            FirePattern::Ring => (0..RING_BULLETS)
+                .map(|i| {
+                    let angle = std::f32::consts::TAU * i as f32 / RING_BULLETS as f32;
+                    Vec2::from_angle(angle).rotate(aim)
+                })
+                .collect(),
+        }
+    }
+}
+
+// --- Components ---
+
+/// An enemy that fires volleys at the player.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Shooter {
+    timer: Timer,
+    pattern: FirePattern,
+    speed: f32,
+}
+
+/// A bullet in flight. Pooled bullets keep their entity but lose this.
+#[derive(Component)]
+pub struct EnemyBullet;
+
+// --- Resources ---
+
+/// Bullet entities that are hidden and ready to be fired again.
+#[derive(Resource, Default)]
+struct BulletPool {
+    free: Vec<Entity>,
+}
+
+pub struct ProjectilePlugin;
+
+impl Plugin for ProjectilePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BulletPool>()
+            .add_systems(
+                Update,
+                (
+                    fire_bullets.in_set(GameSet::Simulation),
+                    release_offscreen_bullets.in_set(GameSet::Cleanup),
+                )
+                    .run_if(in_state(RunPhase::Alive)),
+            )
+            .add_systems(RunCleanup, release_all_bullets);
+    }
+}
+
+/// Arms a freshly spawned enemy if this difficulty lets its kind shoot yet.
+pub fn arm(
+    entity: &mut EntityCommands,
+    kind: EnemyKind,
+    config: &DifficultyConfig,
+    elapsed: f32,
+    rng: &mut impl Rng,
+) {
+    let Some(bullets) = &config.bullets else {
+        return;
+    };
+    if elapsed < bullets.start_seconds
+        || !bullets.shooters.contains(&kind)
+        || rng.random::<f32>() >= bullets.chance
+    {
+        return;
+    }
+    let pattern = FirePattern::ALL[rng.random_range(0..FirePattern::ALL.len())];
+    entity.insert(Shooter {
+        timer: Timer::from_seconds(bullets.fire_interval, TimerMode::Repeating),
+        pattern,
+        speed: bullets.speed,
+    });
+}
+
+/// System that fires a volley from every shooter whose timer is up
+fn fire_bullets(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut pool: ResMut<BulletPool>,
+    mut shooter_query: Query<(&mut Shooter, &Transform), Without<Dying>>,
+    player_query: Query<&Transform, With<Player>>,
+    active_query: Query<(), With<EnemyBullet>>,
+) {
+    let Ok(player_transform) = player_query.single() else {
+        return;
+    };
+    let mut active = active_query.iter().count();
+
+    for (mut shooter, transform) in &mut shooter_query {
+        // Only fire from the top half, so there's always time to react
+        if !shooter.timer.tick(time.delta()).just_finished() || transform.translation.y < 0.0 {
+            continue;
+        }
+        let origin = transform.translation.truncate();
+        let aim = (player_transform.translation.truncate() - origin).normalize_or(Vec2::NEG_Y);
+
+        for direction in shooter.pattern.directions(aim) {
+            if active >= MAX_BULLETS {
+                return;
+            }
+            active += 1;
+            let bullet = (
+                EnemyBullet,
+                Velocity(direction * shooter.speed),
+                Transform::from_translation(origin.extend(0.2)).with_scale(BULLET_SIZE.extend(1.0)),
+                Visibility::Visible,
+            );
+            match pool.free.pop() {
+                Some(entity) => {
+                    commands.entity(entity).insert(bullet);
+                }
+                None => {
+                    commands.spawn((
+                        bullet,
+                        Sprite::from_color(BULLET_COLOR, Vec2::ONE),
+                        BULLET_SHAPE,
+                        Collider::new(BULLET_HITBOX),
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// Hides a bullet and hands it back to the pool.
+fn release(commands: &mut Commands, pool: &mut BulletPool, entity: Entity) {
+    commands
+        .entity(entity)
+        .remove::<(EnemyBullet, Velocity)>()
+        .insert(Visibility::Hidden);
+    pool.free.push(entity);
+}
+
+/// System that returns bullets to the pool once they leave the screen
+fn release_offscreen_bullets(
+    mut commands: Commands,
+    mut pool: ResMut<BulletPool>,
+    query: Query<(Entity, &Transform), With<EnemyBullet>>,
+    window: Res<WindowMetrics>,
+) {
+    let half_extent = Vec2::new(window.width(), window.height()) / 2.0 + BULLET_SIZE;
+
+    for (entity, transform) in &query {
+        let position = transform.translation.truncate();
+        if position.abs().cmpgt(half_extent).any() {
+            release(&mut commands, &mut pool, entity);
+        }
+    }
+}
+
+/// System that clears every bullet in flight when a run is over
+fn release_all_bullets(
+    mut commands: Commands,
+    mut pool: ResMut<BulletPool>,
+    query: Query<Entity, With<EnemyBullet>>,
+) {
+    for entity in &query {
+        release(&mut commands, &mut pool, entity);
+    }
+}
@@ -0,0 +1,252 @@
+use bevy::asset::RenderAssetUsages;
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+
+use crate::input_buffer::LastInputDevice;
+use crate::text_style::TextStyleLibrary;
+
+// Prompt constants
+const CELL_SIZE: UVec2 = UVec2::new(48, 32); // Pixels of one icon in the atlas
+const ICON_SIZE: Vec2 = Vec2::new(36.0, 24.0); // Size an icon is drawn at
+const ICON_BORDER: f32 = 2.0;
+const LABEL_SIZE: f32 = 14.0;
+const KEY_COLOR: Color = Color::srgb(0.85, 0.85, 0.85);
+const KEY_BORDER_COLOR: Color = Color::srgb(0.45, 0.45, 0.45);
+const KEY_LABEL_COLOR: Color = Color::srgb(0.1, 0.1, 0.1);
+const BUTTON_LABEL_COLOR: Color = Color::WHITE;
+
+/// Something the player can be asked to press.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptAction {
+    Confirm,
+    Back,
+    Restart,
+}
+
+impl PromptAction {
+    fn key(self) -> &'static str {
+        match self {
+            PromptAction::Confirm => "Enter",
+            PromptAction::Back => "Esc",
+            PromptAction::Restart => "R",
+        }
+    }
+
+    fn button(self) -> PromptIcon {
+        match self {
+            PromptAction::Confirm => PromptIcon::South,
+            PromptAction::Back => PromptIcon::East,
+            PromptAction::Restart => PromptIcon::North,
+        }
+    }
+}
+
+/// The pictures in the prompt atlas, in atlas order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PromptIcon {
+    /// A blank keycap, labelled with the key's name.
+    Key,
+    South,
+    East,
+    North,
+}
+
+impl PromptIcon {
+    const ALL: [PromptIcon; 4] = [
+        PromptIcon::Key,
+        PromptIcon::South,
+        PromptIcon::East,
+        PromptIcon::North,
+    ];
+
+    /// Face buttons are coloured and lettered the way most pads print them.
+    fn label(self) -> &'static str {
+        match self {
+            PromptIcon::Key => "",
+            PromptIcon::South => "A",
+            PromptIcon::East => "B",
+            PromptIcon::North => "Y",
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            PromptIcon::Key => KEY_COLOR,
+            PromptIcon::South => Color::srgb(0.3, 0.7, 0.3),
+            PromptIcon::East => Color::srgb(0.8, 0.25, 0.25),
+            PromptIcon::North => Color::srgb(0.85, 0.7, 0.2),
+        }
+    }
+
+    /// Signed distance from `point` (relative to the cell's centre) to the
+    /// icon's outline, negative inside.
+    fn distance(self, point: Vec2) -> f32 {
+        match self {
+            PromptIcon::Key => {
+                let half = CELL_SIZE.as_vec2() / 2.0 - Vec2::splat(1.0);
+                let radius = 6.0;
+                let q = point.abs() - half + Vec2::splat(radius);
+                q.max(Vec2::ZERO).length() + q.x.max(q.y).min(0.0) - radius
+            }
+            _ => point.length() - (CELL_SIZE.y as f32 / 2.0 - 1.0),
+        }
+    }
+}
+
+// --- Components ---
+
+/// A "Press <button> to ..." line that shows the keyboard key or gamepad
+/// button, whichever was used last. Its children are built for it.
+#[derive(Component)]
+#[require(Node)]
+pub struct ButtonPrompt {
+    action: PromptAction,
+    text: String,
+}
+
+impl ButtonPrompt {
+    pub fn new(action: PromptAction, text: impl Into<String>) -> Self {
+        Self {
+            action,
+            text: text.into(),
+        }
+    }
+}
+
+// --- Resources ---
+
+/// Every prompt icon drawn once into a single texture.
+#[derive(Resource)]
+struct PromptAtlas {
+    image: Handle<Image>,
+    layout: Handle<TextureAtlasLayout>,
+}
+
+impl FromWorld for PromptAtlas {
+    fn from_world(world: &mut World) -> Self {
+        let image = world.resource_mut::<Assets<Image>>().add(draw_atlas());
+        let layout =
+            world
+                .resource_mut::<Assets<TextureAtlasLayout>>()
+                .add(TextureAtlasLayout::from_grid(
+                    CELL_SIZE,
+                    PromptIcon::ALL.len() as u32,
+                    1,
+                    None,
+                    None,
+                ));
+        Self { image, layout }
+    }
+}
+
+/// Paints the icons side by side, with a darker rim and smoothed edges.
+fn draw_atlas() -> Image {
+    let width = CELL_SIZE.x * PromptIcon::ALL.len() as u32;
+    let mut data = Vec::with_capacity((width * CELL_SIZE.y * 4) as usize);
+    let center = CELL_SIZE.as_vec2() / 2.0;
+    for y in 0..CELL_SIZE.y {
+        for x in 0..width {
+            let icon = PromptIcon::ALL[(x / CELL_SIZE.x) as usize];
+            let point = Vec2::new((x % CELL_SIZE.x) as f32, y as f32) + 0.5 - center;
+            let distance = icon.distance(point);
+            let rim = if icon == PromptIcon::Key {
+                KEY_BORDER_COLOR
+            } else {
+                icon.color().darker(0.2)
+            };
+            let mut color = if distance > -ICON_BORDER {
+                rim
+            } else {
+                icon.color()
+            }
+            .to_srgba();
+            color.alpha *= (0.5 - distance).clamp(0.0, 1.0);
+            data.extend_from_slice(&[
+                (color.red * 255.0) as u8,
+                (color.green * 255.0) as u8,
+                (color.blue * 255.0) as u8,
+                (color.alpha * 255.0) as u8,
+            ]);
+        }
+    }
+    Image::new(
+        Extent3d {
+            width,
+            height: CELL_SIZE.y,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    )
+}
+
+pub struct PromptPlugin;
+
+impl Plugin for PromptPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PromptAtlas>()
+            .add_systems(Update, build_prompts);
+    }
+}
+
+/// System that fills in new prompts, and redraws all of them when the player
+/// switches between keyboard and gamepad
+fn build_prompts(
+    mut commands: Commands,
+    device: Res<LastInputDevice>,
+    atlas: Res<PromptAtlas>,
+    styles: Res<TextStyleLibrary>,
+    mut query: Query<(Entity, Ref<ButtonPrompt>, &mut Node)>,
+) {
+    for (entity, prompt, mut node) in &mut query {
+        if !prompt.is_added() && !device.is_changed() {
+            continue;
+        }
+        node.flex_direction = FlexDirection::Row;
+        node.align_items = AlignItems::Center;
+        node.column_gap = Val::Px(6.0);
+
+        let (icon, label, label_color) = match *device {
+            LastInputDevice::Keyboard => (PromptIcon::Key, prompt.action.key(), KEY_LABEL_COLOR),
+            LastInputDevice::Gamepad => {
+                let button = prompt.action.button();
+                (button, button.label(), BUTTON_LABEL_COLOR)
+            }
+        };
+        let index = PromptIcon::ALL.iter().position(|&i| i == icon).unwrap_or(0);
+        commands
+            .entity(entity)
+            .despawn_related::<Children>()
+            .with_children(|parent| {
+                parent.spawn((Text::new("Press"), styles.body.ui()));
+                parent.spawn((
+                    ImageNode::from_atlas_image(
+                        atlas.image.clone(),
+                        TextureAtlas {
+                            layout: atlas.layout.clone(),
+                            index,
+                        },
+                    ),
+                    Node {
+                        width: Val::Px(ICON_SIZE.x),
+                        height: Val::Px(ICON_SIZE.y),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    children![(
+                        Text::new(label),
+                        TextFont {
+                            font: styles.hud.font.clone(),
+                            font_size: LABEL_SIZE,
+                            ..default()
+                        },
+                        TextColor(label_color),
+                    )],
+                ));
+                parent.spawn((Text::new(prompt.text.clone()), styles.body.ui()));
+            });
+    }
+}
@@ -0,0 +1,107 @@
+use bevy::prelude::*;
+
+use crate::RunPhase;
+use crate::reset::RunSetup;
+use crate::sets::GameSet;
+use crate::stats::RunStats;
+
+/// The keys an `InputFrame` records, in the order of its fields.
+pub const KEYS: [KeyCode; 5] = [
+    KeyCode::ArrowLeft,
+    KeyCode::ArrowRight,
+    KeyCode::ArrowUp,
+    KeyCode::ArrowDown,
+    KeyCode::KeyB,
+];
+
+/// The gameplay keys held on one frame of a run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InputFrame {
+    pub left: bool,
+    pub right: bool,
+    pub up: bool,
+    pub down: bool,
+    pub bomb: bool,
+}
+
+impl InputFrame {
+    fn read(keyboard_input: &ButtonInput<KeyCode>) -> Self {
+        Self {
+            left: keyboard_input.pressed(KeyCode::ArrowLeft),
+            right: keyboard_input.pressed(KeyCode::ArrowRight),
+            up: keyboard_input.pressed(KeyCode::ArrowUp),
+            down: keyboard_input.pressed(KeyCode::ArrowDown),
+            bomb: keyboard_input.just_pressed(KeyCode::KeyB),
+        }
+    }
+
+    /// The keys, in `KEYS` order, as one bit each.
+    pub fn bits(self) -> u8 {
+        self.held()
+            .into_iter()
+            .enumerate()
+            .fold(0, |bits, (bit, held)| bits | u8::from(held) << bit)
+    }
+
+    pub fn from_bits(bits: u8) -> Self {
+        let held = |bit: usize| bits & 1 << bit != 0;
+        Self {
+            left: held(0),
+            right: held(1),
+            up: held(2),
+            down: held(3),
+            bomb: held(4),
+        }
+    }
+
+    /// Whether each of `KEYS` is down on this frame.
+    pub fn held(self) -> [bool; 5] {
+        [self.left, self.right, self.up, self.down, self.bomb]
+    }
+}
+
+// --- Resources ---
+
+/// Every frame's input in the current run, with the run time it was read at.
+#[derive(Resource, Default)]
+pub struct InputRecording {
+    pub frames: Vec<(f32, InputFrame)>,
+}
+
+impl InputRecording {
+    /// The most recent `count` frames, oldest first.
+    pub fn last(&self, count: usize) -> &[(f32, InputFrame)] {
+        &self.frames[self.frames.len().saturating_sub(count)..]
+    }
+}
+
+pub struct RecordingPlugin;
+
+impl Plugin for RecordingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InputRecording>()
+            .add_systems(RunSetup, clear_recording)
+            .add_systems(
+                Update,
+                record_input
+                    .in_set(GameSet::Input)
+                    .run_if(in_state(RunPhase::Alive)),
+            );
+    }
+}
+
+/// System to start the run with an empty recording
+fn clear_recording(mut recording: ResMut<InputRecording>) {
+    recording.frames.clear();
+}
+
+/// System that appends this frame's input to the recording
+fn record_input(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    stats: Res<RunStats>,
+    mut recording: ResMut<InputRecording>,
+) {
+    recording
+        .frames
+        .push((stats.elapsed(), InputFrame::read(&keyboard_input)));
+}
@@ -0,0 +1,509 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use bevy::asset::RenderAssetUsages;
+use bevy::input::InputSystem;
+use bevy::input::common_conditions::input_just_pressed;
+use bevy::prelude::*;
+use bevy::render::camera::{CameraUpdateSystem, RenderTarget};
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat, TextureUsages};
+use bevy::render::view::screenshot::{Screenshot, save_to_disk};
+use bevy::time::{TimeSystem, TimeUpdateStrategy};
+use bevy::window::WindowResolution;
+use serde::{Deserialize, Serialize};
+
+use crate::adaptive::AdaptiveDifficulty;
+use crate::arena::Arena;
+use crate::camera::PLAY_AREA;
+use crate::challenge::Challenge;
+use crate::difficulty::Difficulty;
+use crate::hud_layout::{HudAnchor, HudSlot};
+use crate::input_buffer::{self, GAMEPAD_THRESHOLD};
+use crate::kiosk;
+use crate::lanes::LaneMode;
+use crate::mutator::{MutatorSelection, RunMutators};
+use crate::practice::Practice;
+use crate::recording::{self, InputFrame, InputRecording};
+use crate::reset::{RunScoped, RunSetup};
+use crate::rng::{self, RunSeed};
+use crate::save;
+use crate::score::Score;
+use crate::settings::Settings;
+use crate::stats::RunStats;
+use crate::text_style::TextStyleLibrary;
+use crate::{GameState, RunPhase};
+
+// Replay constants
+const RENDER_FLAG: &str = "--render-replay"; // Followed by the replay file to render
+const SAVE_KEY: KeyCode = KeyCode::KeyV;
+const REPLAYS_DIR: &str = "replays";
+const REPLAY_VERSION: u32 = 2; // Bump with every change to `RunScript`
+const FRAME_RATE: f32 = 60.0; // Frames per second of rendered replays
+const GAME_OVER_HOLD: f32 = 2.0; // Seconds of the Game Over screen at the end of a render
+
+/// A finished run as a small file: how it was set up, every frame's time
+/// step and keys, and how it ended, so it can be played back exactly.
+#[derive(Serialize, Deserialize)]
+struct RunScript {
+    version: u32,
+    /// The run's challenge code: difficulty, mode, mutators and seed.
+    challenge: String,
+    // Older replays lack it, and are turned away by their version instead
+    #[serde(default)]
+    conditions: RunConditions,
+    /// Seconds each frame of the run took, and its keys as `InputFrame::bits`.
+    frames: Vec<(f32, u8)>,
+    score: u32,
+    elapsed: f32,
+}
+
+impl RunScript {
+    fn load(path: &Path) -> Result<Self, String> {
+        let contents =
+            fs::read_to_string(path).map_err(|err| format!("Could not read the file: {err}"))?;
+        let script: Self =
+            ron::from_str(&contents).map_err(|err| format!("This is not a replay: {err}"))?;
+        if script.version > REPLAY_VERSION {
+            return Err("This replay was saved by a newer version of the game".to_string());
+        }
+        if script.version < REPLAY_VERSION {
+            return Err("This replay was saved by an older version of the game".to_string());
+        }
+        Ok(script)
+    }
+}
+
+/// Everything outside the challenge code that shapes a run: the gameplay
+/// settings, the adaptive difficulty's history and the practice checkpoint.
+#[derive(Clone, Default, Serialize, Deserialize)]
+struct RunConditions {
+    safe_spawns: bool,
+    forgiving_hitbox: bool,
+    adaptive_difficulty: bool,
+    adaptive: AdaptiveDifficulty,
+    max_enemies: u32,
+    danger_zone: bool,
+    /// Index of the checkpoint a practice run started at, `None` outside practice.
+    checkpoint: Option<usize>,
+}
+
+// --- Resources ---
+
+/// How the current run was set up, and whether a gamepad was used in it.
+/// Replays only record the keyboard, so gamepad runs can't be saved.
+#[derive(Resource, Default)]
+struct RecordedRun {
+    conditions: RunConditions,
+    gamepad: bool,
+}
+
+/// A replay being rendered to PNG frames, from `--render-replay <file>`.
+/// Time steps come from the script rather than the clock, so the render
+/// takes as long as it needs and every frame lands where it did in the run.
+/// Frames are drawn into an image behind a hidden window, so nothing shows
+/// on screen, but opening that window still takes a display, real or
+/// virtual like Xvfb.
+#[derive(Resource)]
+pub struct ReplayRender {
+    script: RunScript,
+    challenge: Challenge,
+    /// Where the frames are written, next to the replay file.
+    dir: PathBuf,
+    started: bool,
+    /// The next frame of the script to play.
+    next: usize,
+    /// The keys held on the frame just played.
+    held: InputFrame,
+    /// Seconds of video so far, and the frames written for them.
+    clock: f32,
+    written: u32,
+    game_over_at: Option<f32>,
+}
+
+/// The image every camera that would draw to the window draws to instead
+/// while rendering, the size of the play area.
+#[derive(Resource)]
+struct FrameTarget(Handle<Image>);
+
+impl FromWorld for FrameTarget {
+    fn from_world(world: &mut World) -> Self {
+        let size = PLAY_AREA.as_uvec2();
+        let mut image = Image::new_fill(
+            Extent3d {
+                width: size.x,
+                height: size.y,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[0, 0, 0, 255],
+            TextureFormat::Bgra8UnormSrgb,
+            RenderAssetUsages::default(),
+        );
+        // Screenshots copy the frame out of the texture
+        image.texture_descriptor.usage = TextureUsages::TEXTURE_BINDING
+            | TextureUsages::COPY_SRC
+            | TextureUsages::COPY_DST
+            | TextureUsages::RENDER_ATTACHMENT;
+        Self(world.resource_mut::<Assets<Image>>().add(image))
+    }
+}
+
+// --- Components ---
+
+#[derive(Component)]
+#[require(RunScoped)]
+struct ReplayStatus;
+
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RecordedRun>()
+            .add_systems(
+                RunSetup,
+                remember_run_conditions.after(kiosk::start_kiosk_run),
+            )
+            .add_systems(
+                OnEnter(GameState::GameOver),
+                show_replay_status.run_if(not(replay_rendering)),
+            )
+            .add_systems(
+                Update,
+                (
+                    notice_gamepad.run_if(in_state(RunPhase::Alive)),
+                    save_replay.run_if(
+                        in_state(GameState::GameOver)
+                            .and(input_just_pressed(SAVE_KEY))
+                            .and(not(replay_rendering)),
+                    ),
+                ),
+            );
+
+        let Some(path) = env::args().skip_while(|arg| arg != RENDER_FLAG).nth(1) else {
+            return;
+        };
+        let path = PathBuf::from(path);
+        let render = RunScript::load(&path).and_then(|script| {
+            let challenge = Challenge::parse(&script.challenge)
+                .ok_or_else(|| "The replay's challenge code is invalid".to_string())?;
+            Ok(ReplayRender {
+                script,
+                challenge,
+                dir: path.with_extension(""),
+                started: false,
+                next: 0,
+                held: InputFrame::default(),
+                clock: 0.0,
+                written: 0,
+                game_over_at: None,
+            })
+        });
+        let render = match render {
+            Ok(render) => render,
+            Err(err) => {
+                error!(path = %path.display(), "Could not render the replay: {err}");
+                app.add_systems(Startup, give_up_render);
+                return;
+            }
+        };
+        info!(frames = %render.dir.display(), "Rendering a replay");
+        app.insert_resource(render)
+            .init_resource::<FrameTarget>()
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+                1.0 / FRAME_RATE,
+            )))
+            .add_systems(OnEnter(GameState::ProfileSelect), skip_profile_select)
+            .add_systems(First, step_replay_time.before(TimeSystem))
+            .add_systems(
+                PreUpdate,
+                play_replay_input
+                    .after(InputSystem)
+                    .before(input_buffer::buffer_inputs),
+            )
+            .add_systems(
+                Update,
+                (
+                    start_replay.run_if(in_state(GameState::Menu)),
+                    finish_replay.run_if(in_state(GameState::GameOver)),
+                ),
+            )
+            .add_systems(PostUpdate, draw_to_frame.before(CameraUpdateSystem))
+            .add_systems(Last, write_frames);
+    }
+}
+
+/// The primary window when a replay is being rendered: hidden, and the size
+/// of the play area so the HUD is laid out as in a window that fits it.
+pub fn render_window() -> Option<Window> {
+    env::args().any(|arg| arg == RENDER_FLAG).then(|| Window {
+        title: "Rendering a replay".to_string(),
+        visible: false,
+        resolution: WindowResolution::new(PLAY_AREA.x, PLAY_AREA.y).with_scale_factor_override(1.0),
+        ..default()
+    })
+}
+
+pub fn replay_rendering(render: Option<Res<ReplayRender>>) -> bool {
+    render.is_some()
+}
+
+/// System that quits when the replay couldn't be loaded, as the hidden
+/// window has nothing to show
+fn give_up_render(mut app_exit: EventWriter<AppExit>) {
+    app_exit.write(AppExit::error());
+}
+
+/// System that points every camera drawing to the window at the frame image
+/// instead, including any switched back to the window by a settings change
+fn draw_to_frame(frame: Res<FrameTarget>, mut camera_query: Query<&mut Camera>) {
+    for mut camera in &mut camera_query {
+        if matches!(camera.target, RenderTarget::Window(_)) {
+            camera.target = RenderTarget::Image(frame.0.clone().into());
+        }
+    }
+}
+
+/// System that notes the settings and history the run starts with
+fn remember_run_conditions(
+    settings: Res<Settings>,
+    adaptive: Res<AdaptiveDifficulty>,
+    practice: Res<Practice>,
+    mut run: ResMut<RecordedRun>,
+) {
+    *run = RecordedRun {
+        conditions: RunConditions {
+            safe_spawns: settings.safe_spawns,
+            forgiving_hitbox: settings.forgiving_hitbox,
+            adaptive_difficulty: settings.adaptive_difficulty,
+            adaptive: adaptive.clone(),
+            max_enemies: settings.max_enemies,
+            danger_zone: settings.danger_zone,
+            checkpoint: practice.enabled.then(|| practice.checkpoint()),
+        },
+        gamepad: false,
+    };
+}
+
+/// System that marks the run as played on a gamepad once one is touched
+fn notice_gamepad(gamepads: Query<&Gamepad>, mut run: ResMut<RecordedRun>) {
+    let touched = gamepads.iter().any(|gamepad| {
+        gamepad.get_pressed().next().is_some() || gamepad.left_stick().length() > GAMEPAD_THRESHOLD
+    });
+    if touched && !run.gamepad {
+        run.gamepad = true;
+    }
+}
+
+/// System to offer saving the run that just ended as a replay
+fn show_replay_status(mut commands: Commands, styles: Res<TextStyleLibrary>) {
+    commands.spawn((
+        Text::new(format!("{SAVE_KEY:?}: save a replay to render as video")),
+        styles.body.ui(),
+        HudSlot::new(HudAnchor::Bottom, 11),
+        ReplayStatus,
+    ));
+}
+
+/// System that writes the run that just ended to the replays folder
+fn save_replay(
+    recording: Res<InputRecording>,
+    run: Res<RecordedRun>,
+    difficulty: Res<Difficulty>,
+    arena: Res<Arena>,
+    lane_mode: Res<LaneMode>,
+    run_mutators: Res<RunMutators>,
+    seed: Res<RunSeed>,
+    score: Res<Score>,
+    stats: Res<RunStats>,
+    mut query: Query<&mut Text, With<ReplayStatus>>,
+) {
+    if run.gamepad {
+        for mut text in &mut query {
+            text.0 = "Runs played with a gamepad can't be saved as replays".to_string();
+        }
+        return;
+    }
+    let challenge = Challenge::of_run(&difficulty, &arena, &lane_mode, &run_mutators, &seed);
+    // The recording has each frame's run time, the script each frame's length
+    let mut previous = None;
+    let frames = recording
+        .frames
+        .iter()
+        .map(|&(time, input)| {
+            let delta = previous.map_or(1.0 / FRAME_RATE, |previous| time - previous);
+            previous = Some(time);
+            (delta, input.bits())
+        })
+        .collect();
+    let script = RunScript {
+        version: REPLAY_VERSION,
+        challenge: challenge.code(),
+        conditions: run.conditions.clone(),
+        frames,
+        score: score.points(),
+        elapsed: stats.elapsed(),
+    };
+    let path = save::data_dir().join(REPLAYS_DIR).join(format!(
+        "replay-{}-{}.ron",
+        rng::format_seed(seed.seed),
+        script.score
+    ));
+    let status = match save::store(&path, &script) {
+        Ok(()) => {
+            info!(path = %path.display(), "Replay saved");
+            format!(
+                "Replay saved to {}\nRender it with {RENDER_FLAG} <file>",
+                path.display()
+            )
+        }
+        Err(err) => {
+            error!(path = %path.display(), "Could not save the replay: {err}");
+            format!("Could not save the replay: {err}")
+        }
+    };
+    for mut text in &mut query {
+        text.0 = status.clone();
+    }
+}
+
+/// System that goes straight to the menu when rendering, on no profile, so
+/// the replay's settings are never saved over anyone's own
+fn skip_profile_select(mut next_state: ResMut<NextState<GameState>>) {
+    next_state.set(GameState::Menu);
+}
+
+/// System to set up the replayed run from the menu and start it
+fn start_replay(
+    mut render: ResMut<ReplayRender>,
+    mut settings: ResMut<Settings>,
+    mut adaptive: ResMut<AdaptiveDifficulty>,
+    mut difficulty: ResMut<Difficulty>,
+    mut arena: ResMut<Arena>,
+    mut lane_mode: ResMut<LaneMode>,
+    mut mutators: ResMut<MutatorSelection>,
+    mut seed: ResMut<RunSeed>,
+    mut practice: ResMut<Practice>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if render.started {
+        return;
+    }
+    render.challenge.apply(
+        &mut difficulty,
+        &mut arena,
+        &mut lane_mode,
+        &mut mutators,
+        &mut seed,
+    );
+    let conditions = &render.script.conditions;
+    settings.safe_spawns = conditions.safe_spawns;
+    settings.forgiving_hitbox = conditions.forgiving_hitbox;
+    settings.adaptive_difficulty = conditions.adaptive_difficulty;
+    settings.max_enemies = conditions.max_enemies;
+    settings.danger_zone = conditions.danger_zone;
+    // Pauses aren't recorded, so nothing may pause the replay either
+    settings.pause_on_focus_loss = false;
+    settings.afk_pause_seconds = 0;
+    *adaptive = conditions.adaptive.clone();
+    // Watching a run back isn't playing one, so nothing is recorded. Only
+    // practice runs skip to their checkpoint
+    practice.enabled = true;
+    practice.set_checkpoint(conditions.checkpoint.unwrap_or(0));
+    if let Err(err) = fs::create_dir_all(&render.dir) {
+        error!(path = %render.dir.display(), "Could not create the frames folder: {err}");
+    }
+    render.started = true;
+    next_state.set(GameState::Playing);
+}
+
+/// System that moves time on by the length of the run's next frame, or a
+/// frame of video once the recording has run out
+fn step_replay_time(render: Res<ReplayRender>, mut strategy: ResMut<TimeUpdateStrategy>) {
+    let step = match render.script.frames.get(render.next) {
+        Some(&(delta, _)) if render.started => delta,
+        _ => 1.0 / FRAME_RATE,
+    };
+    *strategy = TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(step.max(0.0)));
+}
+
+/// System that holds down the keys the run's next frame had, whatever is
+/// pressed on the real keyboard
+fn play_replay_input(
+    mut render: ResMut<ReplayRender>,
+    mut keyboard_input: ResMut<ButtonInput<KeyCode>>,
+) {
+    if !render.started {
+        return;
+    }
+    keyboard_input.reset_all();
+    let Some(&(_, bits)) = render.script.frames.get(render.next) else {
+        return;
+    };
+    render.next += 1;
+    let input = InputFrame::from_bits(bits);
+    for ((key, held), was_held) in recording::KEYS
+        .into_iter()
+        .zip(input.held())
+        .zip(render.held.held())
+    {
+        if !held {
+            continue;
+        }
+        keyboard_input.press(key);
+        // Keys held since the last frame aren't fresh presses
+        if was_held {
+            keyboard_input.clear_just_pressed(key);
+        }
+    }
+    render.held = input;
+}
+
+/// System that screenshots every frame of video the last step covered
+fn write_frames(
+    mut commands: Commands,
+    real_time: Res<Time<Real>>,
+    frame: Res<FrameTarget>,
+    mut render: ResMut<ReplayRender>,
+) {
+    if !render.started {
+        return;
+    }
+    render.clock += real_time.delta_secs();
+    let due = (render.clock * FRAME_RATE) as u32;
+    // A long step holds its picture for as many frames as it lasted
+    while render.written < due {
+        let path = render.dir.join(format!("frame-{:06}.png", render.written));
+        commands
+            .spawn(Screenshot::image(frame.0.clone()))
+            .observe(save_to_disk(path));
+        render.written += 1;
+    }
+}
+
+/// System that ends the render a little while into Game Over, checking the
+/// replay ended the way the run did
+fn finish_replay(
+    mut render: ResMut<ReplayRender>,
+    score: Res<Score>,
+    stats: Res<RunStats>,
+    mut app_exit: EventWriter<AppExit>,
+) {
+    let clock = render.clock;
+    let game_over_at = *render.game_over_at.get_or_insert(clock);
+    if clock - game_over_at < GAME_OVER_HOLD {
+        return;
+    }
+    if score.points() != render.script.score {
+        warn!(
+            recorded = render.script.score,
+            replayed = score.points(),
+            recorded_time = render.script.elapsed,
+            replayed_time = stats.elapsed(),
+            "The replay drifted from the run it was recorded from"
+        );
+    }
+    info!(frames = render.written, path = %render.dir.display(), "Replay rendered");
+    app_exit.write(AppExit::Success);
+}
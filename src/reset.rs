@@ -0,0 +1,90 @@
+use bevy::ecs::schedule::ScheduleLabel;
+use bevy::prelude::*;
+
+use crate::{GameState, RunPhase};
+
+// --- Schedules ---
+
+/// Systems that set a fresh run up. Runs on entering `GameState::Playing` and
+/// on every reset.
+#[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RunSetup;
+
+/// Systems that clear a finished run away. Runs on leaving Game Over, on
+/// quitting a run to the menu, and on every reset.
+#[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RunCleanup;
+
+// --- Components ---
+
+/// Marks an entity as part of the current run, so run clean-up removes it. The
+/// markers of run entities (player, enemies, pickups, run UI) require it, so it
+/// comes along automatically.
+#[derive(Component, Default)]
+pub struct RunScoped;
+
+// --- Events ---
+
+/// Throws the current run away and starts a new one in place, without leaving
+/// `GameState::Playing`.
+#[derive(Event)]
+pub struct ResetRunEvent;
+
+pub struct ResetPlugin;
+
+impl Plugin for ResetPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_schedule(RunSetup)
+            .init_schedule(RunCleanup)
+            .add_event::<ResetRunEvent>()
+            .add_systems(RunCleanup, despawn_run_scoped)
+            .add_systems(OnEnter(GameState::Playing), run_setup)
+            .add_systems(OnExit(GameState::GameOver), run_cleanup)
+            // Quitting a run from the pause screen skips Game Over
+            .add_systems(
+                OnTransition {
+                    exited: GameState::Playing,
+                    entered: GameState::Menu,
+                },
+                run_cleanup,
+            )
+            .add_systems(
+                Update,
+                reset_run.run_if(on_event::<ResetRunEvent>.and(in_state(GameState::Playing))),
+            );
+    }
+}
+
+/// Exclusive system that sets up a run
+fn run_setup(world: &mut World) {
+    world.run_schedule(RunSetup);
+}
+
+/// Exclusive system that clears a run away
+fn run_cleanup(world: &mut World) {
+    world.run_schedule(RunCleanup);
+}
+
+/// System to despawn everything that belonged to the run
+fn despawn_run_scoped(mut commands: Commands, query: Query<Entity, With<RunScoped>>) {
+    // Despawn right away instead of through the DespawnQueue, so nothing from
+    // the old run is still around when the next one starts. Scoped children go
+    // with their parents, so some may be gone already.
+    for entity in &query {
+        commands.entity(entity).try_despawn();
+    }
+}
+
+/// Exclusive system that swaps the current run for a fresh one
+fn reset_run(world: &mut World) {
+    world.run_schedule(RunCleanup);
+    world.run_schedule(RunSetup);
+
+    let phase = *world.resource::<State<RunPhase>>().get();
+    if phase != RunPhase::Alive {
+        world
+            .resource_mut::<NextState<RunPhase>>()
+            .set(RunPhase::Alive);
+    }
+    info!("Run reset");
+}
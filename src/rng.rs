@@ -0,0 +1,59 @@
+use bevy::prelude::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::reset::RunSetup;
+
+// --- Resources ---
+
+/// The random number generator for everything that affects gameplay. It is
+/// reseeded at the start of each run, so a seed always replays the same run.
+#[derive(Resource)]
+pub struct GameRng(pub StdRng);
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self(StdRng::seed_from_u64(0))
+    }
+}
+
+/// The seed of the current run, and the one the player asked for, if any.
+#[derive(Resource, Default)]
+pub struct RunSeed {
+    pub seed: u64,
+    /// Seed entered on the menu; when unset every run gets a fresh random seed.
+    pub fixed: Option<u64>,
+}
+
+pub struct RngPlugin;
+
+impl Plugin for RngPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameRng>()
+            .init_resource::<RunSeed>()
+            .add_systems(RunSetup, seed_run);
+    }
+}
+
+/// Formats a seed the way it is shown to and typed by players.
+pub fn format_seed(seed: u64) -> String {
+    format!("{seed:X}")
+}
+
+/// Parses a seed typed by the player.
+pub fn parse_seed(text: &str) -> Option<u64> {
+    u64::from_str_radix(text.trim(), 16).ok()
+}
+
+pub fn is_seed_char(c: char) -> bool {
+    c.is_ascii_hexdigit()
+}
+
+/// System that picks the run's seed and reseeds the gameplay RNG
+fn seed_run(mut seed: ResMut<RunSeed>, mut rng: ResMut<GameRng>) {
+    // Random seeds stay within 32 bits so they are short enough to share
+    seed.seed = seed
+        .fixed
+        .unwrap_or_else(|| u64::from(rand::rng().random::<u32>()));
+    rng.0 = StdRng::seed_from_u64(seed.seed);
+}
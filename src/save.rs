@@ -0,0 +1,218 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use bevy::log::{info_span, warn};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+const APP_DIR: &str = "rusty_dodger";
+
+/// Root directory all save data lives under.
+pub fn data_dir() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(APP_DIR)
+}
+
+/// Reads a RON file, falling back to the default value if it is missing or corrupt.
+pub fn load_or_default<T: DeserializeOwned + Default>(path: &Path) -> T {
+    match fs::read_to_string(path) {
+        Ok(contents) => ron::from_str(&contents).unwrap_or_else(|err| {
+            warn!(path = %path.display(), "Ignoring corrupt save file: {err}");
+            T::default()
+        }),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => T::default(),
+        Err(err) => {
+            warn!(path = %path.display(), "Could not read save file: {err}");
+            T::default()
+        }
+    }
+}
+
+/// A save format that records its version, so older files can be upgraded one
+/// step at a time instead of being discarded. Files from before a format had a
+/// version read as version 0.
+pub trait Versioned {
+    /// Version this build writes.
+    const VERSION: u32;
+    fn version(&self) -> u32;
+    fn set_version(&mut self, version: u32);
+    /// Upgrades the value from version `from` to `from + 1`.
+    fn migrate(&mut self, from: u32);
+}
+
+/// A save written by a newer build than this one.
+#[derive(Debug, PartialEq, Eq)]
+pub struct TooNew {
+    pub version: u32,
+    pub supported: u32,
+}
+
+impl fmt::Display for TooNew {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "saved by a newer version of the game (format {}, this one reads up to {})",
+            self.version, self.supported
+        )
+    }
+}
+
+/// Runs every migration between the value's version and the current one.
+/// Returns whether anything was upgraded.
+pub fn upgrade<T: Versioned>(value: &mut T) -> Result<bool, TooNew> {
+    let version = value.version();
+    if version > T::VERSION {
+        return Err(TooNew {
+            version,
+            supported: T::VERSION,
+        });
+    }
+    for from in version..T::VERSION {
+        value.migrate(from);
+    }
+    value.set_version(T::VERSION);
+    Ok(version < T::VERSION)
+}
+
+/// Like `load_or_default`, upgrading older files to the current version.
+/// Files from a newer build are used as they are, as far as they parse.
+pub fn load_versioned<T: DeserializeOwned + Default + Versioned>(path: &Path) -> T {
+    let mut value: T = load_or_default(path);
+    if let Err(err) = upgrade(&mut value) {
+        warn!(path = %path.display(), "Save file {err}");
+    }
+    value
+}
+
+/// Writes a value as RON, creating parent directories as needed.
+///
+/// The file is written next to its destination first and then renamed over it,
+/// so a crash mid-write never leaves a truncated save behind.
+pub fn store<T: Serialize>(path: &Path, value: &T) -> io::Result<()> {
+    let _span = info_span!("store", path = %path.display()).entered();
+    let contents = ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default())
+        .map_err(io::Error::other)?;
+    store_contents(path, &contents)
+}
+
+/// Writes already serialized contents the same way as [`store`].
+pub fn store_contents(path: &Path, contents: &str) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let temp_path = path.with_extension("tmp");
+    fs::write(&temp_path, contents)?;
+    fs::rename(&temp_path, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::profile::Progress;
+    use crate::score::HighScores;
+    use crate::settings::Settings;
+
+    #[derive(Default, Deserialize)]
+    #[serde(default)]
+    struct TestSave {
+        #[serde(default)]
+        version: u32,
+        steps: Vec<u32>,
+    }
+
+    impl Versioned for TestSave {
+        const VERSION: u32 = 3;
+
+        fn version(&self) -> u32 {
+            self.version
+        }
+
+        fn set_version(&mut self, version: u32) {
+            self.version = version;
+        }
+
+        fn migrate(&mut self, from: u32) {
+            self.steps.push(from);
+        }
+    }
+
+    #[test]
+    fn unversioned_files_read_as_version_zero() {
+        let save: TestSave = ron::from_str("(steps: [])").unwrap();
+        assert_eq!(save.version, 0);
+    }
+
+    #[test]
+    fn upgrade_runs_every_step_in_order() {
+        let mut save = TestSave {
+            version: 1,
+            ..TestSave::default()
+        };
+        assert_eq!(upgrade(&mut save), Ok(true));
+        assert_eq!(save.steps, vec![1, 2]);
+        assert_eq!(save.version, 3);
+    }
+
+    #[test]
+    fn current_files_are_left_alone() {
+        let mut save = TestSave {
+            version: 3,
+            ..TestSave::default()
+        };
+        assert_eq!(upgrade(&mut save), Ok(false));
+        assert!(save.steps.is_empty());
+    }
+
+    #[test]
+    fn newer_files_are_rejected() {
+        let mut save = TestSave {
+            version: 4,
+            ..TestSave::default()
+        };
+        assert_eq!(
+            upgrade(&mut save),
+            Err(TooNew {
+                version: 4,
+                supported: 3
+            })
+        );
+        assert_eq!(save.version, 4);
+    }
+
+    #[test]
+    fn settings_upgrade_from_version_zero() {
+        let mut settings: Settings = ron::from_str("(reduce_motion: true)").unwrap();
+        assert_eq!(upgrade(&mut settings), Ok(true));
+        assert_eq!(settings.version(), 1);
+        assert!(settings.reduce_motion);
+        assert!(settings.show_run_graphs);
+    }
+
+    #[test]
+    fn progress_upgrades_from_version_zero() {
+        let mut progress: Progress = ron::from_str("(runs_played: 12, best_score: 340)").unwrap();
+        assert_eq!(upgrade(&mut progress), Ok(true));
+        assert_eq!(progress.version(), 1);
+        assert_eq!(progress.runs_played, 12);
+        assert_eq!(progress.best_score, 340);
+    }
+
+    #[test]
+    fn high_scores_upgrade_from_version_zero() {
+        let mut high_scores: HighScores =
+            ron::from_str("(entries: [(score: 500), (score: 200)])").unwrap();
+        assert_eq!(upgrade(&mut high_scores), Ok(true));
+        assert_eq!(high_scores.version(), 1);
+        let scores: Vec<u32> = high_scores
+            .entries
+            .iter()
+            .map(|entry| entry.score)
+            .collect();
+        assert_eq!(scores, vec![500, 200]);
+    }
+}
@@ -0,0 +1,364 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::bomb::Shockwave;
+use crate::collision::{Collider, CollisionLayer};
+use crate::despawn::DespawnQueue;
+use crate::dying::Dying;
+use crate::projectile::EnemyBullet;
+use crate::reset::{RunCleanup, RunScoped};
+use crate::sets::GameSet;
+use crate::settings::{self, Settings};
+use crate::weather::WindBlown;
+use crate::window::WindowMetrics;
+use crate::{GameState, Player, RunPhase, Velocity, collide};
+
+// Scenery constants
+const SPAWN_INTERVAL: f32 = 0.7; // Seconds between scenery pieces
+const DEBRIS_CHANCE: f64 = 0.3; // Share of pieces that are breakable debris
+const DEBRIS_BREAK_DURATION: f32 = 0.2;
+const FRAGMENTS_PER_ASTEROID: (usize, usize) = (4, 7);
+const FRAGMENT_SCALE: (f32, f32) = (0.25, 0.45); // Fragment size relative to its asteroid
+const FRAGMENT_SPEED: (f32, f32) = (60.0, 180.0); // Scatter speed away from the hit
+const FRAGMENT_LIFETIME: (f32, f32) = (0.5, 1.0);
+const FRAGMENT_DRAG: f32 = 1.5; // Fraction of its speed a fragment loses per second
+const MAX_FRAGMENTS: usize = 120; // Fragments in flight at once; more shatters are skipped
+
+// --- Components ---
+
+/// Decoration scrolling past behind the action. Scenery never touches
+/// gameplay: it is skipped by hazards, bombs and the threat bar alike.
+#[derive(Component)]
+#[require(RunScoped)]
+struct Scenery {
+    spin: f32,
+}
+
+/// An asteroid that bullets and bombs break into fragments.
+#[derive(Component)]
+struct Shatterable;
+
+/// A piece of a shattered asteroid, fading out. Pooled fragments keep their
+/// entity but lose this.
+#[derive(Component)]
+struct Fragment {
+    timer: Timer,
+    spin: f32,
+    alpha: f32,
+}
+
+/// The kinds of scenery, from the far background to just behind the enemies.
+#[derive(Debug, Clone, Copy)]
+enum SceneryKind {
+    Cloud,
+    Asteroid,
+    Debris,
+}
+
+impl SceneryKind {
+    /// Size range of the kind's pieces, in pixels.
+    fn size(self) -> (f32, f32) {
+        match self {
+            SceneryKind::Cloud => (90.0, 180.0),
+            SceneryKind::Asteroid => (20.0, 50.0),
+            SceneryKind::Debris => (8.0, 16.0),
+        }
+    }
+
+    /// Scroll speed range; nearer layers pass faster for a bit of parallax.
+    fn speed(self) -> (f32, f32) {
+        match self {
+            SceneryKind::Cloud => (25.0, 45.0),
+            SceneryKind::Asteroid => (60.0, 90.0),
+            SceneryKind::Debris => (110.0, 150.0),
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            SceneryKind::Cloud => Color::srgba(0.8, 0.85, 0.95, 0.06),
+            SceneryKind::Asteroid => Color::srgba(0.45, 0.42, 0.4, 0.35),
+            SceneryKind::Debris => Color::srgba(0.6, 0.55, 0.5, 0.6),
+        }
+    }
+
+    /// How much the wind pushes the kind's pieces around.
+    fn wind_weight(self) -> f32 {
+        match self {
+            SceneryKind::Cloud => 0.5,
+            SceneryKind::Asteroid => 0.1,
+            SceneryKind::Debris => 0.3,
+        }
+    }
+
+    /// Depth below the enemies, which sit at zero.
+    fn z(self) -> f32 {
+        match self {
+            SceneryKind::Cloud => -0.9,
+            SceneryKind::Asteroid => -0.6,
+            SceneryKind::Debris => -0.3,
+        }
+    }
+}
+
+// --- Resources ---
+
+#[derive(Resource)]
+struct ScenerySpawnTimer(Timer);
+
+/// Fragment entities that are hidden and ready to be reused.
+#[derive(Resource, Default)]
+struct FragmentPool {
+    free: Vec<Entity>,
+}
+
+pub struct SceneryPlugin;
+
+impl Plugin for SceneryPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ScenerySpawnTimer(Timer::from_seconds(
+            SPAWN_INTERVAL,
+            TimerMode::Repeating,
+        )))
+        .init_resource::<FragmentPool>()
+        .add_systems(
+            Update,
+            spawn_scenery.run_if(in_state(RunPhase::Alive).and(settings::motion_enabled)),
+        )
+        .add_systems(
+            Update,
+            (
+                spin_scenery.run_if(settings::motion_enabled),
+                shatter_asteroids
+                    .in_set(GameSet::Collision)
+                    .run_if(settings::motion_enabled),
+                fade_fragments,
+                despawn_offscreen_scenery,
+            )
+                .run_if(in_state(GameState::Playing)),
+        )
+        .add_systems(RunCleanup, release_all_fragments)
+        .add_systems(
+            Update,
+            break_debris
+                .in_set(GameSet::Collision)
+                .run_if(in_state(RunPhase::Alive)),
+        );
+    }
+}
+
+/// System that sends a new piece of scenery down every so often
+fn spawn_scenery(
+    mut commands: Commands,
+    time: Res<Time>,
+    settings: Res<Settings>,
+    mut timer: ResMut<ScenerySpawnTimer>,
+    window: Res<WindowMetrics>,
+) {
+    if !timer.0.tick(time.delta()).just_finished() {
+        return;
+    }
+    // Scenery is cosmetic, so it rolls on its own generator and leaves the
+    // seeded run stream alone
+    let mut rng = rand::rng();
+    let kind = if settings.debris && rng.random_bool(DEBRIS_CHANCE) {
+        SceneryKind::Debris
+    } else if rng.random_bool(0.5) {
+        SceneryKind::Asteroid
+    } else {
+        SceneryKind::Cloud
+    };
+    let (min_size, max_size) = kind.size();
+    let (min_speed, max_speed) = kind.speed();
+    let size = rng.random_range(min_size..max_size);
+    let half_width = window.width() / 2.0;
+    let translation = Vec3::new(
+        rng.random_range(-half_width..half_width),
+        window.height() / 2.0 + size,
+        kind.z(),
+    );
+
+    let mut piece = commands.spawn((
+        Sprite::from_color(kind.color(), Vec2::ONE),
+        Transform::from_translation(translation).with_scale(Vec3::splat(size)),
+        Velocity(Vec2::new(0.0, -rng.random_range(min_speed..max_speed))),
+        Scenery {
+            spin: rng.random_range(-1.0..1.0),
+        },
+        WindBlown(kind.wind_weight()),
+    ));
+    match kind {
+        SceneryKind::Debris => {
+            piece.insert((Collider::new(Vec2::splat(size)), CollisionLayer::Debris));
+        }
+        SceneryKind::Asteroid => {
+            piece.insert(Shatterable);
+        }
+        SceneryKind::Cloud => {}
+    }
+}
+
+/// System that keeps asteroids and debris tumbling
+fn spin_scenery(time: Res<Time>, mut query: Query<(&mut Transform, &Scenery)>) {
+    for (mut transform, scenery) in &mut query {
+        transform.rotate_z(scenery.spin * time.delta_secs());
+    }
+}
+
+/// System to remove scenery that has scrolled past the bottom edge
+fn despawn_offscreen_scenery(
+    mut despawn_queue: ResMut<DespawnQueue>,
+    query: Query<(Entity, &Transform), With<Scenery>>,
+    window: Res<WindowMetrics>,
+) {
+    let bottom = -window.height() / 2.0;
+    for (entity, transform) in &query {
+        if transform.translation.y + transform.scale.y < bottom {
+            despawn_queue.push(entity);
+        }
+    }
+}
+
+/// System that breaks debris the player flies into, without any other effect
+fn break_debris(
+    mut commands: Commands,
+    player_query: Query<(&Transform, &Collider), With<Player>>,
+    debris_query: Query<(Entity, &Transform, &Collider, &CollisionLayer), Without<Dying>>,
+) {
+    let Ok((player_transform, player_collider)) = player_query.single() else {
+        return;
+    };
+    for (entity, transform, collider, layer) in &debris_query {
+        if *layer != CollisionLayer::Debris {
+            continue;
+        }
+        if collide(
+            player_transform.translation,
+            player_collider.effective_size(),
+            transform.translation,
+            collider.effective_size(),
+            transform.rotation,
+        ) {
+            commands
+                .entity(entity)
+                .insert(Dying::new(DEBRIS_BREAK_DURATION));
+        }
+    }
+}
+
+/// System that breaks asteroids hit by a bullet or caught in a bomb's blast
+/// into tumbling fragments
+fn shatter_asteroids(
+    mut commands: Commands,
+    mut pool: ResMut<FragmentPool>,
+    mut despawn_queue: ResMut<DespawnQueue>,
+    asteroid_query: Query<(Entity, &Transform, &Velocity, &Sprite), With<Shatterable>>,
+    bullet_query: Query<&Transform, With<EnemyBullet>>,
+    shockwave_query: Query<&Transform, With<Shockwave>>,
+    fragment_query: Query<(), With<Fragment>>,
+) {
+    let mut active = fragment_query.iter().count();
+    let mut rng = rand::rng();
+    for (entity, transform, velocity, sprite) in &asteroid_query {
+        let center = transform.translation.truncate();
+        let radius = transform.scale.x / 2.0;
+        let bullet_hit = bullet_query.iter().find_map(|bullet| {
+            let at = bullet.translation.truncate();
+            (at.distance(center) < radius + bullet.scale.x / 2.0).then_some(at)
+        });
+        let blast_hit = shockwave_query.iter().find_map(|wave| {
+            let at = wave.translation.truncate();
+            (at.distance(center) < wave.scale.x + radius).then_some(at)
+        });
+        let Some(from) = bullet_hit.or(blast_hit) else {
+            continue;
+        };
+        despawn_queue.push(entity);
+
+        let away = (center - from).normalize_or(Vec2::Y);
+        let count = rng.random_range(FRAGMENTS_PER_ASTEROID.0..=FRAGMENTS_PER_ASTEROID.1);
+        for _ in 0..count {
+            if active >= MAX_FRAGMENTS {
+                return;
+            }
+            active += 1;
+            // Mostly away from the hit, fanned out to either side
+            let direction = Vec2::from_angle(rng.random_range(-1.2..1.2)).rotate(away);
+            let speed = rng.random_range(FRAGMENT_SPEED.0..FRAGMENT_SPEED.1);
+            let size = transform.scale.x * rng.random_range(FRAGMENT_SCALE.0..FRAGMENT_SCALE.1);
+            let offset = direction * radius * rng.random::<f32>();
+            let fragment = (
+                Transform::from_translation((center + offset).extend(transform.translation.z))
+                    .with_rotation(transform.rotation)
+                    .with_scale(Vec3::splat(size)),
+                Velocity(velocity.0 + direction * speed),
+                Fragment {
+                    timer: Timer::from_seconds(
+                        rng.random_range(FRAGMENT_LIFETIME.0..FRAGMENT_LIFETIME.1),
+                        TimerMode::Once,
+                    ),
+                    spin: rng.random_range(-6.0..6.0),
+                    alpha: sprite.color.alpha(),
+                },
+                Sprite::from_color(sprite.color, Vec2::ONE),
+                Visibility::Visible,
+            );
+            match pool.free.pop() {
+                Some(fragment_entity) => {
+                    commands.entity(fragment_entity).insert(fragment);
+                }
+                None => {
+                    commands.spawn(fragment);
+                }
+            }
+        }
+    }
+}
+
+/// Hides a fragment and hands it back to the pool.
+fn release_fragment(commands: &mut Commands, pool: &mut FragmentPool, entity: Entity) {
+    commands
+        .entity(entity)
+        .remove::<(Fragment, Velocity)>()
+        .insert(Visibility::Hidden);
+    pool.free.push(entity);
+}
+
+/// System that slows, spins and fades fragments, pooling them when done
+fn fade_fragments(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut pool: ResMut<FragmentPool>,
+    mut query: Query<(
+        Entity,
+        &mut Fragment,
+        &mut Velocity,
+        &mut Transform,
+        &mut Sprite,
+    )>,
+) {
+    let delta = time.delta_secs();
+    for (entity, mut fragment, mut velocity, mut transform, mut sprite) in &mut query {
+        velocity.0 *= (1.0 - FRAGMENT_DRAG * delta).max(0.0);
+        transform.rotate_z(fragment.spin * delta);
+        fragment.timer.tick(time.delta());
+        sprite
+            .color
+            .set_alpha(fragment.alpha * fragment.timer.fraction_remaining());
+        if fragment.timer.finished() {
+            release_fragment(&mut commands, &mut pool, entity);
+        }
+    }
+}
+
+/// System that puts every fragment still in flight back in the pool when a
+/// run is over
+fn release_all_fragments(
+    mut commands: Commands,
+    mut pool: ResMut<FragmentPool>,
+    query: Query<Entity, With<Fragment>>,
+) {
+    for entity in &query {
+        release_fragment(&mut commands, &mut pool, entity);
+    }
+}
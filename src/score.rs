@@ -0,0 +1,156 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::RunPhase;
+use crate::arena::RiskMultiplier;
+use crate::difficulty::DifficultyPreset;
+use crate::mutator::Mutator;
+use crate::reset::RunSetup;
+use crate::save::Versioned;
+use crate::sets::GameSet;
+
+const POINTS_PER_SECOND: f32 = 10.0; // Score awarded for every second survived
+const MAX_HIGH_SCORES: usize = 10; // Entries kept in a profile's high score table
+
+// --- Resources ---
+
+/// Points earned during the current run.
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource)]
+pub struct Score(pub f32);
+
+impl Score {
+    /// The score as shown to the player.
+    pub fn points(&self) -> u32 {
+        self.0 as u32
+    }
+}
+
+/// What a `ScoreEvent` was earned for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScoreReason {
+    Survival,
+    EliteDodge,
+    BombKill,
+    Objective,
+    Jackpot,
+}
+
+// --- Events ---
+
+/// Points earned, sent by whatever earned them. Only the scoring system adds
+/// them to the score; the floating text reads the same events.
+#[derive(Event)]
+pub struct ScoreEvent {
+    pub amount: f32,
+    pub reason: ScoreReason,
+    /// Where on screen the points were earned, if anywhere in particular.
+    pub position: Option<Vec2>,
+}
+
+/// A finished run worth remembering.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct HighScoreEntry {
+    pub score: u32,
+    #[serde(default)]
+    pub difficulty: DifficultyPreset,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mutators: Vec<Mutator>,
+}
+
+/// Best runs of the active profile, highest first.
+#[derive(Resource, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HighScores {
+    #[serde(default)]
+    version: u32,
+    pub entries: Vec<HighScoreEntry>,
+}
+
+impl Default for HighScores {
+    fn default() -> Self {
+        Self {
+            version: Self::VERSION,
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl Versioned for HighScores {
+    const VERSION: u32 = 1;
+
+    fn version(&self) -> u32 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u32) {
+        self.version = version;
+    }
+
+    // Version 1 only added the version field
+    fn migrate(&mut self, _from: u32) {}
+}
+
+impl HighScores {
+    /// Inserts a run in order, returning its rank if it made the table.
+    pub fn submit(&mut self, entry: HighScoreEntry) -> Option<usize> {
+        let rank = self
+            .entries
+            .iter()
+            .position(|existing| entry.score > existing.score)
+            .unwrap_or(self.entries.len());
+        if rank >= MAX_HIGH_SCORES {
+            return None;
+        }
+        self.entries.insert(rank, entry);
+        self.entries.truncate(MAX_HIGH_SCORES);
+        Some(rank)
+    }
+}
+
+pub struct ScorePlugin;
+
+impl Plugin for ScorePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Score>()
+            .init_resource::<HighScores>()
+            .add_event::<ScoreEvent>()
+            .add_systems(RunSetup, reset_score)
+            .add_systems(
+                Update,
+                survival_score
+                    .in_set(GameSet::Simulation)
+                    .run_if(in_state(RunPhase::Alive)),
+            )
+            // After every kind of hit, so points earned this frame show this frame
+            .add_systems(Update, apply_score_events.in_set(GameSet::Cleanup));
+    }
+}
+
+/// System to start every run from zero
+fn reset_score(mut score: ResMut<Score>, mut events: ResMut<Events<ScoreEvent>>) {
+    score.0 = 0.0;
+    // Nothing earned in the last run carries over
+    events.clear();
+}
+
+/// System that awards points for staying alive, faster in risky spots
+fn survival_score(
+    time: Res<Time>,
+    risk: Res<RiskMultiplier>,
+    mut score_events: EventWriter<ScoreEvent>,
+) {
+    score_events.write(ScoreEvent {
+        amount: POINTS_PER_SECOND * risk.0 * time.delta_secs(),
+        reason: ScoreReason::Survival,
+        position: None,
+    });
+}
+
+/// System that adds up the points earned this frame
+fn apply_score_events(mut score_events: EventReader<ScoreEvent>, mut score: ResMut<Score>) {
+    let earned: f32 = score_events.read().map(|event| event.amount).sum();
+    if earned != 0.0 {
+        score.0 += earned;
+    }
+}
@@ -0,0 +1,168 @@
+use bevy::prelude::*;
+
+use crate::text_style::TextStyleLibrary;
+
+// Segmented bar constants
+const FILL_RATE: f32 = 10.0; // How quickly the fill closes in on its value, per second
+const SEGMENT_GAP: f32 = 2.0; // Pixels between two segments
+const BACKGROUND_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.15);
+const LABEL_SIZE: f32 = 11.0;
+
+// --- Components ---
+
+/// A UI bar split into equal segments, for health, meters and cooldowns. The
+/// fill eases toward its value and changes colour below the thresholds. Give
+/// it a size with its `Node`; the segments and label are added for it.
+#[derive(Component)]
+#[require(Node)]
+pub struct SegmentedBar {
+    target: f32,
+    shown: f32,
+    segments: usize,
+    color: Color,
+    /// `(fraction, colour)`: the fill takes the colour of the lowest threshold
+    /// it's at or below, and `color` above all of them.
+    thresholds: Vec<(f32, Color)>,
+    label: Option<String>,
+}
+
+impl SegmentedBar {
+    pub fn new(segments: usize, color: Color) -> Self {
+        Self {
+            target: 0.0,
+            shown: 0.0,
+            segments: segments.max(1),
+            color,
+            thresholds: Vec::new(),
+            label: None,
+        }
+    }
+
+    /// Fills the bar with `color` while it's at or below `fraction`.
+    pub fn with_threshold(mut self, fraction: f32, color: Color) -> Self {
+        self.thresholds.push((fraction, color));
+        self.thresholds.sort_by(|a, b| a.0.total_cmp(&b.0));
+        self
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Starts filling toward a new value, from 0 (empty) to 1 (full).
+    pub fn set(&mut self, fraction: f32) {
+        self.target = fraction.clamp(0.0, 1.0);
+    }
+
+    fn fill_color(&self) -> Color {
+        self.thresholds
+            .iter()
+            .find(|(fraction, _)| self.shown <= *fraction)
+            .map_or(self.color, |&(_, color)| color)
+    }
+}
+
+/// The fill of one segment, by its index in the bar.
+#[derive(Component)]
+struct SegmentFill(usize);
+
+pub struct SegmentedBarPlugin;
+
+impl Plugin for SegmentedBarPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (build_segmented_bars, animate_segmented_bars).chain(),
+        );
+    }
+}
+
+/// System that gives new bars their segments and label
+fn build_segmented_bars(
+    mut commands: Commands,
+    styles: Res<TextStyleLibrary>,
+    mut query: Query<(Entity, &SegmentedBar, &mut Node), Added<SegmentedBar>>,
+) {
+    for (entity, bar, mut node) in &mut query {
+        node.flex_direction = FlexDirection::Row;
+        node.column_gap = Val::Px(SEGMENT_GAP);
+        commands.entity(entity).with_children(|parent| {
+            for index in 0..bar.segments {
+                parent
+                    .spawn((
+                        Node {
+                            flex_grow: 1.0,
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                        BackgroundColor(BACKGROUND_COLOR),
+                    ))
+                    .with_child((
+                        Node {
+                            width: Val::Percent(0.0),
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                        BackgroundColor(bar.color),
+                        SegmentFill(index),
+                    ));
+            }
+            if let Some(label) = &bar.label {
+                // Laid over the segments rather than beside them
+                parent.spawn((
+                    Text::new(label.clone()),
+                    TextFont {
+                        font: styles.body.font.clone(),
+                        font_size: LABEL_SIZE,
+                        ..default()
+                    },
+                    Node {
+                        position_type: PositionType::Absolute,
+                        left: Val::Px(4.0),
+                        ..default()
+                    },
+                ));
+            }
+        });
+    }
+}
+
+/// System that eases each bar toward its value and recolours it
+fn animate_segmented_bars(
+    real_time: Res<Time<Real>>,
+    mut bar_query: Query<(&mut SegmentedBar, &Children)>,
+    segment_query: Query<&Children>,
+    mut fill_query: Query<(&SegmentFill, &mut Node, &mut BackgroundColor)>,
+) {
+    let delta = real_time.delta_secs();
+    for (mut bar, children) in &mut bar_query {
+        let gap = bar.target - bar.shown;
+        bar.shown = if gap.abs() < 0.001 {
+            bar.target
+        } else {
+            bar.shown + gap * (1.0 - (-FILL_RATE * delta).exp())
+        };
+
+        let color = bar.fill_color();
+        let filled = bar.shown * bar.segments as f32;
+        for &segment in children {
+            let Ok(fills) = segment_query.get(segment) else {
+                continue;
+            };
+            for &fill in fills {
+                let Ok((SegmentFill(index), mut node, mut background)) = fill_query.get_mut(fill)
+                else {
+                    continue;
+                };
+                let width = Val::Percent((filled - *index as f32).clamp(0.0, 1.0) * 100.0);
+                if node.width != width {
+                    node.width = width;
+                }
+                if background.0 != color {
+                    background.0 = color;
+                }
+            }
+        }
+    }
+}
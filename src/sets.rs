@@ -0,0 +1,70 @@
+use bevy::prelude::*;
+
+use crate::reset::RunSetup;
+
+// --- System sets ---
+
+/// The stages of a frame's gameplay in `Update`, run in this order. Systems
+/// that move things go in `Simulation` and systems that test for contact in
+/// `Collision`, so a hit is always judged on this frame's positions. Commands
+/// are applied between the two and again before `Cleanup`, so entities
+/// spawned while simulating can be hit in the same frame and hits are final
+/// before anything is cleaned up.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GameSet {
+    /// Reads the player's controls.
+    Input,
+    /// Spawns, moves and scores.
+    Simulation,
+    /// Tests for hits, near misses and pickups.
+    Collision,
+    /// Removes what went off screen or was used up.
+    Cleanup,
+    /// Brings the HUD up to date with the frame.
+    UiSync,
+}
+
+/// The stages of `RunSetup` that settle the run's difficulty, run in this
+/// order. Systems that need the difficulty the run is played at run after
+/// `DifficultyOverrides`.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RunSetupSet {
+    /// Loads the preset and applies the run's mutators.
+    Difficulty,
+    /// Changes the loaded difficulty for special runs, like stress tests and
+    /// chat votes.
+    DifficultyOverrides,
+}
+
+pub struct SetsPlugin;
+
+impl Plugin for SetsPlugin {
+    fn build(&self, app: &mut App) {
+        app.configure_sets(
+            Update,
+            (
+                GameSet::Input,
+                GameSet::Simulation,
+                GameSet::Collision,
+                GameSet::Cleanup,
+                GameSet::UiSync,
+            )
+                .chain(),
+        )
+        .configure_sets(
+            RunSetup,
+            (RunSetupSet::Difficulty, RunSetupSet::DifficultyOverrides).chain(),
+        )
+        .add_systems(
+            Update,
+            (
+                ApplyDeferred
+                    .after(GameSet::Simulation)
+                    .before(GameSet::Collision),
+                ApplyDeferred
+                    .after(GameSet::Collision)
+                    .before(GameSet::Cleanup),
+            ),
+        );
+    }
+}
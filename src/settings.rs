@@ -0,0 +1,490 @@
+use std::borrow::Cow;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::GameState;
+use crate::appearance::ShipColor;
+use crate::focus::{self, FocusActivated, MenuNav};
+use crate::heat::HeatTint;
+use crate::hud_layout::{HudAnchor, HudSlot};
+use crate::mixer;
+use crate::profile::{ActiveProfile, SETTINGS_FILE};
+use crate::progression::Unlocks;
+use crate::save::{self, Versioned};
+use crate::text_style::TextStyleLibrary;
+
+// Settings constants
+const SAVE_DELAY: f32 = 0.5; // Seconds without changes before the settings are saved
+const SAVE_ERROR_COLOR: Color = Color::srgb(1.0, 0.4, 0.4);
+
+/// Player-facing options, saved per profile.
+#[derive(Resource, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    #[serde(default)]
+    version: u32,
+    /// Draw the score and intensity graphs on the game-over screen.
+    pub show_run_graphs: bool,
+    /// Shrink the player's hitbox and forgive very short overlaps.
+    pub forgiving_hitbox: bool,
+    /// Ease or tighten spawning based on recent runs. Such runs are unranked.
+    pub adaptive_difficulty: bool,
+    /// Reopen the window where it was last session. Turning it off resets it.
+    pub remember_window: bool,
+    /// Turn off purely cosmetic motion such as camera moves, parallax and particles.
+    pub reduce_motion: bool,
+    /// Save the run instead of asking when quitting mid-run.
+    pub autosave_on_quit: bool,
+    /// Tone down flashes and blinking effects to gentle fades.
+    pub epilepsy_safe: bool,
+    /// Scatter breakable debris among the scenery.
+    pub debris: bool,
+    /// Show a run timer with score splits in the corner.
+    pub speedrun_timer: bool,
+    /// Colour of the player's ship, its trail and its shield.
+    pub ship_color: ShipColor,
+    /// Keep early spawns out of a strip directly above the player.
+    pub safe_spawns: bool,
+    /// Pause the run when the window loses focus.
+    pub pause_on_focus_loss: bool,
+    /// Pause the run after this many seconds without input; 0 never does.
+    pub afk_pause_seconds: u32,
+    /// Drop to a few updates a second while the window is in the background.
+    pub background_throttle: bool,
+    /// Silence the game while the window is in the background.
+    pub mute_in_background: bool,
+    /// Tint of the screen edges as a run heats up, or `Off`.
+    pub heat_vignette: HeatTint,
+    /// Draw the game world at a low resolution, scaled up with sharp pixels.
+    pub pixel_mode: bool,
+    /// Draw the game through scanlines and a curved screen, like an old CRT.
+    pub crt_filter: bool,
+    /// Raise a lethal zone from the bottom of the screen during arena runs.
+    pub danger_zone: bool,
+    /// Volume of everything, in percent.
+    pub master_volume: u32,
+    /// Volume of the music bus, in percent of the master volume.
+    pub music_volume: u32,
+    /// Volume of the sound effects bus, in percent of the master volume.
+    pub sfx_volume: u32,
+    /// Volume of the interface sounds bus, in percent of the master volume.
+    pub ui_volume: u32,
+    /// Show sounds on screen: flashes at the edge they came from, or all round it.
+    pub sound_cues: bool,
+    /// Most enemies out at once, holding spawns back past it; 0 has no limit.
+    pub max_enemies: u32,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            version: Self::VERSION,
+            show_run_graphs: true,
+            forgiving_hitbox: false,
+            adaptive_difficulty: false,
+            remember_window: true,
+            reduce_motion: false,
+            autosave_on_quit: false,
+            epilepsy_safe: false,
+            debris: true,
+            speedrun_timer: false,
+            ship_color: ShipColor::default(),
+            safe_spawns: false,
+            pause_on_focus_loss: true,
+            afk_pause_seconds: 30,
+            background_throttle: true,
+            mute_in_background: true,
+            heat_vignette: HeatTint::default(),
+            pixel_mode: false,
+            crt_filter: false,
+            danger_zone: false,
+            master_volume: 100,
+            music_volume: 100,
+            sfx_volume: 100,
+            ui_volume: 100,
+            sound_cues: false,
+            max_enemies: 0,
+        }
+    }
+}
+
+impl Versioned for Settings {
+    const VERSION: u32 = 1;
+
+    fn version(&self) -> u32 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u32) {
+        self.version = version;
+    }
+
+    // Version 1 only added the version field
+    fn migrate(&mut self, _from: u32) {}
+}
+
+/// One line of the settings screen.
+struct SettingItem {
+    label: &'static str,
+    value: fn(&Settings) -> Cow<'static, str>,
+    /// Flips the setting, or moves on to its next choice.
+    toggle: fn(&mut Settings),
+}
+
+fn on_off(enabled: bool) -> Cow<'static, str> {
+    Cow::Borrowed(if enabled { "On" } else { "Off" })
+}
+
+const SETTING_ITEMS: &[SettingItem] = &[
+    SettingItem {
+        label: "Show run graphs",
+        value: |s| on_off(s.show_run_graphs),
+        toggle: |s| s.show_run_graphs = !s.show_run_graphs,
+    },
+    SettingItem {
+        label: "Forgiving hitbox",
+        value: |s| on_off(s.forgiving_hitbox),
+        toggle: |s| s.forgiving_hitbox = !s.forgiving_hitbox,
+    },
+    SettingItem {
+        label: "Adaptive difficulty (unranked)",
+        value: |s| on_off(s.adaptive_difficulty),
+        toggle: |s| s.adaptive_difficulty = !s.adaptive_difficulty,
+    },
+    SettingItem {
+        label: "Remember window position",
+        value: |s| on_off(s.remember_window),
+        toggle: |s| s.remember_window = !s.remember_window,
+    },
+    SettingItem {
+        label: "Reduce motion",
+        value: |s| on_off(s.reduce_motion),
+        toggle: |s| s.reduce_motion = !s.reduce_motion,
+    },
+    SettingItem {
+        label: "Save run when quitting",
+        value: |s| on_off(s.autosave_on_quit),
+        toggle: |s| s.autosave_on_quit = !s.autosave_on_quit,
+    },
+    SettingItem {
+        label: "Epilepsy-safe flashes",
+        value: |s| on_off(s.epilepsy_safe),
+        toggle: |s| s.epilepsy_safe = !s.epilepsy_safe,
+    },
+    SettingItem {
+        label: "Breakable debris",
+        value: |s| on_off(s.debris),
+        toggle: |s| s.debris = !s.debris,
+    },
+    SettingItem {
+        label: "Speedrun timer",
+        value: |s| on_off(s.speedrun_timer),
+        toggle: |s| s.speedrun_timer = !s.speedrun_timer,
+    },
+    SettingItem {
+        label: "Safe spawns",
+        value: |s| on_off(s.safe_spawns),
+        toggle: |s| s.safe_spawns = !s.safe_spawns,
+    },
+    SettingItem {
+        label: "Pause when unfocused",
+        value: |s| on_off(s.pause_on_focus_loss),
+        toggle: |s| s.pause_on_focus_loss = !s.pause_on_focus_loss,
+    },
+    SettingItem {
+        label: "Pause when idle",
+        value: |s| match s.afk_pause_seconds {
+            0 => "Off".into(),
+            seconds => format!("After {seconds} s").into(),
+        },
+        toggle: |s| {
+            s.afk_pause_seconds = match s.afk_pause_seconds {
+                0 => 15,
+                15 => 30,
+                30 => 60,
+                _ => 0,
+            }
+        },
+    },
+    SettingItem {
+        label: "Save power in the background",
+        value: |s| on_off(s.background_throttle),
+        toggle: |s| s.background_throttle = !s.background_throttle,
+    },
+    SettingItem {
+        label: "Mute in the background",
+        value: |s| on_off(s.mute_in_background),
+        toggle: |s| s.mute_in_background = !s.mute_in_background,
+    },
+    SettingItem {
+        label: "Ship color",
+        value: |s| s.ship_color.name().into(),
+        toggle: |s| s.ship_color = s.ship_color.next(),
+    },
+    SettingItem {
+        label: "Heat vignette",
+        value: |s| s.heat_vignette.name().into(),
+        toggle: |s| s.heat_vignette = s.heat_vignette.next(),
+    },
+    SettingItem {
+        label: "Pixel-perfect mode",
+        value: |s| on_off(s.pixel_mode),
+        toggle: |s| s.pixel_mode = !s.pixel_mode,
+    },
+    SettingItem {
+        label: "CRT filter",
+        value: |s| on_off(s.crt_filter),
+        toggle: |s| s.crt_filter = !s.crt_filter,
+    },
+    SettingItem {
+        label: "Danger zone (arena)",
+        value: |s| on_off(s.danger_zone),
+        toggle: |s| s.danger_zone = !s.danger_zone,
+    },
+    SettingItem {
+        label: "Master volume",
+        value: |s| mixer::volume_label(s.master_volume).into(),
+        toggle: |s| s.master_volume = mixer::next_volume(s.master_volume),
+    },
+    SettingItem {
+        label: "Music volume",
+        value: |s| mixer::volume_label(s.music_volume).into(),
+        toggle: |s| s.music_volume = mixer::next_volume(s.music_volume),
+    },
+    SettingItem {
+        label: "Sound effects volume",
+        value: |s| mixer::volume_label(s.sfx_volume).into(),
+        toggle: |s| s.sfx_volume = mixer::next_volume(s.sfx_volume),
+    },
+    SettingItem {
+        label: "Interface volume",
+        value: |s| mixer::volume_label(s.ui_volume).into(),
+        toggle: |s| s.ui_volume = mixer::next_volume(s.ui_volume),
+    },
+    SettingItem {
+        label: "Visual sound cues",
+        value: |s| on_off(s.sound_cues),
+        toggle: |s| s.sound_cues = !s.sound_cues,
+    },
+    SettingItem {
+        label: "Enemy limit",
+        value: |s| match s.max_enemies {
+            0 => "Off".into(),
+            limit => limit.to_string().into(),
+        },
+        toggle: |s| {
+            s.max_enemies = match s.max_enemies {
+                0 => 150,
+                150 => 100,
+                100 => 60,
+                _ => 0,
+            }
+        },
+    },
+];
+
+// --- Resources ---
+
+/// Counts down to saving the settings once they stop changing.
+#[derive(Resource, Default)]
+struct PendingSettingsSave(Option<Timer>);
+
+/// Why the settings couldn't be saved last time, until a save works.
+#[derive(Resource, Default)]
+struct SettingsSaveError(Option<String>);
+
+// --- Components ---
+
+#[derive(Component)]
+struct SettingsScreen;
+
+#[derive(Component)]
+struct SettingsSaveStatus;
+
+/// One toggle of the settings screen, indexing `SETTING_ITEMS`.
+#[derive(Component)]
+struct SettingEntry(usize);
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Settings>()
+            .init_resource::<PendingSettingsSave>()
+            .init_resource::<SettingsSaveError>()
+            .add_systems(Startup, spawn_save_status)
+            .add_systems(OnEnter(GameState::Settings), spawn_settings_screen)
+            .add_systems(
+                Update,
+                (settings_input, update_settings_text)
+                    .chain()
+                    .run_if(in_state(GameState::Settings)),
+            )
+            .add_systems(
+                Update,
+                (
+                    schedule_settings_save.run_if(resource_changed::<Settings>),
+                    save_settings_when_settled,
+                    update_save_status.run_if(resource_changed::<SettingsSaveError>),
+                )
+                    .chain(),
+            )
+            .add_systems(Last, save_settings_now.run_if(on_event::<AppExit>))
+            .add_systems(OnExit(GameState::Settings), despawn_settings_screen);
+    }
+}
+
+/// Run condition for cosmetic motion (camera moves, shake, parallax, particles,
+/// scrolling backgrounds). Anything that affects gameplay must not use it.
+pub fn motion_enabled(settings: Res<Settings>) -> bool {
+    !settings.reduce_motion
+}
+
+/// System to spawn the settings screen with one entry per setting
+fn spawn_settings_screen(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(40.0),
+                left: Val::Px(40.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(4.0),
+                ..default()
+            },
+            SettingsScreen,
+        ))
+        .with_children(|parent| {
+            parent.spawn(Text::new("Settings\n"));
+            for index in 0..SETTING_ITEMS.len() {
+                parent.spawn((focus::entry("", index), SettingEntry(index)));
+            }
+            parent.spawn(Text::new("\nEnter: Change   Esc: Back"));
+        });
+}
+
+/// System to toggle the confirmed setting
+fn settings_input(
+    mut activated: EventReader<FocusActivated>,
+    nav: Res<MenuNav>,
+    unlocks: Res<Unlocks>,
+    mut settings: ResMut<Settings>,
+    entry_query: Query<&SettingEntry>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for event in activated.read() {
+        if let Ok(entry) = entry_query.get(event.0) {
+            (SETTING_ITEMS[entry.0].toggle)(&mut settings);
+            // Ship colours still locked on the progression track are skipped
+            settings.ship_color = unlocks.next_ship_color(settings.ship_color);
+        }
+    }
+    if nav.back {
+        next_state.set(GameState::Menu);
+    }
+}
+
+/// System that redraws each setting's value
+fn update_settings_text(settings: Res<Settings>, mut query: Query<(&SettingEntry, &mut Text)>) {
+    for (entry, mut text) in &mut query {
+        let item = &SETTING_ITEMS[entry.0];
+        let value = (item.value)(&settings);
+        let line = format!("{:<32} {value}", item.label);
+        if text.0 != line {
+            text.0 = line;
+        }
+    }
+}
+
+/// System to remove the settings screen
+fn despawn_settings_screen(mut commands: Commands, query: Query<Entity, With<SettingsScreen>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// System that schedules a save after the settings change, starting over on
+/// every further change
+fn schedule_settings_save(settings: Res<Settings>, mut pending: ResMut<PendingSettingsSave>) {
+    // Settings just loaded for a profile are already on disk
+    if settings.is_added() {
+        return;
+    }
+    pending.0 = Some(Timer::from_seconds(SAVE_DELAY, TimerMode::Once));
+}
+
+/// Writes the settings to the profile, keeping track of whether it worked.
+fn write_settings(profile: &ActiveProfile, settings: &Settings, error: &mut SettingsSaveError) {
+    if profile.name.is_empty() {
+        return;
+    }
+    let path = profile.dir().join(SETTINGS_FILE);
+    match save::store(&path, settings) {
+        Ok(()) => {
+            if error.0.is_some() {
+                error.0 = None;
+            }
+        }
+        Err(err) => {
+            warn!(path = %path.display(), "Could not save the settings: {err}");
+            error.0 = Some(err.to_string());
+        }
+    }
+}
+
+/// System that saves the settings once they have settled
+fn save_settings_when_settled(
+    real_time: Res<Time<Real>>,
+    profile: Res<ActiveProfile>,
+    settings: Res<Settings>,
+    mut pending: ResMut<PendingSettingsSave>,
+    mut error: ResMut<SettingsSaveError>,
+) {
+    let Some(timer) = &mut pending.0 else {
+        return;
+    };
+    if !timer.tick(real_time.delta()).finished() {
+        return;
+    }
+    pending.0 = None;
+    write_settings(&profile, &settings, &mut error);
+}
+
+/// System that saves a change still waiting for its delay as the game closes
+fn save_settings_now(
+    profile: Res<ActiveProfile>,
+    settings: Res<Settings>,
+    mut pending: ResMut<PendingSettingsSave>,
+    mut error: ResMut<SettingsSaveError>,
+) {
+    if pending.0.take().is_some() {
+        write_settings(&profile, &settings, &mut error);
+    }
+}
+
+/// System to spawn the line that reports a failed settings save
+fn spawn_save_status(mut commands: Commands, styles: Res<TextStyleLibrary>) {
+    commands.spawn((
+        Text::default(),
+        styles.body.ui(),
+        TextColor(SAVE_ERROR_COLOR),
+        HudSlot::new(HudAnchor::BottomRight, 110),
+        SettingsSaveStatus,
+    ));
+}
+
+/// System that shows why the settings couldn't be saved, or nothing once
+/// they could
+fn update_save_status(
+    error: Res<SettingsSaveError>,
+    mut query: Query<&mut Text, With<SettingsSaveStatus>>,
+) {
+    let Ok(mut text) = query.single_mut() else {
+        return;
+    };
+    text.0 = match &error.0 {
+        Some(err) => format!("Could not save settings: {err}"),
+        None => String::new(),
+    };
+}
@@ -0,0 +1,278 @@
+use bevy::asset::RenderAssetUsages;
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+
+use crate::enemy::EnemyKind;
+
+// Shape constants
+const SHAPE_RESOLUTION: u32 = 64; // Pixels per side of each shape's texture
+const SHAPE_MARGIN: f32 = 0.02; // Gap left around a shape, as a fraction of its size
+
+/// Outline an enemy or hazard is drawn with. Every kind has its own, so they
+/// can be told apart without their colours.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Shape {
+    Square,
+    /// Pointing down, the way it falls.
+    Triangle,
+    Hexagon,
+    Diamond,
+    Circle,
+    Star,
+    Ring,
+}
+
+impl Shape {
+    pub const ALL: [Shape; 7] = [
+        Shape::Square,
+        Shape::Triangle,
+        Shape::Hexagon,
+        Shape::Diamond,
+        Shape::Circle,
+        Shape::Star,
+        Shape::Ring,
+    ];
+
+    /// The part of a `size` sprite with this shape that hurts: a box centred
+    /// on it that stays inside the outline, so corners that aren't drawn
+    /// can't hit. The ring's hole is too small for anything to slip into.
+    pub fn hitbox(self, size: Vec2) -> Vec2 {
+        let fraction = match self {
+            Shape::Square => Vec2::ONE,
+            Shape::Triangle => Vec2::splat(0.3),
+            Shape::Hexagon => Vec2::new(0.8, 0.48),
+            Shape::Diamond => Vec2::splat(0.48),
+            Shape::Circle | Shape::Ring => Vec2::splat(0.68),
+            Shape::Star => Vec2::splat(0.3),
+        };
+        size * fraction
+    }
+
+    /// Signed distance from `point`, in a unit cell centred on the origin,
+    /// to the shape's outline, negative inside.
+    fn distance(self, point: Vec2) -> f32 {
+        let r = 0.5 - SHAPE_MARGIN;
+        match self {
+            Shape::Square => point.abs().max_element() - r,
+            Shape::Triangle => {
+                let side = Vec2::new(point.x.abs(), point.y + r).dot(Vec2::new(2.0, -1.0));
+                (point.y - r).max(side / 5f32.sqrt())
+            }
+            Shape::Hexagon => {
+                // Flat sides left and right, with a corner at the top and bottom
+                let k = Vec2::new(-0.866_025_4, 0.5);
+                let apothem = r * 0.866_025_4;
+                let mut p = Vec2::new(point.y, point.x).abs();
+                p -= 2.0 * k.dot(p).min(0.0) * k;
+                p -= Vec2::new(
+                    p.x.clamp(-apothem * 0.577_350_3, apothem * 0.577_350_3),
+                    apothem,
+                );
+                p.length() * p.y.signum()
+            }
+            Shape::Diamond => (point.x.abs() + point.y.abs() - r) / 2f32.sqrt(),
+            Shape::Circle => point.length() - r,
+            Shape::Star => {
+                // Five points, one straight up
+                let angle = point.y.atan2(point.x) - std::f32::consts::FRAC_PI_2;
+                let t = 0.5 + 0.5 * (5.0 * angle).cos();
+                point.length() - (r * 0.45 + r * 0.55 * t * t)
+            }
+            Shape::Ring => (point.length() - r * 0.75).abs() - r * 0.25,
+        }
+    }
+
+    /// Opacity of each pixel of the shape's texture, with smoothed edges.
+    fn coverage(self) -> Vec<u8> {
+        let size = SHAPE_RESOLUTION as f32;
+        (0..SHAPE_RESOLUTION * SHAPE_RESOLUTION)
+            .map(|index| {
+                let pixel = UVec2::new(index % SHAPE_RESOLUTION, index / SHAPE_RESOLUTION);
+                // Texture rows run top to bottom
+                let point = Vec2::new(pixel.x as f32 + 0.5, size - pixel.y as f32 - 0.5) / size
+                    - Vec2::splat(0.5);
+                let alpha = (0.5 - self.distance(point) * size).clamp(0.0, 1.0);
+                (alpha * 255.0) as u8
+            })
+            .collect()
+    }
+
+    /// A white picture of the shape, for sprites to tint.
+    fn image(self) -> Image {
+        let data = self
+            .coverage()
+            .into_iter()
+            .flat_map(|alpha| [255, 255, 255, alpha])
+            .collect();
+        Image::new(
+            Extent3d {
+                width: SHAPE_RESOLUTION,
+                height: SHAPE_RESOLUTION,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::RENDER_WORLD,
+        )
+    }
+}
+
+// --- Resources ---
+
+/// Every shape's texture, in `Shape::ALL` order.
+#[derive(Resource)]
+struct ShapeImages(Vec<Handle<Image>>);
+
+impl FromWorld for ShapeImages {
+    fn from_world(world: &mut World) -> Self {
+        let mut images = world.resource_mut::<Assets<Image>>();
+        Self(
+            Shape::ALL
+                .iter()
+                .map(|shape| images.add(shape.image()))
+                .collect(),
+        )
+    }
+}
+
+pub struct ShapePlugin;
+
+impl Plugin for ShapePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ShapeImages>()
+            .add_systems(Update, (shape_enemies, draw_shapes).chain());
+    }
+}
+
+/// System that gives enemies their kind's shape, unless they were spawned
+/// with one of their own
+fn shape_enemies(mut commands: Commands, query: Query<(Entity, &EnemyKind), Without<Shape>>) {
+    for (entity, kind) in &query {
+        commands.entity(entity).insert(kind.shape());
+    }
+}
+
+/// System that draws new or reshaped sprites with their shape's texture,
+/// including ones rebuilt after a snapshot is restored
+fn draw_shapes(
+    images: Res<ShapeImages>,
+    mut query: Query<(&Shape, &mut Sprite), Or<(Changed<Shape>, Added<Sprite>)>>,
+) {
+    for (shape, mut sprite) in &mut query {
+        let index = Shape::ALL.iter().position(|s| s == shape).unwrap_or(0);
+        sprite.image = images.0[index].clone();
+        // The sprite's transform scale is its size
+        sprite.custom_size = Some(Vec2::ONE);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::elite::EliteModifier;
+    use crate::golden::GOLDEN_SHAPE;
+    use crate::gravity_well::ORBITER_SHAPE;
+    use crate::projectile::BULLET_SHAPE;
+
+    const ENEMY_KINDS: [EnemyKind; 4] = [
+        EnemyKind::Basic,
+        EnemyKind::Fast,
+        EnemyKind::Large,
+        EnemyKind::Bouncer,
+    ];
+
+    /// Every enemy kind and hazard the player has to dodge, by name.
+    fn dodgeables() -> Vec<(String, Shape)> {
+        ENEMY_KINDS
+            .iter()
+            .map(|kind| (format!("{kind:?}"), kind.shape()))
+            .chain([
+                ("bullet".to_string(), BULLET_SHAPE),
+                ("golden enemy".to_string(), GOLDEN_SHAPE),
+                ("orbiter".to_string(), ORBITER_SHAPE),
+            ])
+            .collect()
+    }
+
+    #[test]
+    fn every_enemy_kind_and_hazard_has_its_own_shape() {
+        let mut seen = HashSet::new();
+        for (name, shape) in dodgeables() {
+            assert!(seen.insert(shape), "{name} shares the {shape:?} shape");
+        }
+    }
+
+    #[test]
+    fn elite_modifiers_have_their_own_outlines() {
+        let mut seen = HashSet::new();
+        for modifier in EliteModifier::ALL {
+            let shape = modifier.outline_shape();
+            assert!(
+                seen.insert(shape),
+                "{modifier:?} shares the {shape:?} outline"
+            );
+        }
+    }
+
+    #[test]
+    fn shape_silhouettes_are_distinct() {
+        let masks: Vec<Vec<bool>> = Shape::ALL
+            .iter()
+            .map(|shape| shape.coverage().iter().map(|&alpha| alpha >= 128).collect())
+            .collect();
+        // At least a twentieth of the pixels must differ between any two shapes
+        let min_difference = (SHAPE_RESOLUTION * SHAPE_RESOLUTION / 20) as usize;
+        for (a, mask_a) in masks.iter().enumerate() {
+            assert!(
+                mask_a.iter().filter(|&&inside| inside).count() > min_difference,
+                "{:?} is nearly empty",
+                Shape::ALL[a]
+            );
+            for (b, mask_b) in masks.iter().enumerate().skip(a + 1) {
+                let difference = mask_a.iter().zip(mask_b).filter(|(x, y)| x != y).count();
+                assert!(
+                    difference >= min_difference,
+                    "{:?} and {:?} look alike ({difference} pixels apart)",
+                    Shape::ALL[a],
+                    Shape::ALL[b]
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn hitboxes_stay_inside_their_shapes() {
+        for shape in Shape::ALL {
+            let half = shape.hitbox(Vec2::ONE) / 2.0;
+            for corner in [
+                half,
+                -half,
+                Vec2::new(half.x, -half.y),
+                Vec2::new(-half.x, half.y),
+            ] {
+                // The margin is only there to keep the edges smooth
+                let distance = shape.distance(corner);
+                assert!(
+                    distance <= SHAPE_MARGIN + 1e-4,
+                    "{shape:?} hitbox corner {corner} is {distance} outside"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn triangles_point_down() {
+        let coverage = Shape::Triangle.coverage();
+        let row = |y: u32| {
+            let start = (y * SHAPE_RESOLUTION) as usize;
+            coverage[start..start + SHAPE_RESOLUTION as usize]
+                .iter()
+                .filter(|&&alpha| alpha >= 128)
+                .count()
+        };
+        assert!(row(4) > row(SHAPE_RESOLUTION - 5));
+    }
+}
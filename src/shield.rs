@@ -0,0 +1,412 @@
+use bevy::prelude::*;
+use rand::Rng;
+use rusty_dodger::math;
+
+use crate::appearance::PlayerAppearance;
+use crate::collision::Collider;
+use crate::despawn::DespawnQueue;
+use crate::dying::Dying;
+use crate::flash;
+use crate::mixer::{Bus, PlaySound, SoundCue};
+use crate::reset::{RunScoped, RunSetup};
+use crate::rng::GameRng;
+use crate::sets::GameSet;
+use crate::settings::{Settings, motion_enabled};
+use crate::window::WindowMetrics;
+use crate::{GameState, Player, RunPhase, Velocity, collide};
+
+const SHIELD_BLINK_RATE: f32 = 10.0; // Flickers per second while shielded
+const SHIELD_MIN_ALPHA: f32 = 0.35;
+
+// Shield pickup constants
+const FIRST_PICKUP_DELAY: f32 = 25.0; // Seconds into a run before the first pickup
+const PICKUP_COOLDOWN: f32 = 30.0; // Seconds after a bubble breaks before the next pickup
+const PICKUP_SIZE: f32 = 24.0;
+const PICKUP_FALL_SPEED: f32 = 140.0;
+const PICKUP_COLOR: Color = Color::srgb(0.4, 0.9, 1.0);
+const BUBBLE_SCALE: f32 = 1.8; // Bubble diameter relative to the player
+const BUBBLE_ALPHA: f32 = 0.3; // The bubble takes its colour from the ship's accent
+const BUBBLE_GRACE: f32 = 0.75; // Immunity after the bubble breaks, so the same hit can't kill
+const SHARD_COUNT: usize = 12;
+const SHARD_SIZE: f32 = 6.0;
+const SHARD_SPEED: f32 = 260.0;
+const SHARD_LIFETIME: f32 = 0.5;
+const KNOCKBACK_SPEED: f32 = 320.0; // Minimum speed an absorbed enemy flies off at
+const KNOCKBACK_DRAG: f32 = 2.5; // Fraction of its speed lost per second
+const KNOCKBACK_SPIN: f32 = 0.02; // Radians per second per pixel per second of sideways speed
+const KNOCKBACK_DURATION: f32 = 0.6; // Seconds of flight before it starts to vanish
+const KNOCKBACK_FADE: f32 = 0.3;
+pub const BREAK_SOUND: &str = "sounds/shield_break.wav";
+
+// --- Components ---
+
+/// Makes the player immune to collisions until the timer runs out.
+#[derive(Component)]
+pub struct TemporaryShield {
+    timer: Timer,
+}
+
+impl TemporaryShield {
+    pub fn new(duration: f32) -> Self {
+        Self {
+            timer: Timer::from_seconds(duration, TimerMode::Once),
+        }
+    }
+
+    pub fn remaining_secs(&self) -> f32 {
+        self.timer.remaining_secs()
+    }
+}
+
+/// Absorbs the next hit on the player, drawn as a bubble around them.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct ShieldBubble;
+
+#[derive(Component)]
+struct BubbleSprite;
+
+/// An enemy bounced off the bubble, tumbling away without touching anything
+/// until it shrinks out of existence.
+#[derive(Component)]
+pub struct KnockedBack {
+    timer: Timer,
+    spin: f32,
+}
+
+/// A shield power-up falling toward the player.
+#[derive(Component)]
+#[require(RunScoped)]
+struct ShieldPickup;
+
+/// A fragment of a broken bubble, fading out.
+#[derive(Component)]
+#[require(RunScoped)]
+struct Shard {
+    timer: Timer,
+}
+
+// --- Resources ---
+
+/// Counts down to the next pickup. Only ticks while the player has no bubble
+/// and no pickup is on screen.
+#[derive(Resource)]
+struct PickupTimer(Timer);
+
+impl Default for PickupTimer {
+    fn default() -> Self {
+        Self(Timer::from_seconds(FIRST_PICKUP_DELAY, TimerMode::Once))
+    }
+}
+
+#[derive(Resource)]
+struct ShieldAssets {
+    circle: Handle<Mesh>,
+    pickup_material: Handle<ColorMaterial>,
+    bubble_material: Handle<ColorMaterial>,
+    break_sound: Handle<AudioSource>,
+}
+
+impl FromWorld for ShieldAssets {
+    fn from_world(world: &mut World) -> Self {
+        let circle = world.resource_mut::<Assets<Mesh>>().add(Circle::new(0.5));
+        let mut materials = world.resource_mut::<Assets<ColorMaterial>>();
+        let pickup_material = materials.add(ColorMaterial::from_color(PICKUP_COLOR));
+        let bubble_material = materials.add(ColorMaterial::from_color(Color::NONE));
+        Self {
+            circle,
+            pickup_material,
+            bubble_material,
+            break_sound: world.resource::<AssetServer>().load(BREAK_SOUND),
+        }
+    }
+}
+
+// --- Events ---
+
+/// Sent when the bubble takes a hit in the player's place.
+#[derive(Event)]
+pub struct BubbleBroken {
+    pub at: Vec2,
+}
+
+pub struct ShieldPlugin;
+
+impl Plugin for ShieldPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<BubbleBroken>()
+            .init_resource::<PickupTimer>()
+            .init_resource::<ShieldAssets>()
+            .add_systems(Update, tick_temporary_shields)
+            .add_systems(
+                Update,
+                tint_bubble.run_if(resource_changed::<PlayerAppearance>),
+            )
+            .add_systems(RunSetup, reset_pickup_timer)
+            .add_systems(
+                Update,
+                (
+                    spawn_pickups.in_set(GameSet::Simulation),
+                    (collect_pickups, break_bubbles).in_set(GameSet::Collision),
+                    despawn_offscreen_pickups.in_set(GameSet::Cleanup),
+                    spawn_shards.run_if(motion_enabled),
+                    fly_knocked_back.in_set(GameSet::Simulation),
+                )
+                    .run_if(in_state(RunPhase::Alive)),
+            )
+            .add_systems(
+                Update,
+                (add_bubble_sprites, remove_popped_bubbles, fade_shards)
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+/// System that flickers shielded players and removes expired shields
+fn tick_temporary_shields(
+    mut commands: Commands,
+    time: Res<Time>,
+    settings: Res<Settings>,
+    mut query: Query<(Entity, &mut TemporaryShield, &mut Sprite), With<Player>>,
+) {
+    for (entity, mut shield, mut sprite) in &mut query {
+        shield.timer.tick(time.delta());
+        if shield.timer.finished() {
+            sprite.color.set_alpha(1.0);
+            commands.entity(entity).remove::<TemporaryShield>();
+            continue;
+        }
+
+        sprite.color.set_alpha(flash::blink(
+            &settings,
+            shield.timer.elapsed_secs(),
+            SHIELD_BLINK_RATE,
+            SHIELD_MIN_ALPHA,
+        ));
+    }
+}
+
+/// System to hold back the first pickup of a run
+fn reset_pickup_timer(mut pickup_timer: ResMut<PickupTimer>) {
+    *pickup_timer = PickupTimer::default();
+}
+
+/// System that drops a shield pickup once the cooldown is over
+fn spawn_pickups(
+    mut commands: Commands,
+    time: Res<Time>,
+    shield_assets: Res<ShieldAssets>,
+    mut pickup_timer: ResMut<PickupTimer>,
+    mut rng: ResMut<GameRng>,
+    player_query: Query<(), (With<Player>, Without<ShieldBubble>)>,
+    pickup_query: Query<(), With<ShieldPickup>>,
+    window: Res<WindowMetrics>,
+) {
+    if player_query.is_empty() || !pickup_query.is_empty() {
+        return;
+    }
+    if !pickup_timer.0.tick(time.delta()).finished() {
+        return;
+    }
+    // Start over, so a missed pickup isn't replaced the moment it's gone
+    pickup_timer.0.reset();
+    let (x_min, x_max) = math::spawn_x_range(window.width(), PICKUP_SIZE);
+    let x = rng.0.random_range(x_min..=x_max);
+
+    commands.spawn((
+        Mesh2d(shield_assets.circle.clone()),
+        MeshMaterial2d(shield_assets.pickup_material.clone()),
+        Transform::from_xyz(x, window.height() / 2.0, 0.3).with_scale(Vec3::splat(PICKUP_SIZE)),
+        Velocity(Vec2::new(0.0, -PICKUP_FALL_SPEED)),
+        ShieldPickup,
+    ));
+}
+
+/// System that gives the player a bubble when they touch a pickup
+fn collect_pickups(
+    mut commands: Commands,
+    mut despawn_queue: ResMut<DespawnQueue>,
+    player_query: Query<(Entity, &Transform, &Collider), (With<Player>, Without<ShieldBubble>)>,
+    pickup_query: Query<(Entity, &Transform), With<ShieldPickup>>,
+) {
+    let Ok((player, player_transform, player_collider)) = player_query.single() else {
+        return;
+    };
+    for (pickup, pickup_transform) in &pickup_query {
+        if collide(
+            player_transform.translation,
+            player_collider.effective_size(),
+            pickup_transform.translation,
+            Vec2::splat(PICKUP_SIZE),
+            Quat::IDENTITY,
+        ) {
+            commands.entity(player).insert(ShieldBubble);
+            despawn_queue.push(pickup);
+        }
+    }
+}
+
+/// System to remove pickups the player let fall past the bottom of the screen
+fn despawn_offscreen_pickups(
+    mut despawn_queue: ResMut<DespawnQueue>,
+    query: Query<(Entity, &Transform), With<ShieldPickup>>,
+    window: Res<WindowMetrics>,
+) {
+    for (entity, transform) in &query {
+        if transform.translation.y + PICKUP_SIZE < -window.height() / 2.0 {
+            despawn_queue.push(entity);
+        }
+    }
+}
+
+/// System that draws the bubble around newly shielded players, including
+/// ones restored from a snapshot
+fn add_bubble_sprites(
+    mut commands: Commands,
+    shield_assets: Res<ShieldAssets>,
+    query: Query<Entity, Added<ShieldBubble>>,
+) {
+    for entity in &query {
+        commands.entity(entity).with_child((
+            Mesh2d(shield_assets.circle.clone()),
+            MeshMaterial2d(shield_assets.bubble_material.clone()),
+            Transform::from_xyz(0.0, 0.0, 0.1).with_scale(Vec3::splat(BUBBLE_SCALE)),
+            BubbleSprite,
+        ));
+    }
+}
+
+/// System that tints the bubble to match the ship
+fn tint_bubble(
+    appearance: Res<PlayerAppearance>,
+    shield_assets: Res<ShieldAssets>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    if let Some(material) = materials.get_mut(&shield_assets.bubble_material) {
+        material.color = appearance.accent.with_alpha(BUBBLE_ALPHA);
+    }
+}
+
+/// Pops the player's bubble in place of a fatal hit. The grace period keeps
+/// the same enemy from landing a second hit right after.
+pub fn pop_bubble(
+    commands: &mut Commands,
+    bubble_broken: &mut EventWriter<BubbleBroken>,
+    player: Entity,
+    at: Vec2,
+) {
+    commands
+        .entity(player)
+        .remove::<ShieldBubble>()
+        .insert(TemporaryShield::new(BUBBLE_GRACE));
+    bubble_broken.write(BubbleBroken { at });
+}
+
+/// Bounces an enemy off the bubble: its velocity is reflected about the line
+/// from the player to `at` and it stops colliding until it has faded out.
+pub fn knock_back(commands: &mut Commands, enemy: Entity, velocity: Vec2, player: Vec2, at: Vec2) {
+    let normal = (at - player).normalize_or(Vec2::Y);
+    let along = velocity.dot(normal);
+    let reflected = if along < 0.0 {
+        velocity - 2.0 * along * normal
+    } else {
+        velocity
+    };
+    let speed = reflected.length().max(KNOCKBACK_SPEED);
+    let velocity = reflected.normalize_or(normal) * speed;
+    commands.entity(enemy).insert((
+        Velocity(velocity),
+        KnockedBack {
+            timer: Timer::from_seconds(KNOCKBACK_DURATION, TimerMode::Once),
+            spin: velocity.perp_dot(normal) * KNOCKBACK_SPIN,
+        },
+    ));
+}
+
+/// System that plays the shatter sound and starts the pickup cooldown
+fn break_bubbles(
+    mut events: EventReader<BubbleBroken>,
+    mut sounds: EventWriter<PlaySound>,
+    shield_assets: Res<ShieldAssets>,
+    mut pickup_timer: ResMut<PickupTimer>,
+) {
+    for event in events.read() {
+        *pickup_timer = PickupTimer(Timer::from_seconds(PICKUP_COOLDOWN, TimerMode::Once));
+        sounds.write(PlaySound {
+            sound: shield_assets.break_sound.clone(),
+            bus: Bus::Sfx,
+            at: Some(event.at),
+            cue: SoundCue::Alarm,
+        });
+    }
+}
+
+/// System to remove the bubble sprite once its player has lost the bubble
+fn remove_popped_bubbles(
+    mut commands: Commands,
+    bubble_query: Query<(Entity, &ChildOf), With<BubbleSprite>>,
+    shielded_query: Query<(), With<ShieldBubble>>,
+) {
+    for (entity, child_of) in &bubble_query {
+        if !shielded_query.contains(child_of.parent()) {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// System that scatters bubble shards from where the hit landed
+fn spawn_shards(
+    mut commands: Commands,
+    mut events: EventReader<BubbleBroken>,
+    appearance: Res<PlayerAppearance>,
+) {
+    for event in events.read() {
+        for index in 0..SHARD_COUNT {
+            let angle = std::f32::consts::TAU * index as f32 / SHARD_COUNT as f32;
+            commands.spawn((
+                Sprite::from_color(appearance.accent.with_alpha(0.8), Vec2::ONE),
+                Transform::from_translation(event.at.extend(0.4))
+                    .with_rotation(Quat::from_rotation_z(angle))
+                    .with_scale(Vec3::splat(SHARD_SIZE)),
+                Velocity(Vec2::from_angle(angle) * SHARD_SPEED),
+                Shard {
+                    timer: Timer::from_seconds(SHARD_LIFETIME, TimerMode::Once),
+                },
+            ));
+        }
+    }
+}
+
+/// System that slows and spins knocked-back enemies, then plays their death
+/// animation once they've flown for a moment
+fn fly_knocked_back(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut KnockedBack, &mut Velocity, &mut Transform), Without<Dying>>,
+) {
+    let delta = time.delta_secs();
+    for (entity, mut knocked_back, mut velocity, mut transform) in &mut query {
+        velocity.0 *= (1.0 - KNOCKBACK_DRAG * delta).max(0.0);
+        transform.rotate_z(knocked_back.spin * delta);
+        if knocked_back.timer.tick(time.delta()).just_finished() {
+            commands.entity(entity).insert(Dying::new(KNOCKBACK_FADE));
+        }
+    }
+}
+
+/// System that fades shards out and removes them
+fn fade_shards(
+    time: Res<Time>,
+    mut despawn_queue: ResMut<DespawnQueue>,
+    mut query: Query<(Entity, &mut Shard, &mut Sprite)>,
+) {
+    for (entity, mut shard, mut sprite) in &mut query {
+        shard.timer.tick(time.delta());
+        sprite
+            .color
+            .set_alpha(0.8 * shard.timer.fraction_remaining());
+        if shard.timer.finished() {
+            despawn_queue.push(entity);
+        }
+    }
+}
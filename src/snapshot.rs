@@ -0,0 +1,134 @@
+use bevy::ecs::entity::EntityHashMap;
+use bevy::prelude::*;
+use bevy::scene::DynamicSceneBuilder;
+use bevy::scene::serde::SceneDeserializer;
+use serde::de::DeserializeSeed;
+
+use crate::appearance::PlayerAppearance;
+use crate::bomb::Bombs;
+use crate::collision::Collider;
+use crate::dying::Dying;
+use crate::elite::{Elite, Heavy, Intangible, Phasing, Shielded};
+use crate::enemy::{EnemyKind, Spin};
+use crate::graze::GrazeMeter;
+use crate::projectile::Shooter;
+use crate::score::Score;
+use crate::shield::{KnockedBack, ShieldBubble};
+use crate::stats::{Intensity, RunStats, StatSample};
+use crate::{Enemy, EnemySpawnTimer, Player, UpcomingSpawn, Velocity};
+
+pub struct SnapshotPlugin;
+
+impl Plugin for SnapshotPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Player>()
+            .register_type::<Enemy>()
+            .register_type::<EnemyKind>()
+            .register_type::<Velocity>()
+            .register_type::<Spin>()
+            .register_type::<Elite>()
+            .register_type::<Shielded>()
+            .register_type::<Phasing>()
+            .register_type::<Intangible>()
+            .register_type::<Heavy>()
+            .register_type::<Shooter>()
+            .register_type::<ShieldBubble>()
+            .register_type::<Collider>()
+            .register_type::<Score>()
+            .register_type::<RunStats>()
+            .register_type::<StatSample>()
+            .register_type::<Intensity>()
+            .register_type::<Bombs>()
+            .register_type::<GrazeMeter>()
+            .register_type::<EnemySpawnTimer>()
+            .register_type::<UpcomingSpawn>();
+    }
+}
+
+/// Gameplay entities a snapshot covers. Dying and knocked-back entities are
+/// left out, they're about to disappear anyway.
+fn gameplay_entities(world: &mut World) -> Vec<Entity> {
+    world
+        .query_filtered::<Entity, (
+            Or<(With<Player>, With<Enemy>)>,
+            Without<Dying>,
+            Without<KnockedBack>,
+        )>()
+        .iter(world)
+        .collect()
+}
+
+/// Captures the gameplay entities and run resources. Sprites hold asset
+/// handles, so they are rebuilt from the other components on restore.
+pub fn capture(world: &mut World) -> DynamicScene {
+    let entities = gameplay_entities(world);
+    DynamicSceneBuilder::from_world(world)
+        .deny_all()
+        .allow_component::<Transform>()
+        .allow_component::<Player>()
+        .allow_component::<Enemy>()
+        .allow_component::<EnemyKind>()
+        .allow_component::<Velocity>()
+        .allow_component::<Spin>()
+        .allow_component::<Elite>()
+        .allow_component::<Shielded>()
+        .allow_component::<Phasing>()
+        .allow_component::<Intangible>()
+        .allow_component::<Heavy>()
+        .allow_component::<Shooter>()
+        .allow_component::<ShieldBubble>()
+        .allow_component::<Collider>()
+        .deny_all_resources()
+        .allow_resource::<Score>()
+        .allow_resource::<RunStats>()
+        .allow_resource::<Intensity>()
+        .allow_resource::<Bombs>()
+        .allow_resource::<GrazeMeter>()
+        .allow_resource::<EnemySpawnTimer>()
+        .allow_resource::<UpcomingSpawn>()
+        .extract_entities(entities.into_iter())
+        .extract_resources()
+        .build()
+}
+
+/// Replaces the current gameplay entities and run resources with a snapshot.
+pub fn restore(world: &mut World, scene: &DynamicScene) {
+    for entity in gameplay_entities(world) {
+        world.despawn(entity);
+    }
+    if let Err(err) = scene.write_to_world(world, &mut EntityHashMap::default()) {
+        error!("Could not restore snapshot: {err}");
+    }
+
+    let ship = world.resource::<PlayerAppearance>().ship;
+    let mut query = world.query_filtered::<(Entity, Option<&EnemyKind>), (
+        Or<(With<Player>, With<Enemy>)>,
+        Without<Sprite>,
+    )>();
+    let missing: Vec<(Entity, Color)> = query
+        .iter(world)
+        .map(|(entity, kind)| (entity, kind.map_or(ship, |kind| kind.color())))
+        .collect();
+    for (entity, color) in missing {
+        world
+            .entity_mut(entity)
+            .insert(Sprite { color, ..default() });
+    }
+}
+
+/// Turns a snapshot into RON for storing on disk.
+pub fn to_ron(world: &World, scene: &DynamicScene) -> Result<String, String> {
+    let registry = world.resource::<AppTypeRegistry>().read();
+    scene.serialize(&registry).map_err(|err| err.to_string())
+}
+
+/// Reads back a snapshot stored with [`to_ron`].
+pub fn from_ron(world: &World, ron: &str) -> Result<DynamicScene, String> {
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let mut deserializer = ron::de::Deserializer::from_str(ron).map_err(|err| err.to_string())?;
+    SceneDeserializer {
+        type_registry: &registry,
+    }
+    .deserialize(&mut deserializer)
+    .map_err(|err| err.to_string())
+}
@@ -0,0 +1,60 @@
+use bevy::audio::SpatialScale;
+use bevy::prelude::*;
+
+use crate::Player;
+
+// Positional audio constants
+const EAR_GAP: f32 = 300.0; // World units between the listener's ears
+const AUDIO_SCALE: f32 = 1.0 / 600.0; // World units to audio units: sounds start fading past 600
+
+// --- Components ---
+
+/// The ears positional sounds are heard with. Follows the player, and stays
+/// where they were last seen between runs.
+#[derive(Component)]
+struct Ears;
+
+/// Settings for a one-shot sound played at `position`, panned towards the
+/// side of the player it's on and quieter the further away it is. Spawn it
+/// alongside the sound's `AudioPlayer`.
+pub fn at(position: Vec2) -> (PlaybackSettings, Transform) {
+    (
+        PlaybackSettings {
+            spatial: true,
+            spatial_scale: Some(SpatialScale::new_2d(AUDIO_SCALE)),
+            ..PlaybackSettings::DESPAWN
+        },
+        Transform::from_translation(position.extend(0.0)),
+    )
+}
+
+pub struct SpatialAudioPlugin;
+
+impl Plugin for SpatialAudioPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_ears).add_systems(
+            PostUpdate,
+            follow_player.before(TransformSystem::TransformPropagate),
+        );
+    }
+}
+
+/// System to spawn the listener, in the middle of the screen until there's a
+/// player to follow
+fn spawn_ears(mut commands: Commands) {
+    commands.spawn((SpatialListener::new(EAR_GAP), Transform::default(), Ears));
+}
+
+/// System that keeps the listener on the player
+fn follow_player(
+    player_query: Query<&Transform, (With<Player>, Without<Ears>)>,
+    mut ears_query: Query<&mut Transform, With<Ears>>,
+) {
+    let (Ok(player), Ok(mut ears)) = (player_query.single(), ears_query.single_mut()) else {
+        return;
+    };
+    let position = player.translation.truncate().extend(0.0);
+    if ears.translation != position {
+        ears.translation = position;
+    }
+}
@@ -0,0 +1,294 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::data;
+use crate::difficulty::Difficulty;
+use crate::hud_layout::{HudAnchor, HudSlot};
+use crate::practice::practice_enabled;
+use crate::profile::{ActiveProfile, SPLITS_FILE};
+use crate::reset::{RunScoped, RunSetup};
+use crate::save::Versioned;
+use crate::score::Score;
+use crate::settings::Settings;
+use crate::stats::RunStats;
+use crate::text_style::TextStyleLibrary;
+use crate::{GameState, RunPhase};
+
+const SPLITS_CONFIG_FILE: &str = "splits.ron";
+const BUILTIN_SPLITS: &str = include_str!("../assets/splits.ron");
+const LIVESPLIT_FILE: &str = "splits.lss"; // In the profile directory, for LiveSplit
+const AHEAD_COLOR: Color = Color::srgb(0.4, 0.9, 0.4);
+const BEHIND_COLOR: Color = Color::srgb(0.95, 0.4, 0.4);
+
+/// Scores that count as splits, loaded from `assets/splits.ron`.
+#[derive(Deserialize)]
+struct SplitsConfig {
+    thresholds: Vec<u32>,
+}
+
+/// A profile's best split times at one difficulty.
+#[derive(Default, Clone, Serialize, Deserialize)]
+pub struct SplitBests {
+    /// Fastest time from the start of a run to each score.
+    pub personal_best: BTreeMap<u32, f32>,
+    /// Fastest time from the previous split to each score.
+    pub best_segments: BTreeMap<u32, f32>,
+    pub attempts: u32,
+}
+
+// --- Components ---
+
+#[derive(Component)]
+#[require(RunScoped)]
+struct SplitTimer;
+
+// --- Resources ---
+
+#[derive(Resource)]
+struct SplitThresholds(Vec<u32>);
+
+impl Default for SplitThresholds {
+    fn default() -> Self {
+        let mut config: SplitsConfig = data::load_ron(SPLITS_CONFIG_FILE, BUILTIN_SPLITS);
+        config.thresholds.sort_unstable();
+        config.thresholds.dedup();
+        Self(config.thresholds)
+    }
+}
+
+/// Split times of the profile, per difficulty.
+#[derive(Resource, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PersonalBests {
+    #[serde(default)]
+    version: u32,
+    pub by_difficulty: BTreeMap<String, SplitBests>,
+}
+
+impl Default for PersonalBests {
+    fn default() -> Self {
+        Self {
+            version: Self::VERSION,
+            by_difficulty: BTreeMap::new(),
+        }
+    }
+}
+
+impl Versioned for PersonalBests {
+    const VERSION: u32 = 1;
+
+    fn version(&self) -> u32 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u32) {
+        self.version = version;
+    }
+
+    // Splits were versioned from the start
+    fn migrate(&mut self, _from: u32) {}
+}
+
+/// Splits reached in the current run, as `(score, seconds)`.
+#[derive(Resource, Default)]
+struct RunSplits(Vec<(u32, f32)>);
+
+pub struct SplitsPlugin;
+
+impl Plugin for SplitsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SplitThresholds>()
+            .init_resource::<PersonalBests>()
+            .init_resource::<RunSplits>()
+            .add_systems(RunSetup, (reset_splits, spawn_split_timer))
+            .add_systems(
+                Update,
+                (record_splits, update_split_timer)
+                    .chain()
+                    .run_if(in_state(RunPhase::Alive)),
+            )
+            .add_systems(
+                OnEnter(GameState::GameOver),
+                save_personal_bests.run_if(not(practice_enabled)),
+            );
+    }
+}
+
+/// System to clear the splits of the previous run
+fn reset_splits(mut splits: ResMut<RunSplits>) {
+    splits.0.clear();
+}
+
+/// System to spawn the timer in the top-left corner, when it's turned on
+fn spawn_split_timer(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    styles: Res<TextStyleLibrary>,
+) {
+    if !settings.speedrun_timer {
+        return;
+    }
+    commands.spawn((
+        Text::default(),
+        styles.hud.ui(),
+        HudSlot::new(HudAnchor::TopLeft, 0),
+        SplitTimer,
+    ));
+}
+
+/// System that notes the time each split score is first reached
+fn record_splits(
+    score: Res<Score>,
+    stats: Res<RunStats>,
+    thresholds: Res<SplitThresholds>,
+    mut splits: ResMut<RunSplits>,
+) {
+    let points = score.points();
+    while let Some(&next) = thresholds.0.get(splits.0.len())
+        && points >= next
+    {
+        splits.0.push((next, stats.elapsed()));
+    }
+}
+
+/// System that redraws the timer and each split against the personal best
+fn update_split_timer(
+    stats: Res<RunStats>,
+    splits: Res<RunSplits>,
+    thresholds: Res<SplitThresholds>,
+    bests: Res<PersonalBests>,
+    difficulty: Res<Difficulty>,
+    mut query: Query<(&mut Text, &mut TextColor), With<SplitTimer>>,
+) {
+    let Ok((mut text, mut color)) = query.single_mut() else {
+        return;
+    };
+    let best = bests.by_difficulty.get(difficulty.preset.name());
+    let mut lines = vec![format_time(stats.elapsed())];
+    for (index, &threshold) in thresholds.0.iter().enumerate() {
+        let pb = best.and_then(|best| best.personal_best.get(&threshold).copied());
+        let line = match (splits.0.get(index), pb) {
+            (Some(&(_, time)), Some(pb)) => {
+                format!("{threshold:>6}  {}  {:+.2}", format_time(time), time - pb)
+            }
+            (Some(&(_, time)), None) => format!("{threshold:>6}  {}", format_time(time)),
+            (None, Some(pb)) => format!("{threshold:>6}  ({})", format_time(pb)),
+            (None, None) => format!("{threshold:>6}  -"),
+        };
+        lines.push(line);
+    }
+    text.0 = lines.join("\n");
+
+    // Colour the timer by how the last split went
+    let last = splits.0.last().and_then(|&(threshold, time)| {
+        let pb = best?.personal_best.get(&threshold)?;
+        Some(time <= *pb)
+    });
+    color.0 = match last {
+        Some(true) => AHEAD_COLOR,
+        Some(false) => BEHIND_COLOR,
+        None => Color::WHITE,
+    };
+}
+
+/// System that keeps the run's improvements and exports them for LiveSplit
+fn save_personal_bests(
+    profile: Res<ActiveProfile>,
+    splits: Res<RunSplits>,
+    settings: Res<Settings>,
+    thresholds: Res<SplitThresholds>,
+    difficulty: Res<Difficulty>,
+    mut bests: ResMut<PersonalBests>,
+) {
+    // Rubber-banded runs aren't comparable with the rest
+    if settings.adaptive_difficulty {
+        return;
+    }
+    let category = difficulty.preset.name();
+    let best = bests.by_difficulty.entry(category.to_string()).or_default();
+    best.attempts += 1;
+    let mut previous = 0.0;
+    for &(threshold, time) in &splits.0 {
+        let pb = best.personal_best.entry(threshold).or_insert(time);
+        *pb = pb.min(time);
+        let segment = best
+            .best_segments
+            .entry(threshold)
+            .or_insert(time - previous);
+        *segment = segment.min(time - previous);
+        previous = time;
+    }
+    let lss = livesplit_xml(category, &thresholds.0, best);
+    profile.save(SPLITS_FILE, &*bests);
+
+    if profile.name.is_empty() {
+        return;
+    }
+    let path = profile.dir().join(LIVESPLIT_FILE);
+    if let Err(err) = fs::write(&path, lss) {
+        warn!(path = %path.display(), "Could not export splits: {err}");
+    }
+}
+
+/// `m:ss.cc`, the way the timer shows it.
+fn format_time(seconds: f32) -> String {
+    let centis = (seconds.max(0.0) * 100.0) as u32;
+    format!(
+        "{}:{:02}.{:02}",
+        centis / 6000,
+        centis / 100 % 60,
+        centis % 100
+    )
+}
+
+/// `hh:mm:ss.fffffff`, the way LiveSplit stores times.
+fn livesplit_time(seconds: f32) -> String {
+    let ticks = (f64::from(seconds.max(0.0)) * 10_000_000.0) as u64;
+    let total_seconds = ticks / 10_000_000;
+    format!(
+        "{:02}:{:02}:{:02}.{:07}",
+        total_seconds / 3600,
+        total_seconds / 60 % 60,
+        total_seconds % 60,
+        ticks % 10_000_000
+    )
+}
+
+/// A LiveSplit splits file with one segment per score split.
+fn livesplit_xml(category: &str, thresholds: &[u32], best: &SplitBests) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<Run version=\"1.7.0\">\n  <GameIcon />\n  <GameName>Rusty Dodger</GameName>\n");
+    let _ = writeln!(xml, "  <CategoryName>{category}</CategoryName>");
+    xml.push_str("  <Offset>00:00:00</Offset>\n");
+    let _ = writeln!(xml, "  <AttemptCount>{}</AttemptCount>", best.attempts);
+    xml.push_str("  <Segments>\n");
+    for threshold in thresholds {
+        let _ = writeln!(
+            xml,
+            "    <Segment>\n      <Name>{threshold} points</Name>\n      <Icon />"
+        );
+        xml.push_str("      <SplitTimes>\n        <SplitTime name=\"Personal Best\">");
+        if let Some(&time) = best.personal_best.get(threshold) {
+            let _ = write!(
+                xml,
+                "\n          <RealTime>{}</RealTime>\n        ",
+                livesplit_time(time)
+            );
+        }
+        xml.push_str("</SplitTime>\n      </SplitTimes>\n      <BestSegmentTime>");
+        if let Some(&time) = best.best_segments.get(threshold) {
+            let _ = write!(
+                xml,
+                "\n        <RealTime>{}</RealTime>\n      ",
+                livesplit_time(time)
+            );
+        }
+        xml.push_str("</BestSegmentTime>\n      <SegmentHistory />\n    </Segment>\n");
+    }
+    xml.push_str("  </Segments>\n  <AutoSplitterSettings />\n</Run>\n");
+    xml
+}
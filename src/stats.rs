@@ -0,0 +1,178 @@
+use bevy::prelude::*;
+
+use crate::reset::{RunScoped, RunSetup};
+use crate::score::Score;
+use crate::settings::Settings;
+use crate::window::WindowMetrics;
+use crate::{Enemy, GameState, RunPhase};
+
+// Run stats constants
+const SAMPLE_INTERVAL: f32 = 0.25; // Seconds between two samples of the run
+const INTENSITY_ENEMY_CAP: f32 = 15.0; // Enemies on screen that count as full intensity
+const GRAPH_SIZE: Vec2 = Vec2::new(500.0, 160.0);
+const GRAPH_BOTTOM_MARGIN: f32 = 60.0; // Distance from the bottom of the window
+const AXIS_COLOR: Color = Color::srgb(0.6, 0.6, 0.6);
+const SCORE_COLOR: Color = Color::srgb(0.3, 0.6, 1.0);
+const INTENSITY_COLOR: Color = Color::srgb(1.0, 0.6, 0.2);
+
+// --- Resources ---
+
+/// How hectic the run currently is, from 0.0 (empty screen) to 1.0.
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource)]
+pub struct Intensity(pub f32);
+
+/// One point of the run history.
+#[derive(Clone, Copy, Reflect)]
+pub struct StatSample {
+    pub time: f32,
+    pub score: f32,
+    pub intensity: f32,
+}
+
+/// Buffer of samples taken over the current run, used for the summary graphs.
+#[derive(Resource, Reflect)]
+#[reflect(Resource)]
+pub struct RunStats {
+    pub samples: Vec<StatSample>,
+    elapsed: f32,
+    sample_timer: Timer,
+}
+
+impl Default for RunStats {
+    fn default() -> Self {
+        Self {
+            samples: Vec::new(),
+            elapsed: 0.0,
+            sample_timer: Timer::from_seconds(SAMPLE_INTERVAL, TimerMode::Repeating),
+        }
+    }
+}
+
+impl RunStats {
+    /// Seconds survived so far.
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    /// Starts the run as if `elapsed` seconds had already been survived.
+    pub fn skip_to(&mut self, elapsed: f32) {
+        self.elapsed = elapsed;
+    }
+}
+
+pub struct StatsPlugin;
+
+impl Plugin for StatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Intensity>()
+            .init_resource::<RunStats>()
+            .add_systems(RunSetup, reset_run_stats)
+            .add_systems(
+                Update,
+                (update_intensity, record_run_stats)
+                    .chain()
+                    .run_if(in_state(RunPhase::Alive)),
+            )
+            .add_systems(
+                OnEnter(GameState::GameOver),
+                spawn_graph_legend.run_if(graphs_enabled),
+            )
+            .add_systems(
+                Update,
+                draw_run_graphs.run_if(in_state(GameState::GameOver).and(graphs_enabled)),
+            );
+    }
+}
+
+fn graphs_enabled(settings: Res<Settings>) -> bool {
+    settings.show_run_graphs
+}
+
+/// System to clear the previous run's history
+pub fn reset_run_stats(mut stats: ResMut<RunStats>, mut intensity: ResMut<Intensity>) {
+    *stats = RunStats::default();
+    intensity.0 = 0.0;
+}
+
+/// System that derives the current intensity from the number of enemies on screen
+fn update_intensity(mut intensity: ResMut<Intensity>, enemy_query: Query<(), With<Enemy>>) {
+    intensity.0 = (enemy_query.iter().count() as f32 / INTENSITY_ENEMY_CAP).min(1.0);
+}
+
+/// System that samples score and intensity at a fixed interval
+fn record_run_stats(
+    time: Res<Time>,
+    score: Res<Score>,
+    intensity: Res<Intensity>,
+    mut stats: ResMut<RunStats>,
+) {
+    stats.elapsed += time.delta_secs();
+    stats.sample_timer.tick(time.delta());
+
+    if stats.sample_timer.just_finished() || stats.samples.is_empty() {
+        let sample = StatSample {
+            time: stats.elapsed,
+            score: score.0,
+            intensity: intensity.0,
+        };
+        stats.samples.push(sample);
+    }
+}
+
+/// System that labels the two graph lines
+fn spawn_graph_legend(mut commands: Commands) {
+    commands.spawn((
+        Text::new("Score"),
+        TextColor(SCORE_COLOR),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(GRAPH_BOTTOM_MARGIN + GRAPH_SIZE.y + 10.0),
+            left: Val::Percent(50.0),
+            margin: UiRect::left(Val::Px(-GRAPH_SIZE.x / 2.0)),
+            ..default()
+        },
+        children![(TextSpan::new("    Intensity"), TextColor(INTENSITY_COLOR))],
+        RunScoped,
+    ));
+}
+
+/// System that draws score-over-time and intensity-over-time for the finished run
+fn draw_run_graphs(mut gizmos: Gizmos, stats: Res<RunStats>, window: Res<WindowMetrics>) {
+    let origin = Vec2::new(
+        -GRAPH_SIZE.x / 2.0,
+        -window.height() / 2.0 + GRAPH_BOTTOM_MARGIN,
+    );
+
+    // Axes
+    gizmos.line_2d(origin, origin + Vec2::new(GRAPH_SIZE.x, 0.0), AXIS_COLOR);
+    gizmos.line_2d(origin, origin + Vec2::new(0.0, GRAPH_SIZE.y), AXIS_COLOR);
+
+    let Some(last) = stats.samples.last() else {
+        return;
+    };
+    let duration = last.time.max(f32::EPSILON);
+    let max_score = stats
+        .samples
+        .iter()
+        .map(|sample| sample.score)
+        .fold(f32::EPSILON, f32::max);
+
+    let point = |time: f32, value: f32| {
+        origin + Vec2::new(time / duration * GRAPH_SIZE.x, value * GRAPH_SIZE.y)
+    };
+    gizmos.linestrip_2d(
+        stats
+            .samples
+            .iter()
+            .map(|sample| point(sample.time, sample.score / max_score)),
+        SCORE_COLOR,
+    );
+    gizmos.linestrip_2d(
+        stats
+            .samples
+            .iter()
+            .map(|sample| point(sample.time, sample.intensity)),
+        INTENSITY_COLOR,
+    );
+}
@@ -0,0 +1,142 @@
+use bevy::prelude::*;
+
+use crate::GameState;
+use crate::heatmap::DeathHeatmap;
+use crate::profile::Progress;
+
+// Stats screen constants
+const HEATMAP_WIDTH: f32 = 640.0; // Size of the play-area preview on screen
+const HEATMAP_HEIGHT: f32 = 360.0;
+
+// --- Resources ---
+
+/// Whether the death heatmap is drawn over the play-area preview.
+#[derive(Resource)]
+struct HeatmapOverlay(bool);
+
+impl Default for HeatmapOverlay {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+// --- Components ---
+
+#[derive(Component)]
+struct StatsScreen;
+
+#[derive(Component)]
+struct HeatmapImage;
+
+pub struct StatsScreenPlugin;
+
+impl Plugin for StatsScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HeatmapOverlay>()
+            .add_systems(OnEnter(GameState::Stats), spawn_stats_screen)
+            .add_systems(
+                Update,
+                (
+                    stats_screen_input,
+                    update_heatmap_visibility.run_if(resource_changed::<HeatmapOverlay>),
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Stats)),
+            )
+            .add_systems(OnExit(GameState::Stats), despawn_stats_screen);
+    }
+}
+
+/// System to spawn the profile totals and the death heatmap
+fn spawn_stats_screen(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    progress: Res<Progress>,
+    heatmap: Res<DeathHeatmap>,
+    overlay: Res<HeatmapOverlay>,
+) {
+    let minutes = progress.time_played / 60.0;
+    let summary = format!(
+        "Stats\n\nRuns played: {}\nBest score: {}\nTime played: {minutes:.1} min\nDeaths mapped: {}\n\nH: Toggle heatmap   Esc: Back",
+        progress.runs_played,
+        progress.best_score,
+        heatmap.total()
+    );
+    let image = images.add(heatmap.to_image());
+    let display = if overlay.0 {
+        Display::Flex
+    } else {
+        Display::None
+    };
+
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(40.0),
+            left: Val::Px(40.0),
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(20.0),
+            ..default()
+        },
+        StatsScreen,
+        children![
+            Text::new(summary),
+            (
+                // Outline of the play area the heatmap is laid over
+                Node {
+                    width: Val::Px(HEATMAP_WIDTH),
+                    height: Val::Px(HEATMAP_HEIGHT),
+                    border: UiRect::all(Val::Px(2.0)),
+                    ..default()
+                },
+                BackgroundColor(Color::srgb(0.08, 0.08, 0.1)),
+                BorderColor(Color::srgb(0.4, 0.4, 0.45)),
+                children![(
+                    ImageNode::new(image),
+                    Node {
+                        width: Val::Percent(100.0),
+                        height: Val::Percent(100.0),
+                        display,
+                        ..default()
+                    },
+                    HeatmapImage,
+                )],
+            ),
+        ],
+    ));
+}
+
+/// System to toggle the heatmap and leave the stats screen
+fn stats_screen_input(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut overlay: ResMut<HeatmapOverlay>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::KeyH) {
+        overlay.0 = !overlay.0;
+    }
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        next_state.set(GameState::Menu);
+    }
+}
+
+/// System that shows or hides the heatmap image
+fn update_heatmap_visibility(
+    overlay: Res<HeatmapOverlay>,
+    mut query: Query<&mut Node, With<HeatmapImage>>,
+) {
+    for mut node in &mut query {
+        node.display = if overlay.0 {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+}
+
+/// System to remove the stats screen
+fn despawn_stats_screen(mut commands: Commands, query: Query<Entity, With<StatsScreen>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
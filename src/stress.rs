@@ -0,0 +1,106 @@
+use std::env;
+
+use bevy::prelude::*;
+
+use crate::difficulty::Difficulty;
+use crate::practice::Practice;
+use crate::reset::RunSetup;
+use crate::sets::RunSetupSet;
+use crate::{Enemy, RunPhase};
+
+// Stress test constants
+const STRESS_FLAG: &str = "--stress";
+const STRESS_SPAWN_RATE: f32 = 400.0; // Enemies spawned per second, from the first frame
+const LOG_INTERVAL: f32 = 5.0; // Seconds of frame times summed up in each log line
+
+// --- Resources ---
+
+/// Hidden stress test, turned on with `--stress`: runs spawn enemies as fast
+/// as the spawner allows and the player survives every hit, so collision,
+/// spawning and despawning run at full load for as long as the run lasts.
+#[derive(Resource, Default)]
+pub struct StressTest {
+    pub enabled: bool,
+    /// Hits the player survived since the last log line.
+    pub hits: u32,
+    frame_times: Vec<f32>,
+    log_timer: Timer,
+}
+
+pub struct StressPlugin;
+
+impl Plugin for StressPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(StressTest {
+            enabled: env::args().any(|arg| arg == STRESS_FLAG),
+            log_timer: Timer::from_seconds(LOG_INTERVAL, TimerMode::Repeating),
+            ..default()
+        })
+        .add_systems(
+            RunSetup,
+            start_stress_run
+                .in_set(RunSetupSet::DifficultyOverrides)
+                .run_if(stress_enabled),
+        )
+        .add_systems(
+            Update,
+            log_frame_times.run_if(stress_enabled.and(in_state(RunPhase::Alive))),
+        );
+    }
+}
+
+pub fn stress_enabled(stress: Res<StressTest>) -> bool {
+    stress.enabled
+}
+
+/// System that floods the run with enemies and keeps it off the profile
+fn start_stress_run(
+    mut stress: ResMut<StressTest>,
+    mut difficulty: ResMut<Difficulty>,
+    mut practice: ResMut<Practice>,
+) {
+    let spawn = &mut difficulty.config.spawn;
+    spawn.start_interval = 1.0 / STRESS_SPAWN_RATE;
+    spawn.min_interval = 1.0 / STRESS_SPAWN_RATE;
+    practice.enabled = true;
+    stress.hits = 0;
+    stress.frame_times.clear();
+    stress.log_timer.reset();
+    warn!(
+        spawn_rate = STRESS_SPAWN_RATE,
+        "Stress test run: the player can't die and nothing is recorded"
+    );
+}
+
+/// System that logs frame-time statistics and the enemy count every few seconds
+fn log_frame_times(
+    real_time: Res<Time<Real>>,
+    mut stress: ResMut<StressTest>,
+    enemy_query: Query<(), With<Enemy>>,
+) {
+    stress.frame_times.push(real_time.delta_secs() * 1000.0);
+    if !stress.log_timer.tick(real_time.delta()).just_finished() {
+        return;
+    }
+    let mut frame_times = std::mem::take(&mut stress.frame_times);
+    if frame_times.is_empty() {
+        return;
+    }
+    frame_times.sort_by(f32::total_cmp);
+    let mean = frame_times.iter().sum::<f32>() / frame_times.len() as f32;
+    let p99 = frame_times[(frame_times.len() - 1) * 99 / 100];
+    let worst = frame_times[frame_times.len() - 1];
+    info!(
+        enemies = enemy_query.iter().count(),
+        frames = frame_times.len(),
+        mean_ms = %format!("{mean:.2}"),
+        p99_ms = %format!("{p99:.2}"),
+        worst_ms = %format!("{worst:.2}"),
+        hits = stress.hits,
+        "Stress test frame times"
+    );
+    stress.hits = 0;
+    // Hand the buffer back so it isn't reallocated every interval
+    frame_times.clear();
+    stress.frame_times = frame_times;
+}
@@ -0,0 +1,271 @@
+use bevy::prelude::*;
+use bevy::ui::widget::TextShadow;
+use rand::Rng;
+
+use crate::collision::Collider;
+use crate::difficulty::Difficulty;
+use crate::enemy::EnemyKind;
+use crate::flash;
+use crate::mixer::Duck;
+use crate::patterns::{self, Pattern, Reach};
+use crate::reset::{RunScoped, RunSetup};
+use crate::rng::GameRng;
+use crate::settings::Settings;
+use crate::text_style::{SHADOW_COLOR, TextStyle, TextStyleLibrary};
+use crate::window::WindowMetrics;
+use crate::{Enemy, PLAYER_SIZE, RunPhase, Velocity};
+
+// Swarm constants
+const FIRST_SWARM: f32 = 40.0; // Seconds into a run before the first swarm
+const SWARM_GAP_MIN: f32 = 45.0; // Seconds between two swarms, rolled in this range
+const SWARM_GAP_MAX: f32 = 70.0;
+const WARNING_DURATION: f32 = 2.0; // Seconds the warning shows before the first row
+const LANE_WIDTH: f32 = 50.0; // Width of one lane of the curtain
+const ROW_HEIGHT: f32 = 36.0; // Height of the enemies making up a row
+const ROWS: usize = 10; // Rows in one curtain
+const ROW_INTERVAL: f32 = 0.9; // Seconds between two rows
+const SWARM_SPEED: f32 = 0.45; // Fall speed relative to the difficulty's enemy speed
+const GAP_SLACK: usize = 2; // Lanes of room in the gap beyond the player's width
+const GENERATE_ATTEMPTS: usize = 8; // Patterns rolled before an event is skipped
+const PATTERN_CHANCE: f64 = 0.5; // Chance an event is a composed pattern rather than a curtain
+const WARNING_COLOR: Color = Color::srgb(1.0, 0.3, 0.3);
+const WARNING_BLINK_RATE: f32 = 4.0; // Blinks per second of the warning text
+const SWARM_DUCK: f32 = WARNING_DURATION + ROWS as f32 * ROW_INTERVAL; // Music stays ducked for a whole swarm
+
+// --- Resources ---
+
+/// Where the run is in its cycle of swarm and pattern events.
+#[derive(Resource)]
+enum Swarm {
+    /// Normal spawning, counting down to the next swarm.
+    Idle(Timer),
+    /// The warning is up and the curtain is ready to fall.
+    Warning { timer: Timer, pattern: Pattern },
+    /// Rows are coming down one by one.
+    Sweeping {
+        pattern: Pattern,
+        next_row: usize,
+        timer: Timer,
+    },
+}
+
+impl Default for Swarm {
+    fn default() -> Self {
+        Swarm::Idle(Timer::from_seconds(FIRST_SWARM, TimerMode::Once))
+    }
+}
+
+// --- Components ---
+
+#[derive(Component)]
+#[require(RunScoped)]
+struct SwarmWarning;
+
+/// An enemy making up a swarm or pattern, whose gaps the player reads as
+/// the way through.
+#[derive(Component)]
+pub struct PatternEnemy;
+
+// --- Events ---
+
+/// Calls the next swarm in right away, if none is under way.
+#[derive(Event)]
+pub struct SummonSwarm;
+
+pub struct SwarmPlugin;
+
+impl Plugin for SwarmPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Swarm>()
+            .add_event::<SummonSwarm>()
+            .add_systems(RunSetup, reset_swarm)
+            .add_systems(
+                Update,
+                (summon_swarm, run_swarm, flash_warning)
+                    .chain()
+                    .run_if(in_state(RunPhase::Alive)),
+            );
+    }
+}
+
+/// Run condition for regular spawning, which holds off during a swarm.
+pub fn swarm_idle(swarm: Res<Swarm>) -> bool {
+    matches!(*swarm, Swarm::Idle(_))
+}
+
+/// How far the player can move while the gap between two rows passes them.
+fn player_reach(player_speed: f32, fall_speed: f32) -> Reach {
+    let row_spacing = fall_speed * ROW_INTERVAL;
+    let free_time = ((row_spacing - ROW_HEIGHT - PLAYER_SIZE.y) / fall_speed).max(0.0);
+    Reach {
+        player_lanes: (PLAYER_SIZE.x / LANE_WIDTH).ceil() as usize,
+        max_shift: (player_speed * free_time / LANE_WIDTH) as usize,
+    }
+}
+
+/// System to start every run with the first swarm a while away
+fn reset_swarm(mut swarm: ResMut<Swarm>) {
+    *swarm = Swarm::default();
+}
+
+/// System that ends the countdown early when a swarm is summoned
+fn summon_swarm(mut summons: EventReader<SummonSwarm>, mut swarm: ResMut<Swarm>) {
+    if summons.read().count() == 0 {
+        return;
+    }
+    if let Swarm::Idle(timer) = &mut *swarm {
+        let remaining = timer.remaining();
+        timer.tick(remaining);
+    }
+}
+
+/// System that counts down to swarms, warns about them and sends the rows down
+fn run_swarm(
+    mut commands: Commands,
+    time: Res<Time>,
+    difficulty: Res<Difficulty>,
+    mut rng: ResMut<GameRng>,
+    mut swarm: ResMut<Swarm>,
+    styles: Res<TextStyleLibrary>,
+    window: Res<WindowMetrics>,
+    warning_query: Query<Entity, With<SwarmWarning>>,
+    mut ducks: EventWriter<Duck>,
+) {
+    let fall_speed = difficulty.config.enemy_speed * SWARM_SPEED;
+    let lanes = (window.width() / LANE_WIDTH) as usize;
+
+    match &mut *swarm {
+        Swarm::Idle(timer) => {
+            if !timer.tick(time.delta()).finished() {
+                return;
+            }
+            let reach = player_reach(difficulty.config.player_speed, fall_speed);
+            let gap = reach.player_lanes + GAP_SLACK;
+            if lanes <= gap {
+                warn!("Window too narrow for a swarm, skipping");
+                *swarm = next_idle(&mut rng.0);
+                return;
+            }
+            let composed = rng.0.random_bool(PATTERN_CHANCE);
+            let pattern = patterns::first_solvable(GENERATE_ATTEMPTS, reach, || {
+                if composed {
+                    patterns::compose(&mut rng.0, lanes, gap)
+                } else {
+                    patterns::curtain(&mut rng.0, lanes, ROWS, gap, reach)
+                }
+            });
+
+            let Some(pattern) = pattern else {
+                warn!("No solvable pattern for a {lanes}-lane screen, skipping");
+                *swarm = next_idle(&mut rng.0);
+                return;
+            };
+            let warning = if composed {
+                "PATTERN INCOMING"
+            } else {
+                "SWARM INCOMING"
+            };
+            commands.spawn((
+                Text::new(warning),
+                TextStyle {
+                    color: WARNING_COLOR,
+                    ..styles.popup.clone()
+                }
+                .ui(),
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Val::Percent(20.0),
+                    width: Val::Percent(100.0),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                SwarmWarning,
+            ));
+            ducks.write(Duck {
+                seconds: SWARM_DUCK,
+            });
+            *swarm = Swarm::Warning {
+                timer: Timer::from_seconds(WARNING_DURATION, TimerMode::Once),
+                pattern,
+            };
+        }
+        Swarm::Warning { timer, pattern } => {
+            if !timer.tick(time.delta()).finished() {
+                return;
+            }
+            for entity in &warning_query {
+                commands.entity(entity).despawn();
+            }
+            let lanes = pattern.lanes;
+            let pattern = std::mem::replace(pattern, Pattern::empty(lanes, 0));
+            *swarm = Swarm::Sweeping {
+                pattern,
+                next_row: 0,
+                // Already finished, so the first row drops right away
+                timer: Timer::from_seconds(0.0, TimerMode::Once),
+            };
+        }
+        Swarm::Sweeping {
+            pattern,
+            next_row,
+            timer,
+        } => {
+            if !timer.tick(time.delta()).finished() {
+                return;
+            }
+            let Some(row) = pattern.rows.get(*next_row) else {
+                *swarm = next_idle(&mut rng.0);
+                return;
+            };
+
+            // Center the lanes so any leftover width is split between both edges
+            let left = -(pattern.lanes as f32 * LANE_WIDTH) / 2.0 + LANE_WIDTH / 2.0;
+            let y = window.height() / 2.0 + ROW_HEIGHT / 2.0;
+            let size = Vec2::new(LANE_WIDTH * 0.9, ROW_HEIGHT);
+            for (lane, _) in row.iter().enumerate().filter(|(_, blocked)| **blocked) {
+                commands.spawn((
+                    Sprite {
+                        color: EnemyKind::Basic.color(),
+                        ..default()
+                    },
+                    Transform {
+                        translation: Vec3::new(left + lane as f32 * LANE_WIDTH, y, 0.0),
+                        scale: size.extend(1.0),
+                        ..default()
+                    },
+                    Visibility::Visible,
+                    Enemy,
+                    PatternEnemy,
+                    EnemyKind::Basic,
+                    Collider::new(size),
+                    Velocity(Vec2::new(0.0, -fall_speed)),
+                ));
+            }
+            *next_row += 1;
+            *timer = Timer::from_seconds(ROW_INTERVAL, TimerMode::Once);
+        }
+    }
+}
+
+fn next_idle(rng: &mut impl Rng) -> Swarm {
+    Swarm::Idle(Timer::from_seconds(
+        rng.random_range(SWARM_GAP_MIN..SWARM_GAP_MAX),
+        TimerMode::Once,
+    ))
+}
+
+/// System that blinks the swarm warning
+fn flash_warning(
+    swarm: Res<Swarm>,
+    settings: Res<Settings>,
+    mut query: Query<(&mut TextColor, &mut TextShadow), With<SwarmWarning>>,
+) {
+    let Swarm::Warning { timer, .. } = &*swarm else {
+        return;
+    };
+    let alpha = flash::blink(&settings, timer.elapsed_secs(), WARNING_BLINK_RATE, 0.2);
+    for (mut color, mut shadow) in &mut query {
+        color.0 = WARNING_COLOR.with_alpha(alpha);
+        shadow.color = SHADOW_COLOR.with_alpha(SHADOW_COLOR.alpha() * alpha);
+    }
+}
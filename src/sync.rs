@@ -0,0 +1,478 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::prelude::*;
+use bevy::tasks::{IoTaskPool, Task, block_on, futures_lite::future};
+use serde::{Deserialize, Serialize};
+
+use crate::GameState;
+use crate::input_buffer::{BufferedAction, InputBuffer};
+use crate::profile::{
+    self, DEATHS_FILE, HIGH_SCORES_FILE, PROGRESS_FILE, ProfileChosen, SETTINGS_FILE, SPLITS_FILE,
+    profile_dir,
+};
+use crate::save;
+
+// Sync constants
+const SYNC_CONFIG_FILE: &str = "sync.ron"; // In the data directory, shared by all profiles
+const SYNC_STATE_FILE: &str = "sync_state.ron"; // In each profile directory
+const SYNCED_FILES: [&str; 5] = [
+    SETTINGS_FILE,
+    PROGRESS_FILE,
+    HIGH_SCORES_FILE,
+    DEATHS_FILE,
+    SPLITS_FILE,
+];
+
+/// A profile's save files bundled up for transfer between backends.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct SaveBlob {
+    /// Unix time (seconds) of the most recent change to any file.
+    pub modified: u64,
+    /// File name to file contents.
+    pub files: BTreeMap<String, String>,
+}
+
+/// Somewhere a profile's saves can be stored and fetched from.
+pub trait SaveBackend: Send + Sync {
+    fn name(&self) -> &str;
+    /// Fetches a profile's saves, or `None` if the backend has never seen it.
+    fn pull(&self, profile: &str) -> io::Result<Option<SaveBlob>>;
+    /// Replaces a profile's saves.
+    fn push(&self, profile: &str, blob: &SaveBlob) -> io::Result<()>;
+}
+
+/// The profile directories on this machine.
+pub struct LocalBackend;
+
+impl SaveBackend for LocalBackend {
+    fn name(&self) -> &str {
+        "local"
+    }
+
+    fn pull(&self, profile: &str) -> io::Result<Option<SaveBlob>> {
+        let dir = profile_dir(profile);
+        if !dir.is_dir() {
+            return Ok(None);
+        }
+
+        let mut blob = SaveBlob::default();
+        for file in SYNCED_FILES {
+            let path = dir.join(file);
+            match fs::read_to_string(&path) {
+                Ok(contents) => {
+                    let modified = fs::metadata(&path)?.modified()?;
+                    blob.modified = blob.modified.max(unix_seconds(modified));
+                    blob.files.insert(file.to_string(), contents);
+                }
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(Some(blob))
+    }
+
+    fn push(&self, profile: &str, blob: &SaveBlob) -> io::Result<()> {
+        let dir = profile_dir(profile);
+        for (file, contents) in &blob.files {
+            // Only ever write the files we know about, whatever the remote sends
+            if SYNCED_FILES.contains(&file.as_str()) {
+                save::store_contents(&dir.join(file), contents)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A custom HTTP server storing one RON document per profile at
+/// `{endpoint}/profiles/{name}` (GET to fetch, PUT to replace).
+pub struct HttpBackend {
+    endpoint: String,
+    token: Option<String>,
+}
+
+impl HttpBackend {
+    fn url(&self, profile: &str) -> String {
+        format!(
+            "{}/profiles/{}",
+            self.endpoint.trim_end_matches('/'),
+            profile.replace(' ', "%20")
+        )
+    }
+
+    fn authorize(&self, request: ureq::Request) -> ureq::Request {
+        match &self.token {
+            Some(token) => request.set("Authorization", &format!("Bearer {token}")),
+            None => request,
+        }
+    }
+}
+
+impl SaveBackend for HttpBackend {
+    fn name(&self) -> &str {
+        "http"
+    }
+
+    fn pull(&self, profile: &str) -> io::Result<Option<SaveBlob>> {
+        match self.authorize(ureq::get(&self.url(profile))).call() {
+            Ok(response) => {
+                let body = response.into_string()?;
+                ron::from_str(&body).map(Some).map_err(io::Error::other)
+            }
+            Err(ureq::Error::Status(404, _)) => Ok(None),
+            Err(err) => Err(io::Error::other(err)),
+        }
+    }
+
+    fn push(&self, profile: &str, blob: &SaveBlob) -> io::Result<()> {
+        let body = ron::to_string(blob).map_err(io::Error::other)?;
+        self.authorize(ureq::put(&self.url(profile)))
+            .send_string(&body)
+            .map(|_| ())
+            .map_err(io::Error::other)
+    }
+}
+
+/// Contents of `sync.ron`; leaving `endpoint` unset keeps saves local only.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+struct SyncConfig {
+    endpoint: Option<String>,
+    token: Option<String>,
+}
+
+/// Per-profile record of the last time local and remote agreed.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+struct SyncState {
+    last_synced: u64,
+}
+
+/// What to do after comparing the local and remote saves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Resolution {
+    UpToDate,
+    UseLocal,
+    UseRemote,
+    Conflict,
+}
+
+/// Compares modification times against the last agreed sync point. Whichever
+/// side changed since then wins; if both did, the player has to choose.
+fn resolve(local: Option<u64>, remote: Option<u64>, last_synced: u64) -> Resolution {
+    let (local, remote) = match (local, remote) {
+        (_, None) => return Resolution::UseLocal,
+        (None, Some(_)) => return Resolution::UseRemote,
+        (Some(local), Some(remote)) => (local, remote),
+    };
+    if local == remote {
+        return Resolution::UpToDate;
+    }
+    match (local > last_synced, remote > last_synced) {
+        (true, true) => Resolution::Conflict,
+        (true, false) => Resolution::UseLocal,
+        (false, true) => Resolution::UseRemote,
+        (false, false) => Resolution::UpToDate,
+    }
+}
+
+// --- Resources ---
+
+/// Backends saves are synced between.
+#[derive(Resource)]
+pub struct SaveSync {
+    local: Arc<dyn SaveBackend>,
+    remote: Option<Arc<dyn SaveBackend>>,
+}
+
+impl Default for SaveSync {
+    fn default() -> Self {
+        let config: SyncConfig = save::load_or_default(&save::data_dir().join(SYNC_CONFIG_FILE));
+        let remote = config.endpoint.map(|endpoint| {
+            Arc::new(HttpBackend {
+                endpoint,
+                token: config.token,
+            }) as Arc<dyn SaveBackend>
+        });
+        Self {
+            local: Arc::new(LocalBackend),
+            remote,
+        }
+    }
+}
+
+/// Local and remote saves fetched for a profile that is about to be loaded.
+struct Fetched {
+    local: Option<SaveBlob>,
+    remote: Option<SaveBlob>,
+}
+
+/// A sync running in the background for the chosen profile.
+#[derive(Resource)]
+struct PendingSync {
+    profile: String,
+    task: Task<io::Result<Fetched>>,
+}
+
+/// Both sides changed since the last sync; waiting on the player.
+#[derive(Resource)]
+struct SyncConflict {
+    profile: String,
+    local: SaveBlob,
+    remote: SaveBlob,
+}
+
+impl SyncConflict {
+    fn remote_is_newer(&self) -> bool {
+        self.remote.modified > self.local.modified
+    }
+}
+
+// --- Components ---
+
+#[derive(Component)]
+struct SyncPrompt;
+
+pub struct SyncPlugin;
+
+impl Plugin for SyncPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SaveSync>()
+            .add_systems(
+                Update,
+                (
+                    start_sync.run_if(on_event::<ProfileChosen>),
+                    finish_sync.run_if(resource_exists::<PendingSync>),
+                    resolve_conflict.run_if(resource_exists::<SyncConflict>),
+                )
+                    .chain()
+                    .run_if(in_state(GameState::ProfileSelect)),
+            )
+            .add_systems(
+                OnEnter(GameState::GameOver),
+                push_after_run.after(profile::record_run),
+            );
+    }
+}
+
+fn unix_seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+fn sync_state_path(profile: &str) -> PathBuf {
+    profile_dir(profile).join(SYNC_STATE_FILE)
+}
+
+fn mark_synced(profile: &str, modified: u64) {
+    let state = SyncState {
+        last_synced: modified,
+    };
+    if let Err(err) = save::store(&sync_state_path(profile), &state) {
+        warn!(%profile, "Could not record sync state: {err}");
+    }
+}
+
+/// Pushes a profile's local saves to the remote in the background.
+fn push_in_background(sync: &SaveSync, profile: String, blob: SaveBlob) {
+    let Some(remote) = sync.remote.clone() else {
+        return;
+    };
+    IoTaskPool::get()
+        .spawn(async move {
+            match remote.push(&profile, &blob) {
+                Ok(()) => mark_synced(&profile, blob.modified),
+                Err(err) => warn!(
+                    %profile,
+                    backend = remote.name(),
+                    "Could not upload saves: {err}"
+                ),
+            }
+        })
+        .detach();
+}
+
+/// Makes the synced profile active and moves on to the main menu.
+fn enter_profile(commands: &mut Commands, next_state: &mut NextState<GameState>, profile: &str) {
+    profile::activate(commands, profile);
+    next_state.set(GameState::Menu);
+}
+
+/// System that fetches both copies of the chosen profile
+fn start_sync(
+    mut commands: Commands,
+    mut chosen: EventReader<ProfileChosen>,
+    sync: Res<SaveSync>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let Some(ProfileChosen(profile)) = chosen.read().last() else {
+        return;
+    };
+    let Some(remote) = sync.remote.clone() else {
+        enter_profile(&mut commands, &mut next_state, profile);
+        return;
+    };
+
+    let local = sync.local.clone();
+    let name = profile.clone();
+    let task = IoTaskPool::get().spawn(async move {
+        Ok(Fetched {
+            local: local.pull(&name)?,
+            remote: remote.pull(&name)?,
+        })
+    });
+    commands.insert_resource(PendingSync {
+        profile: profile.clone(),
+        task,
+    });
+}
+
+/// System that applies the sync policy once both copies have been fetched
+fn finish_sync(
+    mut commands: Commands,
+    mut pending: ResMut<PendingSync>,
+    sync: Res<SaveSync>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let Some(result) = block_on(future::poll_once(&mut pending.task)) else {
+        return;
+    };
+    commands.remove_resource::<PendingSync>();
+    let profile = pending.profile.clone();
+
+    let fetched = match result {
+        Ok(fetched) => fetched,
+        Err(err) => {
+            // Offline or misconfigured: play with what we have
+            warn!(%profile, "Could not sync, using local saves: {err}");
+            enter_profile(&mut commands, &mut next_state, &profile);
+            return;
+        }
+    };
+
+    let state: SyncState = save::load_or_default(&sync_state_path(&profile));
+    let resolution = resolve(
+        fetched.local.as_ref().map(|blob| blob.modified),
+        fetched.remote.as_ref().map(|blob| blob.modified),
+        state.last_synced,
+    );
+
+    match (resolution, fetched.local, fetched.remote) {
+        (Resolution::Conflict, Some(local), Some(remote)) => {
+            let conflict = SyncConflict {
+                profile,
+                local,
+                remote,
+            };
+            spawn_conflict_prompt(&mut commands, &conflict);
+            commands.insert_resource(conflict);
+        }
+        (Resolution::UseRemote, _, Some(remote)) => {
+            apply_remote(&sync, &profile, &remote);
+            enter_profile(&mut commands, &mut next_state, &profile);
+        }
+        (Resolution::UseLocal, Some(local), _) => {
+            push_in_background(&sync, profile.clone(), local);
+            enter_profile(&mut commands, &mut next_state, &profile);
+        }
+        _ => enter_profile(&mut commands, &mut next_state, &profile),
+    }
+}
+
+/// Overwrites the local profile with the remote copy.
+fn apply_remote(sync: &SaveSync, profile: &str, remote: &SaveBlob) {
+    // Writing the files touches them, so the sync point is the new local time
+    let written = sync
+        .local
+        .push(profile, remote)
+        .and_then(|()| sync.local.pull(profile));
+    match written {
+        Ok(local) => mark_synced(profile, local.map_or(remote.modified, |blob| blob.modified)),
+        Err(err) => error!(%profile, "Could not apply remote saves: {err}"),
+    }
+}
+
+fn spawn_conflict_prompt(commands: &mut Commands, conflict: &SyncConflict) {
+    let newest = if conflict.remote_is_newer() {
+        "cloud"
+    } else {
+        "local"
+    };
+    commands.spawn((
+        Text(format!(
+            "Save conflict for '{}'\nLocal: changed {}\nCloud: changed {}\n\
+             Enter: keep {newest} (newest)   L: keep local   C: keep cloud",
+            conflict.profile,
+            describe_age(conflict.local.modified),
+            describe_age(conflict.remote.modified),
+        )),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(40.0),
+            left: Val::Px(40.0),
+            ..default()
+        },
+        SyncPrompt,
+    ));
+}
+
+/// Describes how long ago a save was last changed.
+fn describe_age(modified: u64) -> String {
+    let minutes = unix_seconds(SystemTime::now()).saturating_sub(modified) / 60;
+    match minutes {
+        0 => "just now".to_string(),
+        1..60 => format!("{minutes} min ago"),
+        60..1440 => format!("{} h ago", minutes / 60),
+        _ => format!("{} days ago", minutes / 1440),
+    }
+}
+
+/// System that asks the player which copy to keep, defaulting to the newest
+fn resolve_conflict(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut input_buffer: ResMut<InputBuffer>,
+    conflict: Res<SyncConflict>,
+    sync: Res<SaveSync>,
+    prompt_query: Query<Entity, With<SyncPrompt>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let keep_remote = if input_buffer.consume(BufferedAction::Confirm) {
+        conflict.remote_is_newer()
+    } else if keyboard_input.just_pressed(KeyCode::KeyC) {
+        true
+    } else if keyboard_input.just_pressed(KeyCode::KeyL) {
+        false
+    } else {
+        return;
+    };
+
+    if keep_remote {
+        apply_remote(&sync, &conflict.profile, &conflict.remote);
+    } else {
+        push_in_background(&sync, conflict.profile.clone(), conflict.local.clone());
+    }
+    enter_profile(&mut commands, &mut next_state, &conflict.profile);
+
+    for entity in &prompt_query {
+        commands.entity(entity).despawn();
+    }
+    commands.remove_resource::<SyncConflict>();
+}
+
+/// System that uploads the profile after its saves changed at the end of a run
+fn push_after_run(sync: Res<SaveSync>, profile: Res<profile::ActiveProfile>) {
+    if sync.remote.is_none() || profile.name.is_empty() {
+        return;
+    }
+    match sync.local.pull(&profile.name) {
+        Ok(Some(blob)) => push_in_background(&sync, profile.name.clone(), blob),
+        Ok(None) => {}
+        Err(err) => warn!(profile = %profile.name, "Could not read saves for upload: {err}"),
+    }
+}
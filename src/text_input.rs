@@ -0,0 +1,48 @@
+use bevy::input::ButtonState;
+use bevy::input::keyboard::{Key, KeyboardInput};
+
+/// What a key press did to a text field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextInputAction {
+    None,
+    Edited,
+    Submit,
+    Cancel,
+}
+
+/// Applies one keyboard event to a text field, keeping only the characters
+/// `accept` allows and at most `max_len` of them.
+pub fn apply_key(
+    buffer: &mut String,
+    event: &KeyboardInput,
+    max_len: usize,
+    accept: impl Fn(char) -> bool,
+) -> TextInputAction {
+    if event.state != ButtonState::Pressed {
+        return TextInputAction::None;
+    }
+
+    let mut push = |c: char| {
+        if accept(c) && buffer.chars().count() < max_len {
+            buffer.push(c);
+        }
+    };
+
+    match &event.logical_key {
+        Key::Enter => TextInputAction::Submit,
+        Key::Escape => TextInputAction::Cancel,
+        Key::Backspace => {
+            buffer.pop();
+            TextInputAction::Edited
+        }
+        Key::Space => {
+            push(' ');
+            TextInputAction::Edited
+        }
+        Key::Character(chars) => {
+            chars.chars().for_each(&mut push);
+            TextInputAction::Edited
+        }
+        _ => TextInputAction::None,
+    }
+}
@@ -0,0 +1,88 @@
+use bevy::prelude::*;
+use bevy::ui::widget::TextShadow;
+
+// Text style constants
+pub const FONT_REGULAR: &str = "fonts/FiraSans-Regular.ttf";
+pub const FONT_BOLD: &str = "fonts/FiraSans-Bold.ttf";
+pub const SHADOW_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.75);
+
+/// Font, size and colour of one kind of text. Every style casts a drop shadow
+/// so it stays readable over enemies and flashes.
+#[derive(Clone)]
+pub struct TextStyle {
+    pub font: Handle<Font>,
+    pub size: f32,
+    pub color: Color,
+    pub shadow_offset: Vec2,
+}
+
+impl TextStyle {
+    /// Components that give a UI text this style.
+    pub fn ui(&self) -> impl Bundle {
+        (
+            TextFont {
+                font: self.font.clone(),
+                font_size: self.size,
+                ..default()
+            },
+            TextColor(self.color),
+            TextShadow {
+                offset: self.shadow_offset,
+                color: SHADOW_COLOR,
+            },
+        )
+    }
+}
+
+// --- Resources ---
+
+/// The game's text styles, so screens share one look.
+#[derive(Resource)]
+pub struct TextStyleLibrary {
+    pub title: TextStyle,
+    pub hud: TextStyle,
+    pub body: TextStyle,
+    pub popup: TextStyle,
+}
+
+impl FromWorld for TextStyleLibrary {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+        let regular = asset_server.load(FONT_REGULAR);
+        let bold = asset_server.load(FONT_BOLD);
+        Self {
+            title: TextStyle {
+                font: bold.clone(),
+                size: 56.0,
+                color: Color::WHITE,
+                shadow_offset: Vec2::splat(3.0),
+            },
+            hud: TextStyle {
+                font: bold.clone(),
+                size: 20.0,
+                color: Color::WHITE,
+                shadow_offset: Vec2::splat(2.0),
+            },
+            body: TextStyle {
+                font: regular,
+                size: 20.0,
+                color: Color::srgb(0.9, 0.9, 0.9),
+                shadow_offset: Vec2::splat(1.5),
+            },
+            popup: TextStyle {
+                font: bold,
+                size: 48.0,
+                color: Color::srgb(1.0, 0.85, 0.3),
+                shadow_offset: Vec2::splat(3.0),
+            },
+        }
+    }
+}
+
+pub struct TextStylePlugin;
+
+impl Plugin for TextStylePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TextStyleLibrary>();
+    }
+}
@@ -0,0 +1,122 @@
+use bevy::prelude::*;
+
+use crate::dying::Dying;
+use crate::enemy::EnemyKind;
+use crate::reset::{RunScoped, RunSetup};
+use crate::window::WindowMetrics;
+use crate::{Enemy, EnemySpawnTimer, Player, RunPhase, UpcomingSpawn};
+
+// Threat bar constants
+const BAR_HEIGHT: f32 = 6.0;
+const BAR_BACKGROUND: Color = Color::srgba(0.0, 0.0, 0.0, 0.5);
+const MARKER_WIDTH: f32 = 4.0;
+const MAX_MARKERS: usize = 64; // Further enemies are left off the bar
+const UPCOMING_COLOR: Color = Color::WHITE;
+const FAR_ALPHA: f32 = 0.3; // Opacity of markers for enemies just entering the screen
+
+// --- Components ---
+
+/// The strip along the top edge showing where threats are across the screen.
+#[derive(Component)]
+#[require(RunScoped)]
+struct ThreatBar;
+
+#[derive(Component)]
+struct ThreatMarker;
+
+pub struct ThreatPlugin;
+
+impl Plugin for ThreatPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(RunSetup, spawn_threat_bar)
+            .add_systems(Update, update_threat_bar.run_if(in_state(RunPhase::Alive)));
+    }
+}
+
+/// System to spawn the empty threat bar
+fn spawn_threat_bar(mut commands: Commands) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(0.0),
+            width: Val::Percent(100.0),
+            height: Val::Px(BAR_HEIGHT),
+            ..default()
+        },
+        BackgroundColor(BAR_BACKGROUND),
+        ThreatBar,
+    ));
+}
+
+/// System that marks every enemy still above the player, and the next spawn
+/// as its timer runs down, at their position across the screen
+fn update_threat_bar(
+    mut commands: Commands,
+    upcoming: Res<UpcomingSpawn>,
+    spawn_timer: Res<EnemySpawnTimer>,
+    enemy_query: Query<(&Transform, Option<&EnemyKind>), (With<Enemy>, Without<Dying>)>,
+    player_query: Query<&Transform, With<Player>>,
+    bar_query: Query<Entity, With<ThreatBar>>,
+    mut marker_query: Query<(&mut Node, &mut BackgroundColor), With<ThreatMarker>>,
+    window: Res<WindowMetrics>,
+) {
+    let (Ok(bar), Ok(player)) = (bar_query.single(), player_query.single()) else {
+        return;
+    };
+    let half_width = window.width() / 2.0;
+    let top = window.height() / 2.0;
+    let player_y = player.translation.y;
+
+    let mut markers: Vec<(f32, Color)> = Vec::new();
+    if let Some(fraction) = upcoming.0 {
+        markers.push((
+            fraction,
+            UPCOMING_COLOR.with_alpha(spawn_timer.0.fraction()),
+        ));
+    }
+    markers.extend(
+        enemy_query
+            .iter()
+            .filter(|(transform, _)| transform.translation.y > player_y)
+            .map(|(transform, kind)| {
+                let fraction = (transform.translation.x + half_width) / window.width();
+                // Brighter as the enemy closes in on the player
+                let closeness =
+                    1.0 - (transform.translation.y - player_y) / (top - player_y).max(1.0);
+                let alpha = FAR_ALPHA + (1.0 - FAR_ALPHA) * closeness.clamp(0.0, 1.0);
+                let color = kind.copied().unwrap_or(EnemyKind::Basic).color();
+                (fraction.clamp(0.0, 1.0), color.with_alpha(alpha))
+            })
+            .take(MAX_MARKERS),
+    );
+
+    let mut existing = marker_query.iter_mut();
+    for &(fraction, color) in &markers {
+        let left = Val::Percent(fraction * 100.0);
+        match existing.next() {
+            Some((mut node, mut background)) => {
+                node.display = Display::Flex;
+                node.left = left;
+                background.0 = color;
+            }
+            None => {
+                commands.entity(bar).with_child((
+                    Node {
+                        position_type: PositionType::Absolute,
+                        left,
+                        margin: UiRect::left(Val::Px(-MARKER_WIDTH / 2.0)),
+                        width: Val::Px(MARKER_WIDTH),
+                        height: Val::Percent(100.0),
+                        ..default()
+                    },
+                    BackgroundColor(color),
+                    ThreatMarker,
+                ));
+            }
+        }
+    }
+    // Leftover markers wait hidden for the next busy moment
+    for (mut node, _) in existing {
+        node.display = Display::None;
+    }
+}
@@ -0,0 +1,294 @@
+use std::collections::{BTreeMap, HashSet};
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpStream;
+use std::sync::Mutex;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+
+use bevy::input::common_conditions::input_just_pressed;
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::difficulty::Difficulty;
+use crate::hud_layout::{HudAnchor, HudSlot};
+use crate::mutator::{self, Mutator, RunMutators};
+use crate::reset::{RunScoped, RunSetup};
+use crate::save;
+use crate::sets::RunSetupSet;
+use crate::swarm::SummonSwarm;
+use crate::text_style::TextStyleLibrary;
+use crate::{GameState, RunPhase};
+
+// Twitch constants
+const TWITCH_CONFIG_FILE: &str = "twitch.ron"; // In the data directory
+const IRC_ADDRESS: &str = "irc.chat.twitch.tv:6667";
+const ANONYMOUS_NICK: &str = "justinfan31337"; // Read-only login that needs no token
+const RECONNECT_DELAY: Duration = Duration::from_secs(10);
+const TOGGLE_KEY: KeyCode = KeyCode::F6;
+
+/// Contents of `twitch.ron`; chat is only joined when `channel` is set.
+#[derive(Deserialize)]
+#[serde(default)]
+struct TwitchConfig {
+    channel: Option<String>,
+    /// Seconds between two swarms summoned from chat.
+    swarm_cooldown: f32,
+}
+
+impl Default for TwitchConfig {
+    fn default() -> Self {
+        Self {
+            channel: None,
+            swarm_cooldown: 60.0,
+        }
+    }
+}
+
+/// A command typed by a viewer.
+enum ChatCommand {
+    Vote { user: String, mutator: Mutator },
+    Swarm,
+}
+
+// --- Components ---
+
+#[derive(Component)]
+#[require(RunScoped)]
+struct ChatStatus;
+
+// --- Resources ---
+
+/// Commands coming in from the chat thread.
+#[derive(Resource)]
+struct ChatLink {
+    channel: String,
+    commands: Mutex<Receiver<ChatCommand>>,
+}
+
+/// What chat has asked for so far, and whether it's listened to.
+#[derive(Resource)]
+struct ChatControl {
+    enabled: bool,
+    /// Votes for the mutator added to the next run.
+    votes: BTreeMap<Mutator, u32>,
+    /// Viewers who already voted for the next run, one vote each.
+    voters: HashSet<String>,
+    swarm_cooldown: Timer,
+}
+
+pub struct TwitchPlugin;
+
+impl Plugin for TwitchPlugin {
+    fn build(&self, app: &mut App) {
+        let config: TwitchConfig =
+            save::load_or_default(&save::data_dir().join(TWITCH_CONFIG_FILE));
+        let Some(channel) = config.channel else {
+            return;
+        };
+        let channel = channel.trim_start_matches('#').to_lowercase();
+        let (sender, receiver) = mpsc::channel();
+        spawn_chat_thread(channel.clone(), sender);
+
+        let mut swarm_cooldown = Timer::from_seconds(config.swarm_cooldown, TimerMode::Once);
+        // The first summon of a session needn't wait
+        swarm_cooldown.tick(swarm_cooldown.duration());
+        app.insert_resource(ChatLink {
+            channel,
+            commands: Mutex::new(receiver),
+        })
+        .insert_resource(ChatControl {
+            enabled: true,
+            votes: BTreeMap::new(),
+            voters: HashSet::new(),
+            swarm_cooldown,
+        })
+        .add_systems(
+            RunSetup,
+            (
+                apply_chat_vote.in_set(RunSetupSet::DifficultyOverrides),
+                spawn_chat_status,
+            ),
+        )
+        .add_systems(
+            Update,
+            (
+                toggle_chat_control
+                    .run_if(in_state(GameState::Playing).and(input_just_pressed(TOGGLE_KEY))),
+                read_chat_commands,
+                update_chat_status.run_if(in_state(GameState::Playing)),
+            )
+                .chain(),
+        );
+    }
+}
+
+/// Joins the channel's chat in the background, reconnecting when dropped, and
+/// forwards viewer commands until the game stops listening.
+fn spawn_chat_thread(channel: String, sender: Sender<ChatCommand>) {
+    thread::spawn(move || {
+        loop {
+            match listen(&channel, &sender) {
+                Ok(()) => return,
+                Err(err) => warn!(%channel, "Lost Twitch chat, reconnecting: {err}"),
+            }
+            thread::sleep(RECONNECT_DELAY);
+        }
+    });
+}
+
+/// Reads chat until the connection drops (`Err`) or the game is gone (`Ok`).
+fn listen(channel: &str, sender: &Sender<ChatCommand>) -> std::io::Result<()> {
+    let mut stream = TcpStream::connect(IRC_ADDRESS)?;
+    write!(stream, "NICK {ANONYMOUS_NICK}\r\nJOIN #{channel}\r\n")?;
+    info!(%channel, "Joined Twitch chat");
+
+    let reader = BufReader::new(stream.try_clone()?);
+    for line in reader.lines() {
+        let line = line?;
+        if let Some(server) = line.strip_prefix("PING ") {
+            write!(stream, "PONG {server}\r\n")?;
+            continue;
+        }
+        let Some(command) = parse_message(&line) else {
+            continue;
+        };
+        if sender.send(command).is_err() {
+            return Ok(());
+        }
+    }
+    Err(std::io::ErrorKind::UnexpectedEof.into())
+}
+
+/// Parses `:user!user@host PRIVMSG #channel :!command args` into a command.
+fn parse_message(line: &str) -> Option<ChatCommand> {
+    let (prefix, rest) = line.strip_prefix(':')?.split_once(' ')?;
+    let (_, text) = rest.strip_prefix("PRIVMSG ")?.split_once(" :")?;
+    let user = prefix.split('!').next()?.to_string();
+
+    let mut words = text.split_whitespace();
+    match words.next()? {
+        "!swarm" => Some(ChatCommand::Swarm),
+        "!vote" => {
+            let wanted: String = words.collect::<String>().to_lowercase();
+            let mutator = Mutator::ALL.into_iter().find(|mutator| {
+                let name = mutator.name().replace(' ', "").to_lowercase();
+                !wanted.is_empty() && name.starts_with(&wanted)
+            })?;
+            Some(ChatCommand::Vote { user, mutator })
+        }
+        _ => None,
+    }
+}
+
+/// System to switch chat control on and off mid-run
+fn toggle_chat_control(mut control: ResMut<ChatControl>) {
+    control.enabled = !control.enabled;
+    info!(enabled = control.enabled, "Chat control toggled");
+}
+
+/// System that tallies votes and summons swarms, within the cooldown
+fn read_chat_commands(
+    time: Res<Time>,
+    link: Res<ChatLink>,
+    phase: Option<Res<State<RunPhase>>>,
+    mut control: ResMut<ChatControl>,
+    mut summons: EventWriter<SummonSwarm>,
+) {
+    control.swarm_cooldown.tick(time.delta());
+    let Ok(commands) = link.commands.lock() else {
+        return;
+    };
+    // Drain even while disabled, so old commands don't all land on re-enabling
+    for command in commands.try_iter() {
+        if !control.enabled {
+            continue;
+        }
+        match command {
+            ChatCommand::Vote { user, mutator } => {
+                if control.voters.insert(user) {
+                    *control.votes.entry(mutator).or_default() += 1;
+                }
+            }
+            ChatCommand::Swarm => {
+                let alive = phase.is_some_and(|phase| *phase.get() == RunPhase::Alive);
+                if alive && control.swarm_cooldown.finished() {
+                    summons.write(SummonSwarm);
+                    control.swarm_cooldown.reset();
+                    info!("Chat summoned a swarm");
+                }
+            }
+        }
+    }
+}
+
+/// The mutator with the most votes; ties go to the earlier mutator.
+fn leading_vote(votes: &BTreeMap<Mutator, u32>) -> Option<(Mutator, u32)> {
+    votes
+        .iter()
+        .rev()
+        .max_by_key(|&(_, &count)| count)
+        .map(|(&mutator, &count)| (mutator, count))
+}
+
+/// System that adds chat's pick to the run's mutators and opens a new vote
+fn apply_chat_vote(
+    mut control: ResMut<ChatControl>,
+    mut run_mutators: ResMut<RunMutators>,
+    mut difficulty: ResMut<Difficulty>,
+) {
+    let winner = leading_vote(&control.votes);
+    control.votes.clear();
+    control.voters.clear();
+    let Some((mutator, count)) = winner else {
+        return;
+    };
+    if !control.enabled || run_mutators.0.contains(&mutator) {
+        return;
+    }
+    run_mutators.0.push(mutator);
+    mutator::apply(&[mutator], &mut difficulty.config);
+    info!(
+        mutator = mutator.name(),
+        votes = count,
+        "Chat voted in a mutator"
+    );
+}
+
+/// System to spawn the chat line in the bottom-left corner
+fn spawn_chat_status(mut commands: Commands, styles: Res<TextStyleLibrary>) {
+    commands.spawn((
+        Text::default(),
+        styles.hud.ui(),
+        HudSlot::new(HudAnchor::BottomLeft, 0),
+        ChatStatus,
+    ));
+}
+
+/// System that shows the channel, whether chat is listened to and the leading vote
+fn update_chat_status(
+    link: Res<ChatLink>,
+    control: Res<ChatControl>,
+    mut query: Query<&mut Text, With<ChatStatus>>,
+) {
+    let Ok(mut text) = query.single_mut() else {
+        return;
+    };
+    if !control.enabled {
+        text.0 = format!("Chat #{}: off ({TOGGLE_KEY:?})", link.channel);
+        return;
+    }
+    let vote = match leading_vote(&control.votes) {
+        Some((mutator, count)) => format!("next: {} ({count})", mutator.name()),
+        None => "!vote <mutator>".to_string(),
+    };
+    let swarm = if control.swarm_cooldown.finished() {
+        "!swarm ready".to_string()
+    } else {
+        format!(
+            "!swarm in {:.0}s",
+            control.swarm_cooldown.remaining_secs().ceil()
+        )
+    };
+    text.0 = format!("Chat #{}: {vote}, {swarm}", link.channel);
+}
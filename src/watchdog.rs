@@ -0,0 +1,121 @@
+use bevy::ecs::archetype::{Archetype, Archetypes};
+use bevy::ecs::component::Components;
+use bevy::ecs::entity::Entities;
+use bevy::prelude::*;
+
+use crate::despawn::DespawnQueue;
+
+// Watchdog constants
+const CENSUS_INTERVAL: f32 = 10.0; // Real seconds between two entity counts
+const CENSUS_TOP: usize = 6; // Most populated archetypes listed in each count
+const LEAK_AGE: f32 = 30.0; // Seconds a transient entity can last before it counts as leaked
+
+// --- Components ---
+
+/// Marks effects that always go away on their own within a few seconds, like
+/// floating text or the bomb's shockwave. The watchdog removes any still
+/// around long after that, in case a clean-up system misses them.
+#[derive(Component, Default)]
+pub struct Transient {
+    age: f32,
+}
+
+// --- Resources ---
+
+/// How many entities there were at the last count, in total and in the most
+/// populated archetypes, named after their components.
+#[derive(Resource, Default)]
+pub struct EntityCensus {
+    pub total: u32,
+    pub archetypes: Vec<(String, usize)>,
+}
+
+#[derive(Resource)]
+struct CensusTimer(Timer);
+
+pub struct WatchdogPlugin;
+
+impl Plugin for WatchdogPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EntityCensus>()
+            .insert_resource(CensusTimer(Timer::from_seconds(
+                CENSUS_INTERVAL,
+                TimerMode::Repeating,
+            )))
+            .add_systems(Update, (take_census, despawn_leaked_transients));
+    }
+}
+
+/// An archetype's components by their type names without module paths,
+/// sorted so the same set always reads the same way.
+fn describe(archetype: &Archetype, components: &Components) -> String {
+    let mut names: Vec<&str> = archetype
+        .components()
+        .filter_map(|id| components.get_info(id))
+        .map(|info| info.name().rsplit("::").next().unwrap_or(info.name()))
+        .collect();
+    names.sort_unstable();
+    names.join(", ")
+}
+
+/// System that counts the entities in every archetype every few seconds and
+/// logs the busiest ones
+fn take_census(
+    real_time: Res<Time<Real>>,
+    mut timer: ResMut<CensusTimer>,
+    mut census: ResMut<EntityCensus>,
+    entities: &Entities,
+    archetypes: &Archetypes,
+    components: &Components,
+) {
+    if !timer.0.tick(real_time.delta()).just_finished() {
+        return;
+    }
+    let mut counts: Vec<(String, usize)> = archetypes
+        .iter()
+        .filter(|archetype| !archetype.is_empty())
+        .map(|archetype| (describe(archetype, components), archetype.len() as usize))
+        .collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1));
+    counts.truncate(CENSUS_TOP);
+
+    census.total = entities.len();
+    census.archetypes = counts;
+    debug!(
+        total = census.total,
+        archetypes = archetypes.len(),
+        "Entity census"
+    );
+    for (components, count) in &census.archetypes {
+        debug!(count, components = %components, "Entity census archetype");
+    }
+}
+
+/// System that ages transient entities and removes any that have outlived
+/// any effect's lifetime, warning about them so the leak gets fixed
+fn despawn_leaked_transients(
+    time: Res<Time>,
+    mut despawn_queue: ResMut<DespawnQueue>,
+    entities: &Entities,
+    archetypes: &Archetypes,
+    components: &Components,
+    mut query: Query<(Entity, &mut Transient)>,
+) {
+    for (entity, mut transient) in &mut query {
+        transient.age += time.delta_secs();
+        if transient.age < LEAK_AGE {
+            continue;
+        }
+        let components = entities
+            .get(entity)
+            .map(|location| describe(&archetypes[location.archetype_id], components))
+            .unwrap_or_default();
+        warn!(
+            ?entity,
+            components = %components,
+            age = transient.age,
+            "Despawned a leaked transient entity"
+        );
+        despawn_queue.push(entity);
+    }
+}
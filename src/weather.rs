@@ -0,0 +1,257 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::GameState;
+use crate::reset::{RunScoped, RunSetup};
+use crate::settings;
+use crate::window::WindowMetrics;
+
+// Weather constants
+const WIND_STRENGTH: f32 = 60.0; // Steady push of the wind, in pixels per second
+const GUST_STRENGTH: f32 = 40.0; // Extra push at the peak of a gust
+const RAIN_COLOR: Color = Color::srgba(0.6, 0.7, 0.9, 0.35);
+const SNOW_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.7);
+const STAR_COLOR: Color = Color::srgba(1.0, 1.0, 0.95, 0.5);
+
+/// The particle layers a run can have.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WeatherKind {
+    Rain,
+    Snow,
+    Starfield,
+}
+
+impl WeatherKind {
+    const ALL: [WeatherKind; 3] = [WeatherKind::Rain, WeatherKind::Snow, WeatherKind::Starfield];
+
+    fn particle_count(self) -> usize {
+        match self {
+            WeatherKind::Rain => 220,
+            WeatherKind::Snow => 160,
+            WeatherKind::Starfield => 120,
+        }
+    }
+
+    /// Particle size range, in pixels.
+    fn size(self) -> (Vec2, Vec2) {
+        match self {
+            WeatherKind::Rain => (Vec2::new(1.0, 10.0), Vec2::new(2.0, 18.0)),
+            WeatherKind::Snow => (Vec2::splat(2.0), Vec2::splat(5.0)),
+            WeatherKind::Starfield => (Vec2::splat(1.0), Vec2::splat(2.5)),
+        }
+    }
+
+    /// Fall speed range, in pixels per second.
+    fn fall_speed(self) -> (f32, f32) {
+        match self {
+            WeatherKind::Rain => (500.0, 750.0),
+            WeatherKind::Snow => (40.0, 90.0),
+            WeatherKind::Starfield => (10.0, 60.0),
+        }
+    }
+
+    /// How much the wind moves a particle; stars are far enough not to care.
+    fn wind_weight(self) -> f32 {
+        match self {
+            WeatherKind::Rain => 0.6,
+            WeatherKind::Snow => 1.5,
+            WeatherKind::Starfield => 0.0,
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            WeatherKind::Rain => RAIN_COLOR,
+            WeatherKind::Snow => SNOW_COLOR,
+            WeatherKind::Starfield => STAR_COLOR,
+        }
+    }
+
+    /// Stars sit behind everything; rain and snow fall just behind the enemies.
+    fn z(self) -> f32 {
+        match self {
+            WeatherKind::Rain | WeatherKind::Snow => -0.1,
+            WeatherKind::Starfield => -0.95,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            WeatherKind::Rain => "Rain",
+            WeatherKind::Snow => "Snow",
+            WeatherKind::Starfield => "Starfield",
+        }
+    }
+}
+
+// --- Components ---
+
+#[derive(Component)]
+#[require(RunScoped)]
+struct WeatherParticle {
+    fall: f32,
+    wind_weight: f32,
+}
+
+/// Lets the wind push a light, purely cosmetic entity around, scaled by the
+/// weight. Never put it on anything that takes part in gameplay.
+#[derive(Component)]
+pub struct WindBlown(pub f32);
+
+// --- Resources ---
+
+/// The weather picked on the menu.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WeatherChoice {
+    /// A different weather every run.
+    #[default]
+    Random,
+    Clear,
+    Fixed(WeatherKind),
+}
+
+impl WeatherChoice {
+    pub fn next(self) -> Self {
+        match self {
+            WeatherChoice::Random => WeatherChoice::Clear,
+            WeatherChoice::Clear => WeatherChoice::Fixed(WeatherKind::Rain),
+            WeatherChoice::Fixed(WeatherKind::Rain) => WeatherChoice::Fixed(WeatherKind::Snow),
+            WeatherChoice::Fixed(WeatherKind::Snow) => WeatherChoice::Fixed(WeatherKind::Starfield),
+            WeatherChoice::Fixed(WeatherKind::Starfield) => WeatherChoice::Random,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            WeatherChoice::Random => "Random",
+            WeatherChoice::Clear => "Clear",
+            WeatherChoice::Fixed(kind) => kind.name(),
+        }
+    }
+}
+
+/// The wind blowing across the screen right now.
+#[derive(Resource, Default)]
+pub struct Wind(pub Vec2);
+
+/// One shared mesh and a material per weather kind, so every particle of a
+/// layer draws in the same batch.
+#[derive(Resource)]
+struct WeatherAssets {
+    quad: Handle<Mesh>,
+    materials: [Handle<ColorMaterial>; 3],
+}
+
+impl FromWorld for WeatherAssets {
+    fn from_world(world: &mut World) -> Self {
+        let quad = world
+            .resource_mut::<Assets<Mesh>>()
+            .add(Rectangle::new(1.0, 1.0));
+        let mut materials = world.resource_mut::<Assets<ColorMaterial>>();
+        Self {
+            quad,
+            materials: WeatherKind::ALL
+                .map(|kind| materials.add(ColorMaterial::from_color(kind.color()))),
+        }
+    }
+}
+
+pub struct WeatherPlugin;
+
+impl Plugin for WeatherPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WeatherChoice>()
+            .init_resource::<Wind>()
+            .init_resource::<WeatherAssets>()
+            .add_systems(RunSetup, start_weather.run_if(settings::motion_enabled))
+            .add_systems(
+                Update,
+                (blow_wind, move_particles, blow_light_entities)
+                    .chain()
+                    .run_if(in_state(GameState::Playing).and(settings::motion_enabled)),
+            );
+    }
+}
+
+/// System to fill the screen with the run's weather particles
+fn start_weather(
+    mut commands: Commands,
+    choice: Res<WeatherChoice>,
+    weather_assets: Res<WeatherAssets>,
+    window: Res<WindowMetrics>,
+) {
+    // Weather is cosmetic, so it rolls on its own generator and leaves the
+    // seeded run stream alone
+    let mut rng = rand::rng();
+    let kind = match *choice {
+        WeatherChoice::Random => WeatherKind::ALL[rng.random_range(0..WeatherKind::ALL.len())],
+        WeatherChoice::Clear => return,
+        WeatherChoice::Fixed(kind) => kind,
+    };
+    info!(weather = kind.name(), "Weather");
+
+    let material = weather_assets.materials[kind as usize].clone();
+    let half_size = Vec2::new(window.width(), window.height()) / 2.0;
+    let (min_size, max_size) = kind.size();
+    let (min_fall, max_fall) = kind.fall_speed();
+    for _ in 0..kind.particle_count() {
+        let size = min_size.lerp(max_size, rng.random());
+        let position = Vec2::new(
+            rng.random_range(-half_size.x..half_size.x),
+            rng.random_range(-half_size.y..half_size.y),
+        );
+        commands.spawn((
+            Mesh2d(weather_assets.quad.clone()),
+            MeshMaterial2d(material.clone()),
+            Transform::from_translation(position.extend(kind.z())).with_scale(size.extend(1.0)),
+            WeatherParticle {
+                fall: rng.random_range(min_fall..max_fall),
+                wind_weight: kind.wind_weight(),
+            },
+        ));
+    }
+}
+
+/// System that lets the wind rise and fall in slow gusts
+fn blow_wind(time: Res<Time>, mut wind: ResMut<Wind>) {
+    let t = time.elapsed_secs();
+    let gust = (t * 0.7).sin().max(0.0) * (t * 0.23).sin().abs();
+    wind.0 = Vec2::new((t * 0.11).sin() * WIND_STRENGTH + gust * GUST_STRENGTH, 0.0);
+}
+
+/// System that moves the weather down the screen, wrapping it around the edges
+/// so the same particles are reused for the whole run
+fn move_particles(
+    time: Res<Time>,
+    wind: Res<Wind>,
+    mut query: Query<(&mut Transform, &WeatherParticle)>,
+    window: Res<WindowMetrics>,
+) {
+    let half_size = Vec2::new(window.width(), window.height()) / 2.0;
+    for (mut transform, particle) in &mut query {
+        let velocity = Vec2::new(0.0, -particle.fall) + wind.0 * particle.wind_weight;
+        let mut position = transform.translation.truncate() + velocity * time.delta_secs();
+        if position.y < -half_size.y {
+            position.y += half_size.y * 2.0;
+        }
+        if position.x < -half_size.x {
+            position.x += half_size.x * 2.0;
+        } else if position.x > half_size.x {
+            position.x -= half_size.x * 2.0;
+        }
+        transform.translation = position.extend(transform.translation.z);
+        // Streaks lean into the wind
+        transform.rotation = Quat::from_rotation_z(velocity.x.atan2(-velocity.y));
+    }
+}
+
+/// System that pushes wind-blown entities along with the weather
+fn blow_light_entities(
+    time: Res<Time>,
+    wind: Res<Wind>,
+    mut query: Query<(&mut Transform, &WindBlown)>,
+) {
+    for (mut transform, blown) in &mut query {
+        transform.translation += (wind.0 * blown.0 * time.delta_secs()).extend(0.0);
+    }
+}
@@ -0,0 +1,291 @@
+use std::fs;
+
+use bevy::prelude::*;
+use bevy::window::{
+    Monitor, PrimaryWindow, WindowMoved, WindowPosition, WindowResizeConstraints, WindowResized,
+    WindowResolution,
+};
+use bevy::winit::WinitWindows;
+use serde::{Deserialize, Serialize};
+use winit::window::Icon;
+
+use crate::camera::PLAY_AREA;
+use crate::replay::replay_rendering;
+use crate::save;
+use crate::settings::Settings;
+
+// Window constants
+const WINDOW_TITLE: &str = "Rusty Dodger";
+// App ID: the Wayland app ID and X11 WM_CLASS that docks and taskbars group by
+const APP_ID: &str = "rusty_dodger";
+const ICON_PNG: &[u8] = include_bytes!("../assets/icon.png");
+const PLACEMENT_FILE: &str = "window.ron"; // In the data directory, shared by all profiles
+const SAVE_DELAY: f32 = 0.5; // Seconds without window changes before the placement is saved
+const MIN_WINDOW_SIZE: Vec2 = Vec2::new(320.0, 180.0); // Logical; any smaller and the HUD can't be read
+
+// --- Resources ---
+
+/// Where the window was and how big it was, restored at startup.
+#[derive(Resource, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+struct WindowPlacement {
+    /// Physical position of the top-left corner on the virtual desktop.
+    position: Option<(i32, i32)>,
+    /// Logical size.
+    size: Option<(f32, f32)>,
+    /// Name of the monitor the window was on.
+    monitor: Option<String>,
+}
+
+impl WindowPlacement {
+    fn load() -> Self {
+        save::load_or_default(&save::data_dir().join(PLACEMENT_FILE))
+    }
+}
+
+/// Counts down to saving the placement once the window stops moving.
+#[derive(Resource, Default)]
+struct PendingPlacementSave(Option<Timer>);
+
+/// The play area's size and the primary window's logical size, as of the last
+/// frame it could be read. Gameplay goes by the play area, which stays the
+/// same on every aspect ratio and is scaled to fit the window, instead of
+/// querying windows, so extra windows or a primary window that's briefly
+/// gone can't trip it up.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct WindowMetrics {
+    pub size: Vec2,
+    pub window: Vec2,
+}
+
+impl Default for WindowMetrics {
+    fn default() -> Self {
+        let resolution = WindowResolution::default();
+        Self {
+            size: PLAY_AREA,
+            window: Vec2::new(resolution.width(), resolution.height()),
+        }
+    }
+}
+
+impl WindowMetrics {
+    pub fn width(&self) -> f32 {
+        self.size.x
+    }
+
+    pub fn height(&self) -> f32 {
+        self.size.y
+    }
+}
+
+/// The primary window as the game opens it, placed where it was last session.
+pub fn primary_window() -> Window {
+    let placement = WindowPlacement::load();
+    let mut window = Window {
+        title: WINDOW_TITLE.to_string(),
+        name: Some(APP_ID.to_string()),
+        resize_constraints: WindowResizeConstraints {
+            min_width: MIN_WINDOW_SIZE.x,
+            min_height: MIN_WINDOW_SIZE.y,
+            ..default()
+        },
+        ..default()
+    };
+    // A placement saved while the window was minimised can be zero-sized
+    if let Some((width, height)) = placement.size {
+        let size = Vec2::new(width, height).max(MIN_WINDOW_SIZE);
+        window.resolution = WindowResolution::new(size.x, size.y);
+    }
+    if let Some((x, y)) = placement.position {
+        window.position = WindowPosition::At(IVec2::new(x, y));
+    }
+    window
+}
+
+pub struct GameWindowPlugin;
+
+impl Plugin for GameWindowPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(WindowPlacement::load())
+            .init_resource::<PendingPlacementSave>()
+            .init_resource::<WindowMetrics>()
+            .add_systems(PreUpdate, update_window_metrics)
+            .add_systems(
+                Update,
+                (
+                    set_window_icon,
+                    check_saved_monitor,
+                    // The hidden window replays render behind isn't the player's
+                    track_window_placement.run_if(not(replay_rendering)),
+                    save_window_placement,
+                    reset_window_placement.run_if(resource_changed::<Settings>),
+                ),
+            );
+    }
+}
+
+/// System that picks up the primary window's size, keeping the last one
+/// while there's no primary window to read or it's minimised
+fn update_window_metrics(
+    mut metrics: ResMut<WindowMetrics>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+) {
+    let Ok(window) = window_query.single() else {
+        return;
+    };
+    let size = Vec2::new(window.width(), window.height());
+    if size.min_element() > 0.0 && metrics.window != size {
+        metrics.window = size;
+    }
+}
+
+/// Decodes the embedded icon into the RGBA pixels `winit` expects.
+fn load_icon() -> Result<Icon, String> {
+    let image = image::load_from_memory(ICON_PNG)
+        .map_err(|err| err.to_string())?
+        .into_rgba8();
+    let (width, height) = image.dimensions();
+    Icon::from_rgba(image.into_raw(), width, height).map_err(|err| err.to_string())
+}
+
+/// System that gives the primary window its icon once `winit` has created it.
+/// macOS ignores window icons; the dock uses the app bundle's icon there.
+fn set_window_icon(
+    mut done: Local<bool>,
+    winit_windows: NonSend<WinitWindows>,
+    window_query: Query<Entity, With<PrimaryWindow>>,
+) {
+    if *done {
+        return;
+    }
+    let Ok(entity) = window_query.single() else {
+        return;
+    };
+    let Some(window) = winit_windows.get_window(entity) else {
+        return;
+    };
+
+    match load_icon() {
+        Ok(icon) => window.set_window_icon(Some(icon)),
+        Err(err) => warn!("Could not load the window icon: {err}"),
+    }
+    *done = true;
+}
+
+/// The monitor whose area contains a physical desktop position.
+fn monitor_at<'a>(
+    monitors: impl IntoIterator<Item = &'a Monitor>,
+    position: IVec2,
+) -> Option<&'a Monitor> {
+    monitors.into_iter().find(|monitor| {
+        let min = monitor.physical_position;
+        let max = min
+            + IVec2::new(
+                monitor.physical_width as i32,
+                monitor.physical_height as i32,
+            );
+        position.cmpge(min).all() && position.cmplt(max).all()
+    })
+}
+
+/// System that moves the window back to the primary monitor if the one it was
+/// saved on is gone, so it never opens off-screen
+fn check_saved_monitor(
+    mut done: Local<bool>,
+    placement: Res<WindowPlacement>,
+    monitor_query: Query<&Monitor>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    // Monitors show up once winit has started
+    if *done || monitor_query.is_empty() {
+        return;
+    }
+    *done = true;
+    let Some((x, y)) = placement.position else {
+        return;
+    };
+
+    let found = monitor_at(&monitor_query, IVec2::new(x, y));
+    let same_monitor = match (&placement.monitor, found) {
+        (Some(saved), Some(monitor)) => monitor.name.as_ref() == Some(saved),
+        (None, found) => found.is_some(),
+        (Some(_), None) => false,
+    };
+    if !same_monitor && let Ok(mut window) = window_query.single_mut() {
+        info!("Saved window monitor is gone, centering on the primary monitor");
+        window.position = WindowPosition::Centered(MonitorSelection::Primary);
+    }
+}
+
+/// System that follows window moves and resizes, scheduling a save
+fn track_window_placement(
+    mut moved_events: EventReader<WindowMoved>,
+    mut resized_events: EventReader<WindowResized>,
+    settings: Res<Settings>,
+    mut placement: ResMut<WindowPlacement>,
+    mut pending: ResMut<PendingPlacementSave>,
+    monitor_query: Query<&Monitor>,
+    primary_query: Query<Entity, With<PrimaryWindow>>,
+) {
+    let Ok(primary) = primary_query.single() else {
+        return;
+    };
+    let mut updated = placement.clone();
+    for event in moved_events.read().filter(|event| event.window == primary) {
+        updated.position = Some((event.position.x, event.position.y));
+        updated.monitor = monitor_at(&monitor_query, event.position).and_then(|m| m.name.clone());
+    }
+    for event in resized_events
+        .read()
+        .filter(|event| event.window == primary && event.width > 0.0 && event.height > 0.0)
+    {
+        updated.size = Some((event.width, event.height));
+    }
+
+    if settings.remember_window && updated != *placement {
+        *placement = updated;
+        pending.0 = Some(Timer::from_seconds(SAVE_DELAY, TimerMode::Once));
+    }
+}
+
+/// System that writes the placement once the window has settled
+fn save_window_placement(
+    time: Res<Time<Real>>,
+    placement: Res<WindowPlacement>,
+    mut pending: ResMut<PendingPlacementSave>,
+) {
+    let Some(timer) = &mut pending.0 else {
+        return;
+    };
+    if !timer.tick(time.delta()).finished() {
+        return;
+    }
+    pending.0 = None;
+    if let Err(err) = save::store(&save::data_dir().join(PLACEMENT_FILE), &*placement) {
+        warn!("Could not save the window placement: {err}");
+    }
+}
+
+/// System that forgets and undoes the saved placement when the setting is off
+fn reset_window_placement(
+    settings: Res<Settings>,
+    mut placement: ResMut<WindowPlacement>,
+    mut pending: ResMut<PendingPlacementSave>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    if settings.remember_window || *placement == WindowPlacement::default() {
+        return;
+    }
+    *placement = WindowPlacement::default();
+    pending.0 = None;
+    let _ = fs::remove_file(save::data_dir().join(PLACEMENT_FILE));
+
+    if let Ok(mut window) = window_query.single_mut() {
+        let default_window = Window::default();
+        window.resolution.set(
+            default_window.resolution.width(),
+            default_window.resolution.height(),
+        );
+        window.position = WindowPosition::Centered(MonitorSelection::Primary);
+    }
+}
@@ -0,0 +1,106 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::camera::GameCamera;
+use crate::data;
+use crate::difficulty::Difficulty;
+use crate::graze::NearMiss;
+use crate::reset::RunSetup;
+use crate::settings;
+use crate::stats::RunStats;
+use crate::{GameState, RunPhase};
+
+const CAMERA_FILE: &str = "camera.ron";
+const BUILTIN_CAMERA: &str = include_str!("../assets/camera.ron");
+
+/// Camera zoom limits, loaded from `assets/camera.ron`.
+#[derive(Debug, Clone, Deserialize)]
+struct ZoomConfig {
+    /// Scale reached once the difficulty has finished ramping up.
+    max_zoom_out: f32,
+    /// Scale of the brief punch-in on a near miss.
+    punch_zoom: f32,
+    punch_seconds: f32,
+    /// How quickly the camera eases toward its target, per second.
+    ease_rate: f32,
+}
+
+// --- Resources ---
+
+/// Smoothly drives the camera's zoom during a run.
+#[derive(Resource)]
+struct CameraZoom {
+    config: ZoomConfig,
+    punch: Timer,
+}
+
+impl Default for CameraZoom {
+    fn default() -> Self {
+        let config: ZoomConfig = data::load_ron(CAMERA_FILE, BUILTIN_CAMERA);
+        let mut punch = Timer::from_seconds(config.punch_seconds, TimerMode::Once);
+        // Start out with no punch-in playing
+        punch.tick(punch.duration());
+        Self { config, punch }
+    }
+}
+
+pub struct ZoomPlugin;
+
+impl Plugin for ZoomPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CameraZoom>()
+            .add_systems(RunSetup, reset_camera_zoom)
+            .add_systems(
+                Update,
+                update_camera_zoom.run_if(in_state(RunPhase::Alive).and(settings::motion_enabled)),
+            )
+            .add_systems(OnExit(GameState::Playing), reset_camera_zoom);
+    }
+}
+
+/// System to put the camera back at its normal zoom
+fn reset_camera_zoom(
+    mut zoom: ResMut<CameraZoom>,
+    mut projection_query: Query<&mut Projection, With<GameCamera>>,
+) {
+    let duration = zoom.punch.duration();
+    zoom.punch.tick(duration);
+    if let Ok(mut projection) = projection_query.single_mut()
+        && let Projection::Orthographic(ortho) = &mut *projection
+    {
+        ortho.scale = 1.0;
+    }
+}
+
+/// System that zooms out as the difficulty ramps up and punches in on near misses
+fn update_camera_zoom(
+    real_time: Res<Time<Real>>,
+    time: Res<Time>,
+    difficulty: Res<Difficulty>,
+    stats: Res<RunStats>,
+    mut zoom: ResMut<CameraZoom>,
+    mut near_misses: EventReader<NearMiss>,
+    mut projection_query: Query<&mut Projection, With<GameCamera>>,
+) {
+    if near_misses.read().count() > 0 {
+        zoom.punch.reset();
+    }
+    zoom.punch.tick(time.delta());
+
+    let ramp = &difficulty.config.spawn;
+    let progress = (stats.elapsed() / ramp.ramp_seconds.max(f32::EPSILON)).clamp(0.0, 1.0);
+    let config = &zoom.config;
+    let mut target = 1.0 + (config.max_zoom_out - 1.0) * progress;
+    if !zoom.punch.finished() {
+        target *= config.punch_zoom;
+    }
+
+    let Ok(mut projection) = projection_query.single_mut() else {
+        return;
+    };
+    if let Projection::Orthographic(ortho) = &mut *projection {
+        // Eased on real time so hitches don't make the camera jump
+        let blend = 1.0 - (-config.ease_rate * real_time.delta_secs()).exp();
+        ortho.scale += (target - ortho.scale) * blend;
+    }
+}